@@ -14,6 +14,7 @@ use anyhow::ensure;
 use holochain_conductor_api::AdminInterfaceConfig;
 use holochain_conductor_api::AdminRequest;
 use holochain_conductor_api::AdminResponse;
+use holochain_conductor_api::InstalledAppInfo;
 use holochain_conductor_api::InterfaceDriver;
 use holochain_p2p::kitsune_p2p;
 use holochain_p2p::kitsune_p2p::agent_store::AgentInfoSigned;
@@ -65,8 +66,11 @@ pub enum AdminRequestCli {
     ListCells,
     /// Calls AdminRequest::ListActiveApps.
     ListActiveApps,
+    /// Calls AdminRequest::ListApps.
+    ListApps,
     ActivateApp(ActivateApp),
     DeactivateApp(DeactivateApp),
+    UninstallApp(UninstallApp),
     DumpState(DumpState),
     /// Calls AdminRequest::AddAgentInfo.
     /// [Unimplemented].
@@ -89,6 +93,10 @@ pub struct AddAppWs {
     /// Optional port number.
     /// Defaults to assigned by OS.
     pub port: Option<u16>,
+    #[structopt(short, long)]
+    /// The InstalledAppId which the interface makes crypto requests for.
+    /// If not set then crypto requests are refused.
+    pub app_id: Option<String>,
 }
 
 #[derive(Debug, StructOpt, Clone)]
@@ -151,6 +159,14 @@ pub struct DeactivateApp {
     pub app_id: String,
 }
 
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AdminRequest::UninstallApp
+/// and uninstalls the app, deleting the data of its cells.
+pub struct UninstallApp {
+    /// The InstalledAppId to uninstall.
+    pub app_id: String,
+}
+
 #[derive(Debug, StructOpt, Clone)]
 /// Calls AdminRequest::DumpState
 /// and dumps the current cell's state.
@@ -261,6 +277,10 @@ async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Resul
             let apps = list_active_apps(cmd).await?;
             msg!("Active Apps: {:?}", apps);
         }
+        AdminRequestCli::ListApps => {
+            let apps = list_apps(cmd).await?;
+            msg!("Apps: {:?}", apps);
+        }
         AdminRequestCli::ActivateApp(args) => {
             let app_id = args.app_id.clone();
             activate_app(cmd, args).await?;
@@ -271,6 +291,11 @@ async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Resul
             deactivate_app(cmd, args).await?;
             msg!("Deactivated app: {:?}", app_id);
         }
+        AdminRequestCli::UninstallApp(args) => {
+            let app_id = args.app_id.clone();
+            uninstall_app(cmd, args).await?;
+            msg!("Uninstalled app: {:?}", app_id);
+        }
         AdminRequestCli::DumpState(args) => {
             let state = dump_state(cmd, args).await?;
             msg!("DUMP STATE \n{}", state);
@@ -468,6 +493,16 @@ pub async fn list_active_apps(cmd: &mut CmdRunner) -> anyhow::Result<Vec<String>
     Ok(expect_match!(resp => AdminResponse::ActiveAppsListed, "Failed to list active apps"))
 }
 
+/// Calls [`AdminRequest::ListApps`] for apps of any status.
+pub async fn list_apps(cmd: &mut CmdRunner) -> anyhow::Result<Vec<InstalledAppInfo>> {
+    let resp = cmd
+        .command(AdminRequest::ListApps {
+            status_filter: None,
+        })
+        .await?;
+    Ok(expect_match!(resp => AdminResponse::AppsListed, "Failed to list apps"))
+}
+
 /// Calls [`AdminRequest::ActivateApp`] and activates the installed app.
 pub async fn activate_app(cmd: &mut CmdRunner, args: ActivateApp) -> anyhow::Result<()> {
     let resp = cmd
@@ -498,13 +533,31 @@ pub async fn deactivate_app(cmd: &mut CmdRunner, args: DeactivateApp) -> anyhow:
     Ok(())
 }
 
+/// Calls [`AdminRequest::UninstallApp`] and uninstalls the app.
+pub async fn uninstall_app(cmd: &mut CmdRunner, args: UninstallApp) -> anyhow::Result<()> {
+    let resp = cmd
+        .command(AdminRequest::UninstallApp {
+            installed_app_id: args.app_id,
+        })
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::AppUninstalled),
+        "Failed to uninstall app, got: {:?}",
+        resp
+    );
+    Ok(())
+}
+
 /// Calls [`AdminRequest::AttachAppInterface`] and adds another app interface.
 pub async fn attach_app_interface(cmd: &mut CmdRunner, args: AddAppWs) -> anyhow::Result<u16> {
     if let Some(port) = args.port {
         ensure!(is_free(port), "port {} is not free", port);
     }
     let resp = cmd
-        .command(AdminRequest::AttachAppInterface { port: args.port })
+        .command(AdminRequest::AttachAppInterface {
+            port: args.port,
+            installed_app_id: args.app_id,
+        })
         .await?;
     match resp {
         AdminResponse::AppInterfaceAttached { port } => Ok(port),
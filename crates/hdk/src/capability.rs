@@ -186,3 +186,37 @@ pub fn update_cap_grant(
         EntryWithDefId::new(EntryDefId::CapGrant, Entry::CapGrant(new_grant_value)),
     )
 }
+
+/// List the live capability grants on the local source chain.
+///
+/// Only grants that have been committed and not since updated or deleted are returned.
+/// Each [ `CapGrantInfo` ] includes the [ `HeaderHash` ] of the grant so that it can be passed to
+/// [ `delete_cap_grant` ] or [ `update_cap_grant` ] to revoke or roll the grant.
+///
+/// Use [ `CapQuery::tag` ] to only list the grants with a given tag, or [ `CapQuery::all` ] to
+/// list every live grant.
+pub fn capability_grants(query: CapQuery) -> ExternResult<Vec<CapGrantInfo>> {
+    host_call::<CapQuery, Vec<CapGrantInfo>>(__capability_grants, query)
+}
+
+/// List the live capability claims on the local source chain.
+///
+/// Works the same way as [ `capability_grants` ] but for the [ `CapClaim` ] entries committed by
+/// [ `create_cap_claim` ].
+///
+/// There is no guarantee that any listed claim is still usable, the grantor may have revoked it.
+pub fn capability_claims(query: CapQuery) -> ExternResult<Vec<CapClaimInfo>> {
+    host_call::<CapQuery, Vec<CapClaimInfo>>(__capability_claims, query)
+}
+
+/// Get the capability that authorized the current zome call.
+///
+/// The [ `CapabilityInfo` ] contains the agent that made the call and the [ `CapGrant` ] that
+/// allowed it. Calls made by the chain author to their own cell, including scheduled functions,
+/// are always authorized by [ `CapGrant::ChainAuthor` ].
+///
+/// This is only available to zome calls and scheduled functions, other callbacks are not called
+/// with a capability.
+pub fn capability_info() -> ExternResult<CapabilityInfo> {
+    host_call::<(), CapabilityInfo>(__capability_info, ())
+}
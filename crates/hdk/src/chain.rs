@@ -6,6 +6,11 @@ use crate::prelude::*;
 /// The agent activity is held by the neighbourhood centered on the agent's public key, rather than a content hash like the rest of the DHT.
 ///
 /// The agent activity can be filtered with [ `ChainQueryFilter` ] like a local chain query.
+/// A long chain can be fetched a page at a time by setting the sequence range of the query.
+///
+/// [ `ActivityRequest::Elements` ] returns the full elements, with the rejected elements and
+/// the reasons they were rejected, instead of just the header hashes. Public entries are
+/// included if the query asks for them.
 pub fn get_agent_activity(
     agent: AgentPubKey,
     query: ChainQueryFilter,
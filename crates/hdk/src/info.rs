@@ -13,12 +13,24 @@ pub fn agent_info() -> ExternResult<AgentInfo> {
     host_call::<(), AgentInfo>(__agent_info, ())
 }
 
-/// @todo Not implemented
+/// Get information about the app the current cell is installed in.
+/// There are no inputs to [ `app_info` ].
+///
+/// The [ `AppInfo` ] includes the installed app id and the [ `CellId` ] of every cell in the app,
+/// each with the nick of its slot. Multi-dna apps can use this to find the sibling cells to make
+/// bridging [ `call` ]s to.
+///
+/// Only zome calls and scheduled functions have access to the app info.
 pub fn app_info() -> ExternResult<AppInfo> {
     host_call::<(), AppInfo>(__app_info, ())
 }
 
-/// @todo Not implemented
+/// Get information about the current dna.
+/// There are no inputs to [ `dna_info` ].
+///
+/// The [ `DnaInfo` ] includes the name, hash and properties of the dna and the names of its zomes.
+/// The properties are set when the dna is installed so happs can use them to configure behaviour
+/// at runtime.
 pub fn dna_info() -> ExternResult<DnaInfo> {
     host_call::<(), DnaInfo>(__dna_info, ())
 }
@@ -34,7 +46,13 @@ pub fn zome_info() -> ExternResult<ZomeInfo> {
     host_call::<(), ZomeInfo>(__zome_info, ())
 }
 
-/// @todo Not implemented
+/// Get information about the current zome call.
+/// There are no inputs to [ `call_info` ].
+///
+/// The [ `CallInfo` ] includes the agent that made the call, the [ `CapGrant` ] that authorized it,
+/// the name of the function being called and the source chain head when the call started.
+///
+/// Only zome calls and scheduled functions have access to the call info.
 pub fn call_info() -> ExternResult<CallInfo> {
     host_call::<(), CallInfo>(__call_info, ())
 }
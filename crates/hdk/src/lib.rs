@@ -143,6 +143,12 @@
 //!   - All zomes in a DNA define all their entries at the same time for the host
 //!   - All entry defs are combined into a single ordered list per zone and exposed to tooling such as DNA generation
 //!   - Entry defs are referenced by `u8` numerical position externally and in DHT headers and by id/name e.g. "post" in sparse callbacks
+//! - `function genesis_self_check(data: GenesisSelfCheckData) -> ExternResult<ValidateCallbackResult>`:
+//!   - Allows the guest to pass/fail an agent before genesis, e.g. by checking the membrane proof against the agent key
+//!   - Runs when an app is installed and before genesis commits anything to the source chain
+//!   - There is no source chain or network yet so the guest can't call host functions that need them
+//!   - All zomes in a DNA are called and any failure fails the install with `CreateAppError::GenesisSelfCheckFailed`
+//!   - Missing dependencies can't be fetched so they count as a failure
 //! - `function init(_: ()) -> ExternResult<InitResult>`:
 //!   - Allows the guest to pass/fail/retry initialization with `InitResult`
 //!   - All zomes in a DNA init at the same time
@@ -1,4 +1,7 @@
 pub use crate::app_entry;
+pub use crate::capability::capability_claims;
+pub use crate::capability::capability_grants;
+pub use crate::capability::capability_info;
 pub use crate::capability::create_cap_claim;
 pub use crate::capability::create_cap_grant;
 pub use crate::capability::delete_cap_grant;
@@ -31,6 +34,9 @@ pub use crate::hash_path::anchor::list_anchor_type_addresses;
 pub use crate::hash_path::anchor::Anchor;
 pub use crate::hash_path::path::Path;
 pub use crate::info::agent_info;
+pub use crate::info::app_info;
+pub use crate::info::call_info;
+pub use crate::info::dna_info;
 pub use crate::info::zome_info;
 pub use crate::link::create_link;
 pub use crate::link::delete_link;
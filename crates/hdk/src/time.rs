@@ -65,12 +65,68 @@ pub fn sys_time() -> ExternResult<core::time::Duration> {
     host_call::<(), core::time::Duration>(__sys_time, ())
 }
 
-/// @todo Not implemented
-pub fn schedule(execute_after: std::time::Duration) -> ExternResult<()> {
-    host_call::<std::time::Duration, ()>(__schedule, execute_after)
+/// Schedule a function in the current zome to be called after a duration.
+///
+/// The scheduled function is called by the conductor as the author of the cell, with the
+/// serialized payload as its input, once `execute_after` has elapsed.
+/// Schedules are persisted with the source chain so they survive conductor restarts, and are only
+/// saved if the zome call that scheduled them succeeds.
+///
+/// The scheduled function is an ordinary extern of the current zome, so it can commit entries,
+/// make network calls and even `schedule` itself again to run periodically:
+///
+/// ```ignore
+/// #[hdk_extern]
+/// fn expire_offer(offer: HeaderHash) -> ExternResult<()> {
+///     delete_entry(offer)?;
+///     Ok(())
+/// }
+///
+/// schedule("expire_offer", offer_hash, std::time::Duration::from_secs(60 * 60))?;
+/// ```
+///
+/// Scheduled functions are best effort, they will not run before `execute_after` has elapsed but
+/// may run later than that if the conductor was offline or busy.
+/// If the scheduled function fails its changes are discarded and it is not retried.
+pub fn schedule<I>(
+    scheduled_fn: impl Into<FunctionName>,
+    payload: I,
+    execute_after: std::time::Duration,
+) -> ExternResult<()>
+where
+    I: serde::Serialize + std::fmt::Debug,
+{
+    host_call::<Schedule, ()>(
+        __schedule,
+        Schedule::new(
+            scheduled_fn.into(),
+            ExternIO::encode(payload)?,
+            execute_after,
+        ),
+    )
 }
 
-/// @todo Not implemented
+/// Suspend the current zome call for a duration.
+///
+/// Useful for retry and backoff loops, e.g. around `get` or `call_remote` when data may not have
+/// propagated yet:
+///
+/// ```ignore
+/// let mut wait = std::time::Duration::from_millis(100);
+/// let element = loop {
+///     match get(hash.clone(), GetOptions::latest())? {
+///         Some(element) => break element,
+///         None => {
+///             sleep(wait)?;
+///             wait *= 2;
+///         }
+///     }
+/// };
+/// ```
+///
+/// The conductor limits how long a single sleep may be and the zome call timeout still applies,
+/// so sleeping past either limit returns an error rather than waiting.
+/// Only zome calls and scheduled functions can sleep, not callbacks such as validation.
 pub fn sleep(wake_after: std::time::Duration) -> ExternResult<()> {
     host_call::<std::time::Duration, ()>(__sleep, wake_after)
 }
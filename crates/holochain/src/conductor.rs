@@ -21,6 +21,7 @@ mod conductor;
 pub mod config;
 #[allow(missing_docs)]
 pub mod dna_store;
+pub mod dpki;
 pub mod entry_def_store;
 #[allow(missing_docs)]
 pub mod error;
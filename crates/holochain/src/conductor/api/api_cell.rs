@@ -10,10 +10,10 @@ use crate::core::workflow::call_zome_workflow::call_zome_workspace_lock::CallZom
 use crate::core::workflow::ZomeCallResult;
 use async_trait::async_trait;
 use holo_hash::DnaHash;
+use holochain_conductor_api::config::conductor::ZomeCallConfig;
 use holochain_conductor_api::ZomeCall;
 use holochain_keystore::KeystoreSender;
 use holochain_types::prelude::*;
-use tracing::*;
 
 /// The concrete implementation of [CellConductorApiT], which is used to give
 /// Cells an API for calling back to their [Conductor].
@@ -62,9 +62,8 @@ impl CellConductorApiT for CellConductorApi {
         }
     }
 
-    async fn dpki_request(&self, _method: String, _args: String) -> ConductorApiResult<String> {
-        warn!("Using placeholder dpki");
-        Ok("TODO".to_string())
+    async fn agent_key_state(&self, key: &AgentPubKey) -> ConductorApiResult<KeyState> {
+        self.conductor_handle.agent_key_state(key).await
     }
 
     async fn autonomic_cue(&self, cue: AutonomicCue) -> ConductorApiResult<()> {
@@ -77,6 +76,10 @@ impl CellConductorApiT for CellConductorApi {
         self.conductor_handle.keystore()
     }
 
+    fn zome_call_config(&self) -> ZomeCallConfig {
+        self.conductor_handle.zome_call_config()
+    }
+
     async fn signal_broadcaster(&self) -> SignalBroadcaster {
         self.conductor_handle.signal_broadcaster().await
     }
@@ -125,9 +128,8 @@ pub trait CellConductorApiT: Clone + Send + Sync + Sized {
         call: ZomeCall,
     ) -> ConductorApiResult<ZomeCallResult>;
 
-    /// Make a request to the DPKI service running for this Conductor.
-    /// TODO: decide on actual signature
-    async fn dpki_request(&self, method: String, args: String) -> ConductorApiResult<String>;
+    /// Ask the DPKI of this Conductor whether an agent key is valid
+    async fn agent_key_state(&self, key: &AgentPubKey) -> ConductorApiResult<KeyState>;
 
     /// Cue the autonomic system to run an [AutonomicProcess] earlier than its scheduled time.
     /// This is basically a heuristic designed to help things run more smoothly.
@@ -136,6 +138,9 @@ pub trait CellConductorApiT: Clone + Send + Sync + Sized {
     /// Request access to this conductor's keystore
     fn keystore(&self) -> &KeystoreSender;
 
+    /// The time limits for zome calls on this conductor
+    fn zome_call_config(&self) -> ZomeCallConfig;
+
     /// Access the broadcast Sender which will send a Signal across every
     /// attached app interface
     async fn signal_broadcaster(&self) -> SignalBroadcaster;
@@ -172,6 +177,9 @@ pub trait CellConductorReadHandleT: Send + Sync {
 
     /// Get a zome from this cell's Dna
     async fn get_zome(&self, dna_hash: &DnaHash, zome_name: &ZomeName) -> ConductorApiResult<Zome>;
+
+    /// Get the app this cell is installed in
+    async fn get_this_app(&self) -> ConductorApiResult<Option<InstalledApp>>;
 }
 
 #[async_trait]
@@ -197,4 +205,11 @@ impl CellConductorReadHandleT for CellConductorApi {
     async fn get_zome(&self, dna_hash: &DnaHash, zome_name: &ZomeName) -> ConductorApiResult<Zome> {
         CellConductorApiT::get_zome(self, dna_hash, zome_name).await
     }
+
+    async fn get_this_app(&self) -> ConductorApiResult<Option<InstalledApp>> {
+        Ok(self
+            .conductor_handle
+            .find_app_containing_cell(&self.cell_id)
+            .await?)
+    }
 }
@@ -1,5 +1,6 @@
 use crate::conductor::interface::error::InterfaceResult;
 use holochain_serialized_bytes::prelude::*;
+use holochain_types::signal::Signal;
 
 mod admin_interface;
 mod app_interface;
@@ -18,4 +19,16 @@ pub trait InterfaceApi: 'static + Send + Sync + Clone {
         &self,
         request: Result<Self::ApiRequest, SerializedBytesError>,
     ) -> InterfaceResult<Self::ApiResponse>;
+
+    // -- provided -- //
+
+    /// A copy of this API for a new connection to the interface
+    fn for_connection(&self) -> Self {
+        self.clone()
+    }
+
+    /// Should the connection of this API receive this signal?
+    async fn allows_signal(&self, _signal: &Signal) -> bool {
+        true
+    }
 }
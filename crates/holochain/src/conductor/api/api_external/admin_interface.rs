@@ -4,7 +4,6 @@ use crate::conductor::api::error::ConductorApiResult;
 
 use crate::conductor::api::error::SerializationError;
 
-use crate::conductor::error::CreateAppError;
 use crate::conductor::interface::error::InterfaceError;
 use crate::conductor::interface::error::InterfaceResult;
 use crate::conductor::ConductorHandle;
@@ -108,13 +107,66 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                 Ok(AdminResponse::DnaRegistered(hash))
             }
             CreateCloneCell(payload) => {
-                let cell_id = payload.cell_id();
-                self.conductor_handle
+                let cell_id = self
+                    .conductor_handle
                     .clone()
                     .create_clone_cell(*payload)
                     .await?;
                 Ok(AdminResponse::CloneCellCreated(cell_id))
             }
+            DestroyCloneCell { cell_id } => {
+                self.conductor_handle
+                    .clone()
+                    .destroy_clone_cell(cell_id)
+                    .await?;
+                Ok(AdminResponse::CloneCellDestroyed)
+            }
+            ArchiveCloneCell {
+                installed_app_id,
+                slot_id,
+                cell_id,
+            } => {
+                self.conductor_handle
+                    .archive_clone_cell(installed_app_id, slot_id, cell_id)
+                    .await?;
+                Ok(AdminResponse::CloneCellArchived)
+            }
+            RestoreCloneCell {
+                installed_app_id,
+                slot_id,
+                cell_id,
+            } => {
+                self.conductor_handle
+                    .clone()
+                    .restore_clone_cell(installed_app_id, slot_id, cell_id)
+                    .await?;
+                Ok(AdminResponse::CloneCellRestored)
+            }
+            ListCloneCells {
+                installed_app_id,
+                slot_id,
+            } => {
+                let slot = self
+                    .conductor_handle
+                    .get_app_slot(&installed_app_id, &slot_id)
+                    .await?;
+                Ok(AdminResponse::CloneCellsListed {
+                    clones: slot.clones().iter().cloned().collect(),
+                    archived: slot.archived_clones().iter().cloned().collect(),
+                })
+            }
+            GetCellHealth { cell_id } => Ok(AdminResponse::CellHealth(
+                self.conductor_handle.cell_health(&cell_id).await?,
+            )),
+            SetAppCryptoPermissions {
+                installed_app_id,
+                permissions,
+            } => {
+                self.conductor_handle
+                    .set_app_crypto_permissions(installed_app_id, permissions)
+                    .await?;
+                Ok(AdminResponse::AppCryptoPermissionsSet)
+            }
             InstallApp(payload) => {
                 trace!(?payload.dnas);
                 let InstallAppPayload {
@@ -207,6 +259,10 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                 let app_ids = self.conductor_handle.list_active_apps().await?;
                 Ok(AdminResponse::ActiveAppsListed(app_ids))
             }
+            ListApps { status_filter } => {
+                let apps = self.conductor_handle.list_apps(status_filter).await?;
+                Ok(AdminResponse::AppsListed(apps))
+            }
             ActivateApp { installed_app_id } => {
                 // Activate app
                 self.conductor_handle
@@ -220,12 +276,7 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                 errors
                     .into_iter()
                     // We only care about this app for the activate command
-                    .find(|cell_error| match cell_error {
-                        CreateAppError::Failed {
-                            installed_app_id: error_app_id,
-                            ..
-                        } => error_app_id == &installed_app_id,
-                    })
+                    .find(|cell_error| cell_error.installed_app_id() == &installed_app_id)
                     // There was an error in this app so return it
                     .map(|this_app_error| Ok(AdminResponse::Error(this_app_error.into())))
                     // No error, return success
@@ -234,16 +285,25 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
             DeactivateApp { installed_app_id } => {
                 // Activate app
                 self.conductor_handle
-                    .deactivate_app(installed_app_id.clone())
+                    .deactivate_app(installed_app_id.clone(), DeactivationReason::Normal)
                     .await?;
                 Ok(AdminResponse::AppDeactivated)
             }
-            AttachAppInterface { port } => {
+            UninstallApp { installed_app_id } => {
+                self.conductor_handle
+                    .uninstall_app(&installed_app_id)
+                    .await?;
+                Ok(AdminResponse::AppUninstalled)
+            }
+            AttachAppInterface {
+                port,
+                installed_app_id,
+            } => {
                 let port = port.unwrap_or(0);
                 let port = self
                     .conductor_handle
                     .clone()
-                    .add_app_interface(port)
+                    .add_app_interface(port, installed_app_id)
                     .await?;
                 Ok(AdminResponse::AppInterfaceAttached { port })
             }
@@ -527,6 +587,259 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test(threaded_scheduler)]
+    async fn install_uninstall_app() -> Result<()> {
+        observability::test_run().ok();
+        let envs = test_environments();
+        let handle = Conductor::builder().test(&envs).await?;
+        let shutdown = handle.take_shutdown_handle().await.unwrap();
+        let admin_api = RealAdminInterfaceApi::new(handle.clone());
+        let uuid = Uuid::new_v4();
+        let dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![(TestWasm::Foo.into(), TestWasm::Foo.into())],
+        );
+        let (dna_path, _tempdir) = write_fake_dna_file(dna.clone()).await.unwrap();
+        let installed_app_id = "test-uninstall".to_string();
+        let path_install_payload = InstallAppPayload {
+            dnas: vec![InstallAppDnaPayload::path_only(dna_path, "".to_string())],
+            installed_app_id: installed_app_id.clone(),
+            agent_key: fake_agent_pubkey_1(),
+        };
+
+        let install_response = admin_api
+            .handle_admin_request(AdminRequest::InstallApp(Box::new(path_install_payload)))
+            .await;
+        assert_matches!(install_response, AdminResponse::AppInstalled(_));
+
+        // A freshly installed app is inactive
+        let res = admin_api
+            .handle_admin_request(AdminRequest::ListApps {
+                status_filter: Some(AppStatusFilter::Inactive),
+            })
+            .await;
+        assert_matches!(res, AdminResponse::AppsListed(apps)
+            if apps.len() == 1
+            && apps[0].installed_app_id == installed_app_id
+            && apps[0].agent_key == fake_agent_pubkey_1()
+            && apps[0].status == InstalledAppStatus::Inactive {
+                reason: DeactivationReason::NeverActivated
+            }
+        );
+
+        let res = admin_api
+            .handle_admin_request(AdminRequest::ActivateApp {
+                installed_app_id: installed_app_id.clone(),
+            })
+            .await;
+        assert_matches!(res, AdminResponse::AppActivated);
+
+        let res = admin_api
+            .handle_admin_request(AdminRequest::ListApps {
+                status_filter: Some(AppStatusFilter::Active),
+            })
+            .await;
+        assert_matches!(res, AdminResponse::AppsListed(apps)
+            if apps.len() == 1 && apps[0].status == InstalledAppStatus::Active
+        );
+        let res = admin_api
+            .handle_admin_request(AdminRequest::ListApps {
+                status_filter: Some(AppStatusFilter::Inactive),
+            })
+            .await;
+        assert_matches!(res, AdminResponse::AppsListed(apps) if apps.is_empty());
+
+        let res = admin_api
+            .handle_admin_request(AdminRequest::UninstallApp {
+                installed_app_id: installed_app_id.clone(),
+            })
+            .await;
+        assert_matches!(res, AdminResponse::AppUninstalled);
+
+        // The app and its cell are gone
+        let res = admin_api
+            .handle_admin_request(AdminRequest::ListActiveApps)
+            .await;
+        assert_matches!(res, AdminResponse::ActiveAppsListed(v) if v.is_empty());
+        let res = admin_api
+            .handle_admin_request(AdminRequest::ListCellIds)
+            .await;
+        assert_matches!(res, AdminResponse::CellIdsListed(v) if v.is_empty());
+        assert!(handle
+            .get_state_from_handle()
+            .await?
+            .get_app_info(&installed_app_id)
+            .is_none());
+
+        // An app can't be uninstalled twice
+        let res = admin_api
+            .handle_admin_request(AdminRequest::UninstallApp { installed_app_id })
+            .await;
+        assert_matches!(res, AdminResponse::Error(_));
+
+        handle.shutdown().await;
+        tokio::time::timeout(std::time::Duration::from_secs(1), shutdown)
+            .await
+            .ok();
+        Ok(())
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn create_archive_restore_destroy_clone_cell() -> Result<()> {
+        observability::test_run().ok();
+        let envs = test_environments();
+        let handle = Conductor::builder().test(&envs).await?;
+        let shutdown = handle.take_shutdown_handle().await.unwrap();
+        let admin_api = RealAdminInterfaceApi::new(handle.clone());
+        let uuid = Uuid::new_v4();
+        let dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![(TestWasm::Foo.into(), TestWasm::Foo.into())],
+        );
+        let manifest: AppManifest = serde_yaml::from_str(
+            r#"
+manifest_version: "1"
+name: "clones"
+slots:
+  - id: "nick"
+    provisioning:
+      strategy: "create"
+      deferred: false
+    dna:
+      bundled: "dna.dna"
+      clone_limit: 1
+        "#,
+        )
+        .unwrap();
+        let resources = vec![(
+            PathBuf::from("dna.dna"),
+            DnaBundle::from_dna_file(dna).await?,
+        )];
+        let bundle = AppBundle::new(manifest, resources, PathBuf::from(".")).await?;
+        let installed_app_id = "clones".to_string();
+        let slot_id: SlotId = "nick".into();
+        let agent_key = fake_agent_pubkey_1();
+
+        let res = admin_api
+            .handle_admin_request(AdminRequest::InstallAppBundle(Box::new(
+                InstallAppBundlePayload {
+                    source: AppBundleSource::Bundle(bundle),
+                    agent_key: agent_key.clone(),
+                    installed_app_id: Some(installed_app_id.clone()),
+                    membrane_proofs: Default::default(),
+                },
+            )))
+            .await;
+        let base_cell_id = match res {
+            AdminResponse::AppBundleInstalled(app) => app.slot(&slot_id)?.cell_id().clone(),
+            r => panic!("unexpected response: {:?}", r),
+        };
+        let res = admin_api
+            .handle_admin_request(AdminRequest::ActivateApp {
+                installed_app_id: installed_app_id.clone(),
+            })
+            .await;
+        assert_matches!(res, AdminResponse::AppActivated);
+
+        let clone_payload = |dna_hash: DnaHash, agent_key: AgentPubKey| {
+            AdminRequest::CreateCloneCell(Box::new(CreateCloneCellPayload {
+                properties: None,
+                dna_hash,
+                agent_key,
+                installed_app_id: installed_app_id.clone(),
+                slot_id: slot_id.clone(),
+                membrane_proof: None,
+            }))
+        };
+        let list_clones = || AdminRequest::ListCloneCells {
+            installed_app_id: installed_app_id.clone(),
+            slot_id: slot_id.clone(),
+        };
+
+        // Clones can't be made of a cell which isn't the base cell of the slot
+        let other_agent = handle
+            .keystore()
+            .clone()
+            .generate_sign_keypair_from_pure_entropy()
+            .await?;
+        let res = admin_api
+            .handle_admin_request(clone_payload(base_cell_id.dna_hash().clone(), other_agent))
+            .await;
+        assert_matches!(res, AdminResponse::Error(_));
+
+        let res = admin_api
+            .handle_admin_request(clone_payload(
+                base_cell_id.dna_hash().clone(),
+                agent_key.clone(),
+            ))
+            .await;
+        let clone_id = match res {
+            AdminResponse::CloneCellCreated(cell_id) => cell_id,
+            r => panic!("unexpected response: {:?}", r),
+        };
+        assert_eq!(clone_id.agent_pubkey(), &agent_key);
+        assert!(handle.list_cell_ids().await?.contains(&clone_id));
+
+        // The clone limit is reached
+        let res = admin_api
+            .handle_admin_request(clone_payload(
+                base_cell_id.dna_hash().clone(),
+                agent_key.clone(),
+            ))
+            .await;
+        assert_matches!(res, AdminResponse::Error(_));
+
+        // An archived clone stops running but is still listed
+        let res = admin_api
+            .handle_admin_request(AdminRequest::ArchiveCloneCell {
+                installed_app_id: installed_app_id.clone(),
+                slot_id: slot_id.clone(),
+                cell_id: clone_id.clone(),
+            })
+            .await;
+        assert_matches!(res, AdminResponse::CloneCellArchived);
+        assert!(!handle.list_cell_ids().await?.contains(&clone_id));
+        assert!(handle.list_cell_ids().await?.contains(&base_cell_id));
+        let res = admin_api.handle_admin_request(list_clones()).await;
+        assert_matches!(res, AdminResponse::CloneCellsListed { clones, archived }
+            if clones.is_empty() && archived == vec![clone_id.clone()]
+        );
+
+        // A restored clone runs again
+        let res = admin_api
+            .handle_admin_request(AdminRequest::RestoreCloneCell {
+                installed_app_id: installed_app_id.clone(),
+                slot_id: slot_id.clone(),
+                cell_id: clone_id.clone(),
+            })
+            .await;
+        assert_matches!(res, AdminResponse::CloneCellRestored);
+        assert!(handle.list_cell_ids().await?.contains(&clone_id));
+        let res = admin_api.handle_admin_request(list_clones()).await;
+        assert_matches!(res, AdminResponse::CloneCellsListed { clones, archived }
+            if clones == vec![clone_id.clone()] && archived.is_empty()
+        );
+
+        // A destroyed clone is gone
+        let res = admin_api
+            .handle_admin_request(AdminRequest::DestroyCloneCell {
+                cell_id: clone_id.clone(),
+            })
+            .await;
+        assert_matches!(res, AdminResponse::CloneCellDestroyed);
+        assert!(!handle.list_cell_ids().await?.contains(&clone_id));
+        let res = admin_api.handle_admin_request(list_clones()).await;
+        assert_matches!(res, AdminResponse::CloneCellsListed { clones, archived }
+            if clones.is_empty() && archived.is_empty()
+        );
+
+        handle.shutdown().await;
+        tokio::time::timeout(std::time::Duration::from_secs(1), shutdown)
+            .await
+            .ok();
+        Ok(())
+    }
+
     #[tokio::test(threaded_scheduler)]
     async fn dna_read_parses() -> Result<()> {
         let uuid = Uuid::new_v4();
@@ -2,11 +2,15 @@ use super::InterfaceApi;
 use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::api::error::ExternalApiWireError;
 use crate::conductor::api::error::SerializationError;
+use crate::conductor::error::ConductorError;
 use crate::conductor::interface::error::InterfaceError;
 use crate::conductor::interface::error::InterfaceResult;
+use crate::conductor::interface::SignalSubscriptions;
 use crate::conductor::state::AppInterfaceId;
 use crate::conductor::ConductorHandle;
 
+use holochain_keystore::AgentPubKeyExt;
+use holochain_keystore::KeystoreSenderExt;
 use holochain_serialized_bytes::prelude::*;
 
 use holochain_types::prelude::*;
@@ -41,6 +45,9 @@ pub trait AppInterfaceApi: 'static + Send + Sync + Clone {
 pub struct RealAppInterfaceApi {
     conductor_handle: ConductorHandle,
     interface_id: AppInterfaceId,
+    /// The app which crypto requests are made for
+    installed_app_id: Option<InstalledAppId>,
+    signal_subscriptions: SignalSubscriptions,
 }
 
 impl RealAppInterfaceApi {
@@ -49,8 +56,66 @@ impl RealAppInterfaceApi {
         Self {
             conductor_handle,
             interface_id,
+            installed_app_id: None,
+            signal_subscriptions: SignalSubscriptions::default(),
         }
     }
+
+    /// Make crypto requests for this app. Without an app crypto requests
+    /// are refused.
+    pub fn with_app(mut self, installed_app_id: InstalledAppId) -> Self {
+        self.installed_app_id = Some(installed_app_id);
+        self
+    }
+
+    /// Perform a crypto request for the app of the interface with the
+    /// keystore, if the app's crypto permissions allow it
+    async fn handle_crypto_request(
+        &self,
+        request: CryptoRequest,
+    ) -> ConductorApiResult<AppResponse> {
+        let installed_app_id = match &self.installed_app_id {
+            Some(installed_app_id) => installed_app_id.clone(),
+            None => {
+                return Ok(AppResponse::Error(
+                    ExternalApiWireError::CryptoUnauthorized(format!(
+                        "The app interface {} was not attached for an app, so it can't make the crypto request {:?}",
+                        self.interface_id, request
+                    )),
+                ))
+            }
+        };
+        let app_info = self
+            .conductor_handle
+            .get_app_info(&installed_app_id)
+            .await?
+            .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?;
+        let permissions = self
+            .conductor_handle
+            .get_app_crypto_permissions(&installed_app_id)
+            .await?;
+        if !request.is_allowed_by(&permissions) {
+            return Ok(AppResponse::Error(
+                ExternalApiWireError::CryptoUnauthorized(format!(
+                    "The app {} is not permitted to make the crypto request {:?}",
+                    installed_app_id, request
+                )),
+            ));
+        }
+        let keystore = self.conductor_handle.keystore();
+        let response = match request {
+            CryptoRequest::Sign { data } => {
+                CryptoResponse::Signed(app_info.agent_key.sign_raw(keystore, data.as_ref()).await?)
+            }
+            CryptoRequest::Encrypt { input } => {
+                CryptoResponse::Encrypted(keystore.x_25519_x_salsa20_poly1305_encrypt(input).await?)
+            }
+            CryptoRequest::Decrypt { input } => {
+                CryptoResponse::Decrypted(keystore.x_25519_x_salsa20_poly1305_decrypt(input).await?)
+            }
+        };
+        Ok(AppResponse::Crypto(Box::new(response)))
+    }
 }
 
 #[async_trait::async_trait]
@@ -97,8 +162,23 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                     Err(e) => Ok(AppResponse::Error(e.into())),
                 }
             }
-            AppRequest::SignalSubscription(_) => Ok(AppResponse::Unimplemented(request)),
-            AppRequest::Crypto(_) => Ok(AppResponse::Unimplemented(request)),
+            AppRequest::SignalSubscription(subscription) => {
+                let installed_app_id = subscription.installed_app_id().clone();
+                let signal_broadcaster = self.conductor_handle.signal_broadcaster().await;
+                // The version is read first so a change while the cells are
+                // looked up is caught by the next signal
+                let version = signal_broadcaster.apps_version().get();
+                let cells = self
+                    .conductor_handle
+                    .get_app_cells(&installed_app_id)
+                    .await?
+                    .ok_or(ConductorError::AppNotInstalled(installed_app_id))?;
+                self.signal_subscriptions
+                    .subscribe(subscription, cells, version, &signal_broadcaster)
+                    .await;
+                Ok(AppResponse::SignalSubscriptionUpdated)
+            }
+            AppRequest::Crypto(request) => self.handle_crypto_request(*request).await,
         }
     }
 }
@@ -123,4 +203,39 @@ impl InterfaceApi for RealAppInterfaceApi {
             Err(e) => Ok(AppResponse::Error(SerializationError::from(e).into())),
         }
     }
+
+    /// A copy of this api for a new connection to the interface,
+    /// which starts without any signal subscriptions
+    fn for_connection(&self) -> Self {
+        Self {
+            installed_app_id: self.installed_app_id.clone(),
+            ..Self::new(self.conductor_handle.clone(), self.interface_id.clone())
+        }
+    }
+
+    /// A connection without subscriptions receives every signal, otherwise
+    /// the signal must be about a cell of a subscribed app and pass its
+    /// filters. The cells of each app are looked up when subscribing and
+    /// again after the apps change, so cells cloned since subscribing are
+    /// included.
+    async fn allows_signal(&self, signal: &Signal) -> bool {
+        if self.signal_subscriptions.is_empty().await {
+            return true;
+        }
+        for (installed_app_id, version) in self.signal_subscriptions.stale_apps().await {
+            match self.conductor_handle.get_app_cells(&installed_app_id).await {
+                Ok(cells) => {
+                    self.signal_subscriptions
+                        .refresh_cells(&installed_app_id, cells.unwrap_or_default(), version)
+                        .await
+                }
+                Err(e) => tracing::warn!(
+                    ?installed_app_id,
+                    error = ?e,
+                    "Failed to look up the cells of a subscribed app"
+                ),
+            }
+        }
+        self.signal_subscriptions.allows(signal).await
+    }
 }
@@ -1,5 +1,6 @@
 //! Errors occurring during a [CellConductorApi] or [InterfaceApi] call
 
+use crate::conductor::dpki::DpkiError;
 use crate::conductor::error::ConductorError;
 use crate::conductor::error::CreateAppError;
 use crate::conductor::interface::error::InterfaceError;
@@ -88,6 +89,9 @@ pub enum ConductorApiError {
     #[error(transparent)]
     AppBundleError(#[from] AppBundleError),
 
+    #[error(transparent)]
+    DpkiError(#[from] DpkiError),
+
     #[error(transparent)]
     JsonDumpError(#[from] serde_json::Error),
 }
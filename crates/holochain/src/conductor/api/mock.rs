@@ -7,6 +7,7 @@ use crate::conductor::interface::SignalBroadcaster;
 use crate::core::workflow::ZomeCallResult;
 use async_trait::async_trait;
 use holo_hash::DnaHash;
+use holochain_conductor_api::config::conductor::ZomeCallConfig;
 use holochain_keystore::KeystoreSender;
 use holochain_types::prelude::*;
 use mockall::mock;
@@ -28,9 +29,10 @@ mock! {
 
         fn sync_autonomic_cue(&self, cue: AutonomicCue) -> ConductorApiResult<()>;
 
-        fn sync_dpki_request(&self, method: String, args: String) -> ConductorApiResult<String>;
+        fn sync_agent_key_state(&self, key: &AgentPubKey) -> ConductorApiResult<KeyState>;
 
         fn mock_keystore(&self) -> &KeystoreSender;
+        fn mock_zome_call_config(&self) -> ZomeCallConfig;
         fn mock_signal_broadcaster(&self) -> SignalBroadcaster;
         fn sync_get_dna(&self, dna_hash: &DnaHash) -> Option<DnaFile>;
         fn sync_get_this_dna(&self) -> ConductorApiResult<DnaFile>;
@@ -58,8 +60,8 @@ impl CellConductorApiT for MockCellConductorApi {
         self.sync_call_zome(cell_id, call)
     }
 
-    async fn dpki_request(&self, method: String, args: String) -> ConductorApiResult<String> {
-        self.sync_dpki_request(method, args)
+    async fn agent_key_state(&self, key: &AgentPubKey) -> ConductorApiResult<KeyState> {
+        self.sync_agent_key_state(key)
     }
 
     async fn autonomic_cue(&self, cue: AutonomicCue) -> ConductorApiResult<()> {
@@ -70,6 +72,10 @@ impl CellConductorApiT for MockCellConductorApi {
         self.mock_keystore()
     }
 
+    fn zome_call_config(&self) -> ZomeCallConfig {
+        self.mock_zome_call_config()
+    }
+
     async fn signal_broadcaster(&self) -> SignalBroadcaster {
         self.mock_signal_broadcaster()
     }
@@ -22,6 +22,8 @@ use crate::core::ribosome::ZomeCallInvocation;
 use crate::core::workflow::call_zome_workflow;
 use crate::core::workflow::error::WorkflowError;
 use crate::core::workflow::genesis_workflow::genesis_workflow;
+use crate::core::workflow::health_check_workflow::check_cell_health;
+use crate::core::workflow::health_check_workflow::HealthCheckWorkspace;
 use crate::core::workflow::incoming_dht_ops_workflow::incoming_dht_ops_workflow;
 use crate::core::workflow::initialize_zomes_workflow;
 use crate::core::workflow::produce_dht_ops_workflow::dht_op_light::light_to_op;
@@ -161,11 +163,14 @@ impl Cell {
     /// Performs the Genesis workflow the Cell, ensuring that its initial
     /// elements are committed. This is a prerequisite for any other interaction
     /// with the SourceChain
+    ///
+    /// The agent key of a cell of the DPKI app is not checked with DPKI.
     pub async fn genesis(
         id: CellId,
         conductor_handle: ConductorHandle,
         cell_env: EnvironmentWrite,
         membrane_proof: Option<SerializedBytes>,
+        is_dpki_cell: bool,
     ) -> CellResult<()> {
         // get the dna
         let dna_file = conductor_handle
@@ -180,7 +185,14 @@ impl Cell {
             .await
             .map_err(ConductorApiError::from)
             .map_err(Box::new)?;
-        let args = GenesisWorkflowArgs::new(dna_file, id.agent_pubkey().clone(), membrane_proof);
+        let ribosome = RealRibosome::new(dna_file.clone());
+        let args = GenesisWorkflowArgs::new(
+            dna_file,
+            id.agent_pubkey().clone(),
+            membrane_proof,
+            ribosome,
+            is_dpki_cell,
+        );
 
         genesis_workflow(workspace, cell_env.clone().into(), conductor_api, args)
             .await
@@ -417,12 +429,12 @@ impl Cell {
         Ok(())
     }
 
-    #[instrument(skip(self, _request_validation_receipt, _dht_hash, ops))]
+    #[instrument(skip(self, request_validation_receipt, _dht_hash, ops))]
     /// we are receiving a "publish" event from the network
     async fn handle_publish(
         &self,
         from_agent: AgentPubKey,
-        _request_validation_receipt: bool,
+        request_validation_receipt: bool,
         _dht_hash: holo_hash::AnyDhtHash,
         ops: Vec<(holo_hash::DhtOpHash, holochain_types::dht_op::DhtOp)>,
     ) -> CellResult<()> {
@@ -431,6 +443,7 @@ impl Cell {
             self.queue_triggers.sys_validation.clone(),
             ops,
             Some(from_agent),
+            request_validation_receipt,
         )
         .await
         .map_err(Box::new)
@@ -558,8 +571,30 @@ impl Cell {
 
     /// a remote agent is sending us a validation receipt.
     #[tracing::instrument(skip(self))]
-    async fn handle_validation_receipt(&self, _receipt: SerializedBytes) -> CellResult<()> {
-        unimplemented!()
+    async fn handle_validation_receipt(&self, receipt: SerializedBytes) -> CellResult<()> {
+        let receipt: SignedValidationReceipt = receipt.try_into()?;
+
+        // Only count receipts which were really signed by their validator
+        let valid = receipt
+            .receipt
+            .validator
+            .verify_signature(&receipt.validator_signature, receipt.receipt.clone())
+            .await
+            .map_err(DatabaseError::from)?;
+        if !valid {
+            warn!(
+                receipt = ?receipt.receipt,
+                "Dropping validation receipt with an invalid signature"
+            );
+            return Ok(());
+        }
+
+        let mut receipts = ValidationReceiptsBuf::new(&self.env)?;
+        receipts.add_if_unique(receipt)?;
+        self.env
+            .guard()
+            .with_commit(|writer| receipts.flush_to_txn(writer))?;
+        Ok(())
     }
 
     #[instrument(skip(self, dht_arc, since, until))]
@@ -606,7 +641,8 @@ impl Cell {
                         let cas = ElementBuf::rejected(self.env.clone().into())?;
                         light_to_op(val.op, &cas)?
                     }
-                    ValidationStatus::Abandoned => todo!("Add when abandoned store is added"),
+                    // We don't hold the data of abandoned ops
+                    ValidationStatus::Abandoned => continue,
                 };
                 let basis = full_op.dht_basis();
                 out.push((basis, op_hash, full_op));
@@ -626,11 +662,25 @@ impl Cell {
     #[tracing::instrument(skip(self, process))]
     pub async fn handle_autonomic_process(&self, process: AutonomicProcess) -> CellResult<()> {
         match process {
-            AutonomicProcess::SlowHeal => unimplemented!(),
-            AutonomicProcess::HealthCheck => unimplemented!(),
+            AutonomicProcess::SlowHeal => {
+                self.queue_triggers.slow_heal.clone().trigger();
+                Ok(())
+            }
+            AutonomicProcess::HealthCheck => {
+                self.queue_triggers.health_check.clone().trigger();
+                Ok(())
+            }
         }
     }
 
+    /// Measure how well the ops this cell has authored are held on the DHT
+    pub async fn health(&self) -> CellResult<CellHealth> {
+        let workspace = HealthCheckWorkspace::new(self.env.clone().into())?;
+        Ok(check_cell_health(&workspace, &self.conductor_api)
+            .await
+            .map_err(Box::new)?)
+    }
+
     #[instrument(skip(self, from_agent, fn_name, cap, payload))]
     /// a remote agent is attempting a "call_remote" on this cell.
     async fn handle_call_remote(
@@ -676,8 +726,10 @@ impl Cell {
         };
 
         let conductor_api = self.conductor_api.clone();
+        let timeout = conductor_api.zome_call_config().timeout();
         let signal_tx = self.signal_broadcaster().await;
         let ribosome = self.get_ribosome().await?;
+        let (zome_name, fn_name) = (call.zome_name.clone(), call.fn_name.clone());
         let invocation = ZomeCallInvocation::from_interface_call(conductor_api.clone(), call).await;
 
         let args = CallZomeWorkflowArgs {
@@ -687,16 +739,21 @@ impl Cell {
             signal_tx,
             is_root_zome_call,
         };
-        Ok(call_zome_workflow(
+        // Nothing is committed if the call times out. A guest that is still
+        // running isn't interrupted, but it can no longer sleep.
+        let workflow = call_zome_workflow(
             workspace_lock,
             self.holochain_p2p_cell.clone(),
             keystore,
-            arc.clone().into(),
+            arc.clone(),
             args,
             self.queue_triggers.produce_dht_ops.clone(),
-        )
-        .await
-        .map_err(Box::new)?)
+            self.queue_triggers.schedule.clone(),
+        );
+        Ok(tokio::time::timeout(timeout, workflow)
+            .await
+            .map_err(|_| CellError::ZomeCallTimeout(zome_name, fn_name, timeout))?
+            .map_err(Box::new)?)
     }
 
     /// Check if each Zome's init callback has been run, and if not, run it.
@@ -740,9 +797,18 @@ impl Cell {
         .await
         .map_err(Box::new)?;
         trace!(?init_result);
+        let mut signal_tx = conductor_api.signal_broadcaster().await;
         match init_result {
-            InitResult::Pass => {}
-            r => return Err(CellError::InitFailed(r)),
+            InitResult::Pass => {
+                signal_tx.send_system(SystemSignal::CellInitCompleted { cell_id: id });
+            }
+            r => {
+                signal_tx.send_system(SystemSignal::CellInitFailed {
+                    cell_id: id,
+                    reason: format!("{:?}", r),
+                });
+                return Err(CellError::InitFailed(r));
+            }
         }
         Ok(())
     }
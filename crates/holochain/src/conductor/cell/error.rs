@@ -54,8 +54,41 @@ pub enum CellError {
     SerializedBytesError(#[from] holochain_serialized_bytes::SerializedBytesError),
     #[error(transparent)]
     DhtOpConvertError(#[from] DhtOpConvertError),
+    #[error("The zome call to {0}::{1} did not complete within {2:?}")]
+    ZomeCallTimeout(ZomeName, FunctionName, std::time::Duration),
     #[error("Todo")]
     Todo,
 }
 
+impl CellError {
+    /// Whether creating the cell again can't succeed, as opposed to a
+    /// failure which may clear up on a retry, such as a database error
+    pub fn is_permanent(&self) -> bool {
+        match self {
+            CellError::CellWithoutGenesis(_)
+            | CellError::Genesis(_)
+            | CellError::DnaError(_)
+            | CellError::InitFailed(_) => true,
+            _ => self.genesis_self_check_rejection().is_some(),
+        }
+    }
+
+    /// The zome and reason of a genesis self check which rejected the cell,
+    /// if that is why genesis failed
+    pub fn genesis_self_check_rejection(&self) -> Option<(&ZomeName, &str)> {
+        match self {
+            CellError::ConductorApiError(e) => match e.as_ref() {
+                ConductorApiError::WorkflowError(e) => match e.as_ref() {
+                    WorkflowError::GenesisSelfCheckFailed(zome_name, reason) => {
+                        Some((zome_name, reason))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
 pub type CellResult<T> = Result<T, CellError>;
@@ -28,12 +28,21 @@ async fn test_cell_handle_publish() {
     mock_handler
         .expect_get_dna()
         .returning(|_| Some(fixt!(DnaFile)));
+    mock_handler
+        .expect_agent_key_state()
+        .returning(|_| Ok(KeyState::Valid));
 
     let mock_handler: crate::conductor::handle::ConductorHandle = Arc::new(mock_handler);
 
-    super::Cell::genesis(cell_id.clone(), mock_handler.clone(), env.clone(), None)
-        .await
-        .unwrap();
+    super::Cell::genesis(
+        cell_id.clone(),
+        mock_handler.clone(),
+        env.clone(),
+        None,
+        false,
+    )
+    .await
+    .unwrap();
 
     let (add_task_sender, shutdown) = spawn_task_manager();
     let (stop_tx, _) = sync::broadcast::channel(1);
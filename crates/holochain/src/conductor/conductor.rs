@@ -14,17 +14,22 @@ use super::config::AdminInterfaceConfig;
 use super::config::InterfaceDriver;
 use super::dna_store::DnaDefBuf;
 use super::dna_store::RealDnaStore;
+use super::dpki::dpki_from_config;
+use super::dpki::Dpki;
 use super::entry_def_store::get_entry_defs;
 use super::entry_def_store::EntryDefBuf;
 use super::error::ConductorError;
 use super::error::CreateAppError;
 use super::handle::ConductorHandleImpl;
+use super::handle::ConductorHandleT;
 use super::interface::error::InterfaceResult;
 use super::interface::websocket::spawn_admin_interface_task;
 use super::interface::websocket::spawn_app_interface_task;
 use super::interface::websocket::spawn_websocket_listener;
 use super::interface::websocket::SIGNAL_BUFFER_SIZE;
 use super::interface::SignalBroadcaster;
+use super::interface::AppsVersion;
+use super::interface::SubscribedBases;
 use super::manager::keep_alive_task;
 use super::manager::spawn_task_manager;
 use super::manager::ManagedTaskAdd;
@@ -41,6 +46,7 @@ use super::CellError;
 use super::{api::CellConductorApi, state::AppInterfaceConfig};
 use super::{api::CellConductorApiT, interface::AppInterfaceRuntime};
 use crate::conductor::api::error::ConductorApiResult;
+use crate::conductor::cell::error::CellResult;
 use crate::conductor::cell::Cell;
 use crate::conductor::config::ConductorConfig;
 use crate::conductor::error::ConductorResult;
@@ -50,9 +56,12 @@ use crate::core::workflow::integrate_dht_ops_workflow;
 pub use builder::*;
 use fallible_iterator::FallibleIterator;
 use futures::future;
-use futures::future::TryFutureExt;
 use futures::stream::StreamExt;
 use holo_hash::DnaHash;
+use holochain_conductor_api::AppCryptoPermissions;
+use holochain_conductor_api::AppStatusFilter;
+use holochain_conductor_api::DeactivationReason;
+use holochain_conductor_api::InstalledAppInfo;
 use holochain_conductor_api::JsonDump;
 use holochain_keystore::lair_keystore::spawn_lair_keystore;
 use holochain_keystore::test_keystore::spawn_test_keystore;
@@ -156,6 +165,15 @@ where
 
     /// Handle to the network actor.
     holochain_p2p: holochain_p2p::HolochainP2pRef,
+
+    /// Checks whether agent keys are valid, if DPKI is configured
+    dpki: Option<Dpki>,
+
+    /// The bases which app interface connections subscribe to signals about
+    subscribed_bases: SubscribedBases,
+
+    /// Changes whenever the conductor state, and so the installed apps, do
+    apps_version: AppsVersion,
 }
 
 impl Conductor {
@@ -311,10 +329,14 @@ where
     pub(super) async fn add_app_interface_via_handle(
         &mut self,
         port: u16,
+        installed_app_id: Option<InstalledAppId>,
         handle: ConductorHandle,
     ) -> ConductorResult<u16> {
         let interface_id: AppInterfaceId = format!("interface-{}", port).into();
-        let app_api = RealAppInterfaceApi::new(handle, interface_id.clone());
+        let mut app_api = RealAppInterfaceApi::new(handle, interface_id.clone());
+        if let Some(installed_app_id) = installed_app_id.clone() {
+            app_api = app_api.with_app(installed_app_id);
+        }
         // This receiver is thrown away because we can produce infinite new
         // receivers from the Sender
         let (signal_tx, _r) = tokio::sync::broadcast::channel(SIGNAL_BUFFER_SIZE);
@@ -331,7 +353,10 @@ where
         }
 
         self.app_interfaces.insert(interface_id.clone(), interface);
-        let config = AppInterfaceConfig::websocket(port);
+        let config = AppInterfaceConfig {
+            installed_app_id,
+            ..AppInterfaceConfig::websocket(port)
+        };
         self.update_state(|mut state| {
             state.app_interfaces.insert(interface_id, config);
             Ok(state)
@@ -386,12 +411,20 @@ where
                 unreachable!()
             };
             let _ = self
-                .add_app_interface_via_handle(port, handle.clone())
+                .add_app_interface_via_handle(port, i.installed_app_id.clone(), handle.clone())
                 .await?;
         }
         Ok(())
     }
 
+    pub(super) fn dpki(&self) -> Option<Dpki> {
+        self.dpki.clone()
+    }
+
+    pub(super) fn set_dpki(&mut self, dpki: Dpki) {
+        self.dpki = Some(dpki);
+    }
+
     pub(super) fn signal_broadcaster(&self) -> SignalBroadcaster {
         SignalBroadcaster::new(
             self.app_interfaces
@@ -400,35 +433,49 @@ where
                 .cloned()
                 .collect(),
         )
+        .with_subscribed_bases(self.subscribed_bases.clone())
+        .with_apps_version(self.apps_version.clone())
     }
 
-    /// Perform Genesis on the source chains for each of the specified CellIds.
+    /// Perform Genesis on the source chains for each of the specified CellIds
+    /// of an app.
     ///
     /// If genesis fails for any cell, this entire function fails, and all other
     /// partial or complete successes are rolled back.
     pub(super) async fn genesis_cells(
         &self,
+        installed_app_id: &InstalledAppId,
         cell_ids_with_proofs: Vec<(CellId, Option<MembraneProof>)>,
         conductor_handle: ConductorHandle,
     ) -> ConductorResult<()> {
         let root_env_dir = std::path::PathBuf::from(self.root_env_dir.clone());
         let keystore = self.keystore.clone();
+        let is_dpki_cell = self
+            .dpki
+            .as_ref()
+            .map_or(false, |dpki| dpki.is_dpki_app(installed_app_id));
 
         let cells_tasks = cell_ids_with_proofs.into_iter().map(|(cell_id, proof)| {
             let root_env_dir = root_env_dir.clone();
             let keystore = self.keystore.clone();
             let conductor_handle = conductor_handle.clone();
             let cell_id_inner = cell_id.clone();
-            tokio::spawn(async move {
+            let task = tokio::spawn(async move {
                 let env = EnvironmentWrite::new(
                     &root_env_dir,
                     EnvironmentKind::Cell(cell_id_inner.clone()),
                     keystore.clone(),
                 )?;
-                Cell::genesis(cell_id_inner, conductor_handle, env, proof).await
-            })
-            .map_err(CellError::from)
-            .and_then(|result| async move { result.map(|_| cell_id) })
+                Cell::genesis(cell_id_inner, conductor_handle, env, proof, is_dpki_cell).await
+            });
+            // Keep the id of a cell whose genesis failed to report it
+            async move {
+                match task.await {
+                    Ok(Ok(())) => Ok(cell_id),
+                    Ok(Err(e)) => Err((cell_id, e)),
+                    Err(e) => Err((cell_id, CellError::from(e))),
+                }
+            }
         });
         let (success, errors): (Vec<_>, Vec<_>) = futures::future::join_all(cells_tasks)
             .await
@@ -450,7 +497,7 @@ where
             }
 
             // match needed to avoid Debug requirement on unwrap_err
-            let errors = errors
+            let errors: Vec<_> = errors
                 .into_iter()
                 .map(|e| match e {
                     Err(e) => e,
@@ -458,7 +505,24 @@ where
                 })
                 .collect();
 
-            Err(ConductorError::GenesisFailed { errors })
+            // The app can't be installed if a genesis self check rejected
+            // one of its cells
+            if let Some((cell_id, (zome_name, reason))) = errors
+                .iter()
+                .find_map(|(cell_id, e)| e.genesis_self_check_rejection().map(|r| (cell_id, r)))
+            {
+                return Err(CreateAppError::GenesisSelfCheckFailed {
+                    installed_app_id: installed_app_id.clone(),
+                    cell_id: cell_id.clone(),
+                    zome_name: zome_name.clone(),
+                    reason: reason.to_string(),
+                }
+                .into());
+            }
+
+            Err(ConductorError::GenesisFailed {
+                errors: errors.into_iter().map(|(_, e)| e).collect(),
+            })
         } else {
             // No errors so return the cells
             Ok(())
@@ -473,17 +537,11 @@ where
         // Only create the active apps
         let active_apps = self.get_state().await?.active_apps;
 
-        // Data required to create apps
-        let root_env_dir = self.root_env_dir.clone();
-        let keystore = self.keystore.clone();
-
         // Closure for creating all cells in an app
         let tasks = active_apps.into_iter().map(
             move |(installed_app_id, app): (InstalledAppId, InstalledApp)| {
                 // Clone data for async block
-                let root_env_dir = std::path::PathBuf::from(root_env_dir.clone());
                 let conductor_handle = conductor_handle.clone();
-                let keystore = keystore.clone();
 
                 // Task that creates the cells
                 async move {
@@ -491,41 +549,12 @@ where
                     let cells_to_create = app
                         .all_cells()
                         .filter(|cell_id| !self.cells.contains_key(cell_id))
-                        .map(|cell_id| {
-                            (
-                                cell_id,
-                                root_env_dir.clone(),
-                                keystore.clone(),
-                                conductor_handle.clone(),
-                            )
-                        });
-
-                    use holochain_p2p::actor::HolochainP2pRefToCell;
+                        .map(|cell_id| (cell_id, conductor_handle.clone()));
 
                     // Create each cell
-                    let cells_tasks = cells_to_create.map(
-                        |(cell_id, dir, keystore, conductor_handle)| async move {
-                            let holochain_p2p_cell = self.holochain_p2p.to_cell(
-                                cell_id.dna_hash().clone(),
-                                cell_id.agent_pubkey().clone(),
-                            );
-
-                            let env = EnvironmentWrite::new_cell(
-                                &dir,
-                                cell_id.clone(),
-                                keystore.clone(),
-                            )?;
-                            Cell::create(
-                                cell_id.clone(),
-                                conductor_handle.clone(),
-                                env,
-                                holochain_p2p_cell,
-                                self.managed_task_add_sender.clone(),
-                                self.managed_task_stop_broadcaster.clone(),
-                            )
-                            .await
-                        },
-                    );
+                    let cells_tasks = cells_to_create.map(|(cell_id, conductor_handle)| {
+                        self.create_cell(cell_id.clone(), conductor_handle)
+                    });
 
                     // Join all the cell create tasks for this app
                     // and separate any errors
@@ -572,6 +601,30 @@ where
         Ok(futures::future::join_all(tasks).await)
     }
 
+    /// Create a single Cell, which is not yet added to the Conductor
+    pub(super) async fn create_cell(
+        &self,
+        cell_id: CellId,
+        conductor_handle: ConductorHandle,
+    ) -> CellResult<(Cell, InitialQueueTriggers)> {
+        use holochain_p2p::actor::HolochainP2pRefToCell;
+        let holochain_p2p_cell = self
+            .holochain_p2p
+            .to_cell(cell_id.dna_hash().clone(), cell_id.agent_pubkey().clone());
+        let root_env_dir = std::path::PathBuf::from(self.root_env_dir.clone());
+        let env =
+            EnvironmentWrite::new_cell(&root_env_dir, cell_id.clone(), self.keystore.clone())?;
+        Cell::create(
+            cell_id,
+            conductor_handle,
+            env,
+            holochain_p2p_cell,
+            self.managed_task_add_sender.clone(),
+            self.managed_task_stop_broadcaster.clone(),
+        )
+        .await
+    }
+
     /// Register an app inactive in the database
     pub(super) async fn add_inactive_app_to_db(
         &mut self,
@@ -604,6 +657,7 @@ where
                 .inactive_apps
                 .remove(&installed_app_id)
                 .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?;
+            state.deactivation_reasons.remove(&installed_app_id);
             state.active_apps.insert(app);
             Ok(state)
         })
@@ -611,10 +665,30 @@ where
         Ok(())
     }
 
-    /// Deactivate an app in the database
+    /// Set the crypto permissions of an installed app in the database
+    pub(super) async fn set_app_crypto_permissions_in_db(
+        &mut self,
+        installed_app_id: InstalledAppId,
+        permissions: AppCryptoPermissions,
+    ) -> ConductorResult<()> {
+        self.update_state(move |mut state| {
+            if state.get_app_mut(&installed_app_id).is_none() {
+                return Err(ConductorError::AppNotInstalled(installed_app_id));
+            }
+            state
+                .app_crypto_permissions
+                .insert(installed_app_id, permissions);
+            Ok(state)
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Deactivate an app in the database, recording why
     pub(super) async fn deactivate_app_in_db(
         &mut self,
         installed_app_id: InstalledAppId,
+        reason: DeactivationReason,
     ) -> ConductorResult<Vec<CellId>> {
         let state = self
             .update_state({
@@ -625,6 +699,9 @@ where
                         .remove(&installed_app_id)
                         .ok_or_else(|| ConductorError::AppNotActive(installed_app_id.clone()))?;
                     state.inactive_apps.insert(app);
+                    state
+                        .deactivation_reasons
+                        .insert(installed_app_id.clone(), reason);
                     Ok(state)
                 }
             })
@@ -661,6 +738,7 @@ where
         &mut self,
         installed_app_id: &InstalledAppId,
         slot_id: &SlotId,
+        base_cell_id: &CellId,
         properties: YamlProperties,
     ) -> ConductorResult<CellId> {
         let (_, child_dna) = self
@@ -670,6 +748,14 @@ where
                         .slots()
                         .get(slot_id)
                         .ok_or_else(|| AppError::SlotIdMissing(slot_id.to_owned()))?;
+                    // Clones can only be made from the base cell of the slot
+                    if slot.cell_id() != base_cell_id {
+                        return Err(AppError::CloneBaseCellMismatch(
+                            slot_id.to_owned(),
+                            base_cell_id.to_owned(),
+                        )
+                        .into());
+                    }
                     let parent_dna_hash = slot.dna_hash();
                     let dna = self
                         .dna_store
@@ -743,6 +829,137 @@ where
         Ok((dnas, defs))
     }
 
+    /// The cells of an app, active or inactive, that no other app uses
+    #[allow(clippy::ptr_arg)]
+    pub(super) async fn cells_removed_with_app(
+        &self,
+        installed_app_id: &InstalledAppId,
+    ) -> ConductorResult<Vec<CellId>> {
+        let state = self.get_state().await?;
+        let app = state
+            .active_apps
+            .get(installed_app_id)
+            .or_else(|| state.inactive_apps.get(installed_app_id))
+            .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?;
+        let other_apps: Vec<_> = state
+            .active_apps
+            .values()
+            .chain(state.inactive_apps.values())
+            .filter(|other| other.installed_app_id() != installed_app_id)
+            .collect();
+        Ok(app
+            .all_cells()
+            .chain(app.disabled_cells())
+            .filter(|cell_id| {
+                !other_apps.iter().any(|other| {
+                    other
+                        .all_cells()
+                        .chain(other.disabled_cells())
+                        .any(|id| id == *cell_id)
+                })
+            })
+            .cloned()
+            .collect())
+    }
+
+    /// Remove an app, active or inactive, from the database
+    pub(super) async fn remove_app_from_db(
+        &mut self,
+        installed_app_id: InstalledAppId,
+    ) -> ConductorResult<()> {
+        self.update_state(move |mut state| {
+            state
+                .active_apps
+                .remove(&installed_app_id)
+                .or_else(|| state.inactive_apps.remove(&installed_app_id))
+                .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?;
+            state.deactivation_reasons.remove(&installed_app_id);
+            state.app_crypto_permissions.remove(&installed_app_id);
+            for interface in state.app_interfaces.values_mut() {
+                interface.signal_subscriptions.remove(&installed_app_id);
+            }
+            Ok(state)
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Remove a clone cell, archived or not, from the app that contains it
+    pub(super) async fn remove_clone_cell_from_db(
+        &mut self,
+        cell_id: CellId,
+    ) -> ConductorResult<()> {
+        self.update_state(move |mut state| {
+            let installed_app_id = state
+                .find_app_containing_clone(&cell_id)
+                .map(|app| app.installed_app_id().clone())
+                .ok_or_else(|| ConductorError::CloneCellNotFound(cell_id.clone()))?;
+            let app = state
+                .get_app_mut(&installed_app_id)
+                .expect("The app containing the clone was just found");
+            let slot_id = app
+                .clone_slot(&cell_id)
+                .expect("The app contains the clone")
+                .clone();
+            app.remove_clone(&slot_id, &cell_id)?;
+            Ok(state)
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Archive a clone cell of an app in the database
+    pub(super) async fn archive_clone_cell_in_db(
+        &mut self,
+        installed_app_id: InstalledAppId,
+        slot_id: SlotId,
+        cell_id: CellId,
+    ) -> ConductorResult<()> {
+        self.update_state(move |mut state| {
+            state
+                .get_app_mut(&installed_app_id)
+                .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?
+                .archive_clone(&slot_id, &cell_id)?;
+            Ok(state)
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Restore an archived clone cell of an app in the database
+    pub(super) async fn restore_clone_cell_in_db(
+        &mut self,
+        installed_app_id: InstalledAppId,
+        slot_id: SlotId,
+        cell_id: CellId,
+    ) -> ConductorResult<()> {
+        self.update_state(move |mut state| {
+            state
+                .get_app_mut(&installed_app_id)
+                .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?
+                .restore_clone(&slot_id, &cell_id)?;
+            Ok(state)
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Get a slot of an installed app
+    #[allow(clippy::ptr_arg)]
+    pub(super) async fn get_app_slot(
+        &self,
+        installed_app_id: &InstalledAppId,
+        slot_id: &SlotId,
+    ) -> ConductorResult<AppSlot> {
+        let state = self.get_state().await?;
+        let app = state
+            .active_apps
+            .get(installed_app_id)
+            .or_else(|| state.inactive_apps.get(installed_app_id))
+            .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?;
+        Ok(app.slot(slot_id)?.clone())
+    }
+
     /// Remove cells from the cell map in the Conductor
     pub(super) fn remove_cells(&mut self, cell_ids: Vec<CellId>) {
         for cell_id in cell_ids {
@@ -750,10 +967,29 @@ where
         }
     }
 
-    pub(super) fn add_agent_infos(
+    /// Delete the LMDB environments of cells along with all of their data
+    pub(super) async fn delete_cell_envs(&self, cell_ids: Vec<CellId>) -> ConductorResult<()> {
+        let root_env_dir = std::path::PathBuf::from(self.root_env_dir.clone());
+        for cell_id in cell_ids {
+            EnvironmentWrite::new(
+                &root_env_dir,
+                EnvironmentKind::Cell(cell_id),
+                self.keystore.clone(),
+            )?
+            .remove()
+            .await?;
+        }
+        Ok(())
+    }
+
+    pub(super) async fn add_agent_infos(
         &self,
         agent_infos: Vec<AgentInfoSigned>,
     ) -> ConductorApiResult<()> {
+        // reject the whole batch if any of it is forged or stale
+        for info in agent_infos.iter() {
+            info.verify().await.map_err(ConductorError::from)?;
+        }
         Ok(inject_agent_infos(self.p2p_env.clone(), agent_infos)?)
     }
 
@@ -814,6 +1050,19 @@ where
         Ok(zome_defs)
     }
 
+    /// The registered DNAs and the provisioned cells of every installed app,
+    /// which app bundles are resolved against
+    pub(super) async fn dna_gamut(&self) -> ConductorResult<DnaGamut> {
+        let state = self.get_state().await?;
+        let cells = state
+            .active_apps
+            .values()
+            .chain(state.inactive_apps.values())
+            .flat_map(|app| app.provisioned_cells().map(|(_, cell_id)| cell_id.clone()))
+            .collect::<Vec<_>>();
+        Ok(DnaGamut::new(self.dna_store().list(), cells))
+    }
+
     pub(super) async fn list_cell_ids(&self) -> ConductorResult<Vec<CellId>> {
         Ok(self.cells.keys().cloned().collect())
     }
@@ -823,6 +1072,13 @@ where
         Ok(active_apps.keys().cloned().collect())
     }
 
+    pub(super) async fn list_apps(
+        &self,
+        status_filter: Option<AppStatusFilter>,
+    ) -> ConductorResult<Vec<InstalledAppInfo>> {
+        Ok(self.get_state().await?.list_app_info(status_filter))
+    }
+
     pub(super) async fn dump_cell_state(&self, cell_id: &CellId) -> ConductorApiResult<String> {
         let cell = self.cell_by_id(cell_id)?;
         let arc = cell.env();
@@ -915,6 +1171,9 @@ where
             keystore,
             root_env_dir,
             holochain_p2p,
+            dpki: None,
+            subscribed_bases: SubscribedBases::default(),
+            apps_version: AppsVersion::default(),
         })
     }
 
@@ -948,6 +1207,7 @@ where
             self.state_db.put(txn, &UnitDbKey, &new_state)?;
             Result::<_, ConductorError>::Ok((new_state, output))
         })?;
+        self.apps_version.increment();
         Ok(output)
     }
 
@@ -1113,15 +1373,24 @@ mod builder {
             let holochain_p2p = conductor.holochain_p2p.clone();
 
             // Create handle
-            let handle: ConductorHandle = Arc::new(ConductorHandleImpl {
+            let handle_impl = Arc::new(ConductorHandleImpl {
                 conductor: RwLock::new(conductor),
                 keystore,
                 holochain_p2p,
+                zome_call_config: conductor_config.zome_call.clone(),
             });
+            let handle: ConductorHandle = handle_impl.clone();
+
+            // DPKI is ready before any cell validates ops
+            if let Some(dpki_config) = &conductor_config.dpki {
+                let dpki = dpki_from_config(dpki_config, handle.clone())?;
+                handle_impl.conductor.write().await.set_dpki(dpki);
+            }
 
             handle.load_dnas().await?;
 
             tokio::task::spawn(p2p_event_task(p2p_evt, handle.clone()));
+            tokio::task::spawn(prune_agent_info_task(handle_impl.clone()));
 
             let cell_startup_errors = handle.clone().setup_cells().await?;
 
@@ -1205,6 +1474,26 @@ mod builder {
     }
 }
 
+/// How often expired agent info is removed from the p2p store, so peers
+/// which have left are signalled even if nobody queries for peers
+const PRUNE_AGENT_INFO_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+#[instrument(skip(handle))]
+async fn prune_agent_info_task<DS: DnaStore + 'static>(handle: Arc<ConductorHandleImpl<DS>>) {
+    loop {
+        tokio::time::delay_for(PRUNE_AGENT_INFO_INTERVAL).await;
+        if handle.check_running().await.is_err() {
+            break;
+        }
+        if let Err(e) = handle.prune_agent_info().await {
+            tracing::error!(
+                message = "error pruning expired agent info",
+                error = ?e,
+            );
+        }
+    }
+}
+
 #[instrument(skip(p2p_evt, handle))]
 async fn p2p_event_task(
     p2p_evt: holochain_p2p::event::HolochainP2pEventReceiver,
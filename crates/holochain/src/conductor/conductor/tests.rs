@@ -5,6 +5,8 @@ use crate::test_utils::fake_valid_dna_file;
 use ::fixt::prelude::*;
 use holochain_lmdb::test_utils::test_environments;
 use holochain_types::test_utils::fake_cell_id;
+use holochain_types::test_utils::fake_dna_zomes;
+use holochain_wasm_test_utils::TestWasm;
 use matches::assert_matches;
 
 #[tokio::test(threaded_scheduler)]
@@ -89,13 +91,39 @@ async fn can_add_clone_cell_to_app() {
 
     matches::assert_matches!(
         conductor
-            .add_clone_cell_to_app(&"no clone".to_string(), &"nick".to_string(), ().into())
+            .add_clone_cell_to_app(
+                &"no clone".to_string(),
+                &"nick".to_string(),
+                &cell_id,
+                ().into()
+            )
             .await,
         Err(ConductorError::AppError(AppError::CloneLimitExceeded(0, _)))
     );
 
+    // Clones can't be made from a cell which isn't the base cell of the slot
+    let other_cell_id = CellId::new(cell_id.dna_hash().to_owned(), fixt!(AgentPubKey));
+    matches::assert_matches!(
+        conductor
+            .add_clone_cell_to_app(
+                &"yes clone".to_string(),
+                &"nick".to_string(),
+                &other_cell_id,
+                ().into()
+            )
+            .await,
+        Err(ConductorError::AppError(AppError::CloneBaseCellMismatch(
+            ..
+        )))
+    );
+
     let cloned_cell_id = conductor
-        .add_clone_cell_to_app(&"yes clone".to_string(), &"nick".to_string(), ().into())
+        .add_clone_cell_to_app(
+            &"yes clone".to_string(),
+            &"nick".to_string(),
+            &cell_id,
+            ().into(),
+        )
         .await
         .unwrap();
 
@@ -171,6 +199,53 @@ async fn cell_nicks_are_unique() {
     );
 }
 
+/// App can't be installed if the genesis self check of one of its DNAs
+/// rejects the agent of a cell
+#[tokio::test(threaded_scheduler)]
+async fn genesis_self_check_rejects_install() {
+    let envs = test_environments();
+    let handle = Conductor::builder().test(&envs).await.unwrap();
+    let dna = fake_dna_zomes(
+        "",
+        vec![(
+            TestWasm::GenesisSelfCheck.into(),
+            TestWasm::GenesisSelfCheck.into(),
+        )],
+    );
+    handle.register_dna(dna.clone()).await.unwrap();
+
+    let agent = fixt!(AgentPubKey);
+    let cell_id = CellId::new(dna.dna_hash().to_owned(), agent.clone());
+    let installed_cell = InstalledCell::new(cell_id.clone(), "nick".to_string());
+
+    assert_matches!(
+        handle
+            .clone()
+            .install_app("rejected".to_string(), vec![(installed_cell.clone(), None)])
+            .await,
+        Err(ConductorError::CreateAppFailed(CreateAppError::GenesisSelfCheckFailed {
+            installed_app_id,
+            cell_id: rejected,
+            ..
+        })) if installed_app_id == "rejected" && rejected == cell_id
+    );
+    assert!(handle
+        .get_app_info(&"rejected".to_string())
+        .await
+        .unwrap()
+        .is_none());
+
+    // The same cell passes the check with a membrane proof
+    let proof = SerializedBytes::try_from(()).unwrap();
+    handle
+        .clone()
+        .install_app("accepted".to_string(), vec![(installed_cell, Some(proof))])
+        .await
+        .unwrap();
+
+    handle.shutdown().await;
+}
+
 #[tokio::test(threaded_scheduler)]
 async fn can_set_fake_state() {
     let envs = test_environments();
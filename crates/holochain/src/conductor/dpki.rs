@@ -0,0 +1,339 @@
+//! Distributed Public Key Infrastructure (DPKI) tells the conductor whether an
+//! agent key is still valid, so keys can be revoked when a device is lost.
+//!
+//! The conductor config chooses what backs the [DpkiT] trait:
+//! - [CellDpki] asks a DPKI DNA installed as an app on this conductor,
+//!   with the answers remembered for a while by [CachedDpki]
+//! - [AllowListDpki] reads the key states from a static file
+//!
+//! Without any DPKI config every key is treated as valid.
+
+use super::api::error::ConductorApiError;
+use super::config::DpkiConfig;
+use super::ConductorHandle;
+use holochain_conductor_api::ZomeCall;
+use holochain_types::prelude::*;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+use thiserror::Error;
+
+/// How long the state of a key is remembered before DPKI is asked again
+pub const KEY_STATE_TTL: Duration = Duration::from_secs(60);
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum DpkiError {
+    #[error("Could not read the DPKI allow-list: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Could not parse the DPKI allow-list: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    #[error("The DPKI allow-list contains an invalid agent key: {0}")]
+    BadKey(String),
+
+    #[error("The DPKI allow-list contains an invalid revocation time: {0}")]
+    BadTime(String),
+
+    #[error("The DPKI app {0} is not installed or has no cells")]
+    AppMissing(InstalledAppId),
+
+    #[error("The DPKI zome call did not succeed: {0}")]
+    ZomeCallFailed(String),
+
+    #[error(transparent)]
+    SerializedBytesError(#[from] SerializedBytesError),
+
+    #[error(transparent)]
+    ConductorApiError(#[from] Box<ConductorApiError>),
+}
+
+/// Type alias
+pub type DpkiResult<T> = Result<T, DpkiError>;
+
+/// A shared DPKI backend
+pub type Dpki = Arc<dyn DpkiT>;
+
+/// Looks up the state of agent keys
+#[async_trait::async_trait]
+pub trait DpkiT: Send + Sync {
+    /// What DPKI knows about this key
+    async fn key_state(&self, key: &AgentPubKey) -> DpkiResult<KeyState>;
+
+    /// Is this the app that DPKI itself runs in.
+    /// Its cells are not checked at genesis, as DPKI can't answer for
+    /// anyone until they exist.
+    fn is_dpki_app(&self, _installed_app_id: &InstalledAppId) -> bool {
+        false
+    }
+}
+
+/// Build the DPKI backend chosen by the conductor config
+pub fn dpki_from_config(
+    config: &DpkiConfig,
+    conductor_handle: ConductorHandle,
+) -> DpkiResult<Dpki> {
+    Ok(match config {
+        DpkiConfig::Cell {
+            installed_app_id,
+            zome_name,
+            fn_name,
+        } => Arc::new(CachedDpki::new(
+            Arc::new(CellDpki {
+                conductor_handle,
+                installed_app_id: installed_app_id.clone(),
+                zome_name: zome_name.clone().into(),
+                fn_name: fn_name.clone().into(),
+            }),
+            KEY_STATE_TTL,
+        )),
+        DpkiConfig::AllowList { path } => Arc::new(AllowListDpki::load(path)?),
+    })
+}
+
+/// DPKI backed by a DPKI DNA running in the first cell of an installed app.
+///
+/// The DNA's key state function is called with the key as its payload
+/// and returns a [KeyState]. The agent of that cell is always valid, so the
+/// DPKI cell can commit without asking itself about its own key.
+pub struct CellDpki {
+    conductor_handle: ConductorHandle,
+    installed_app_id: InstalledAppId,
+    zome_name: ZomeName,
+    fn_name: FunctionName,
+}
+
+#[async_trait::async_trait]
+impl DpkiT for CellDpki {
+    async fn key_state(&self, key: &AgentPubKey) -> DpkiResult<KeyState> {
+        let cell_id = self
+            .conductor_handle
+            .get_app_info(&self.installed_app_id)
+            .await
+            .map_err(|e| Box::new(ConductorApiError::from(e)))?
+            .and_then(|app| app.cell_data.first().map(|cell| cell.as_id().clone()))
+            .ok_or_else(|| DpkiError::AppMissing(self.installed_app_id.clone()))?;
+        if cell_id.agent_pubkey() == key {
+            return Ok(KeyState::Valid);
+        }
+        let call = ZomeCall {
+            provenance: cell_id.agent_pubkey().clone(),
+            cell_id,
+            zome_name: self.zome_name.clone(),
+            fn_name: self.fn_name.clone(),
+            payload: ExternIO::encode(key)?,
+            cap: None,
+        };
+        match self
+            .conductor_handle
+            .call_zome(call)
+            .await
+            .map_err(Box::new)?
+            .map_err(|e| DpkiError::ZomeCallFailed(e.to_string()))?
+        {
+            ZomeCallResponse::Ok(output) => Ok(output.decode()?),
+            other => Err(DpkiError::ZomeCallFailed(format!("{:?}", other))),
+        }
+    }
+
+    fn is_dpki_app(&self, installed_app_id: &InstalledAppId) -> bool {
+        *installed_app_id == self.installed_app_id
+    }
+}
+
+/// Remembers the key states given by another DPKI backend for a while,
+/// so validating many ops by the same author asks DPKI once.
+/// Failures to look up a key are not remembered.
+pub struct CachedDpki {
+    inner: Dpki,
+    ttl: Duration,
+    key_states: parking_lot::Mutex<HashMap<AgentPubKey, (KeyState, Instant)>>,
+}
+
+impl CachedDpki {
+    /// Remember the key states of the inner backend for the ttl
+    pub fn new(inner: Dpki, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            key_states: parking_lot::Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DpkiT for CachedDpki {
+    async fn key_state(&self, key: &AgentPubKey) -> DpkiResult<KeyState> {
+        let cached = self.key_states.lock().get(key).copied();
+        if let Some((key_state, looked_up)) = cached {
+            if looked_up.elapsed() < self.ttl {
+                return Ok(key_state);
+            }
+        }
+        let key_state = self.inner.key_state(key).await?;
+        let mut key_states = self.key_states.lock();
+        let ttl = self.ttl;
+        key_states.retain(|_, (_, looked_up)| looked_up.elapsed() < ttl);
+        key_states.insert(key.clone(), (key_state, Instant::now()));
+        Ok(key_state)
+    }
+
+    fn is_dpki_app(&self, installed_app_id: &InstalledAppId) -> bool {
+        self.inner.is_dpki_app(installed_app_id)
+    }
+}
+
+/// DPKI backed by fixed lists of valid and revoked keys.
+/// A key in both lists is revoked.
+#[derive(Debug, Default)]
+pub struct AllowListDpki {
+    valid: HashSet<AgentPubKey>,
+    /// The revoked keys with the time of their revocation, if known
+    revoked: HashMap<AgentPubKey, Option<Timestamp>>,
+}
+
+/// The format of the allow-list file
+#[derive(serde::Deserialize)]
+struct AllowListFile {
+    #[serde(default)]
+    valid: Vec<String>,
+    #[serde(default)]
+    revoked: Vec<RevokedKey>,
+}
+
+/// A revoked key in the allow-list file, either on its own or with the
+/// RFC 3339 time it was revoked at
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum RevokedKey {
+    Key(String),
+    At { key: String, revoked_at: String },
+}
+
+impl AllowListDpki {
+    /// Read the allow-list from a YAML file
+    pub fn load(path: &Path) -> DpkiResult<Self> {
+        Self::from_yaml(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parse the allow-list from YAML, with the keys in their base64 form
+    pub fn from_yaml(yaml: &str) -> DpkiResult<Self> {
+        let file: AllowListFile = serde_yaml::from_str(yaml)?;
+        let parse_key =
+            |key: String| AgentPubKey::try_from(&key).map_err(|_| DpkiError::BadKey(key));
+        let parse_time = |time: String| {
+            chrono::DateTime::parse_from_rfc3339(&time)
+                .map(|time| Timestamp::from(time.with_timezone(&chrono::Utc)))
+                .map_err(|_| DpkiError::BadTime(time))
+        };
+        let valid = file
+            .valid
+            .into_iter()
+            .map(parse_key)
+            .collect::<DpkiResult<_>>()?;
+        let revoked = file
+            .revoked
+            .into_iter()
+            .map(|revoked| match revoked {
+                RevokedKey::Key(key) => Ok((parse_key(key)?, None)),
+                RevokedKey::At { key, revoked_at } => {
+                    Ok((parse_key(key)?, Some(parse_time(revoked_at)?)))
+                }
+            })
+            .collect::<DpkiResult<_>>()?;
+        Ok(Self { valid, revoked })
+    }
+}
+
+#[async_trait::async_trait]
+impl DpkiT for AllowListDpki {
+    async fn key_state(&self, key: &AgentPubKey) -> DpkiResult<KeyState> {
+        Ok(if let Some(revoked_at) = self.revoked.get(key) {
+            KeyState::Revoked(*revoked_at)
+        } else if self.valid.contains(key) {
+            KeyState::Valid
+        } else {
+            KeyState::Unknown
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_types::test_utils::fake_agent_pubkey_1;
+    use holochain_types::test_utils::fake_agent_pubkey_2;
+    use matches::assert_matches;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn allow_list_key_states() {
+        let alice = fake_agent_pubkey_1();
+        let bob = fake_agent_pubkey_2();
+        let carol = AgentPubKey::from_raw_32(vec![3; 32]);
+        let dave = AgentPubKey::from_raw_32(vec![4; 32]);
+        let yaml = format!(
+            concat!(
+                "---\nvalid:\n  - {alice}\n  - {bob}\n",
+                "revoked:\n  - {bob}\n  - key: {dave}\n    revoked_at: \"2021-03-01T00:00:00Z\"\n",
+            ),
+            alice = alice,
+            bob = bob,
+            dave = dave
+        );
+        let dpki = AllowListDpki::from_yaml(&yaml).unwrap();
+
+        assert_eq!(dpki.key_state(&alice).await.unwrap(), KeyState::Valid);
+        assert_eq!(dpki.key_state(&bob).await.unwrap(), KeyState::Revoked(None));
+        assert_eq!(dpki.key_state(&carol).await.unwrap(), KeyState::Unknown);
+        let revoked_at = Timestamp::from(1_614_556_800i64);
+        assert_eq!(
+            dpki.key_state(&dave).await.unwrap(),
+            KeyState::Revoked(Some(revoked_at))
+        );
+
+        assert_matches!(
+            AllowListDpki::from_yaml("valid:\n  - not-a-key\n"),
+            Err(DpkiError::BadKey(_))
+        );
+        let yaml = format!("revoked:\n  - key: {}\n    revoked_at: yesterday\n", dave);
+        assert_matches!(AllowListDpki::from_yaml(&yaml), Err(DpkiError::BadTime(_)));
+    }
+
+    /// Counts how often it is asked
+    #[derive(Default)]
+    struct CountingDpki(AtomicUsize);
+
+    #[async_trait::async_trait]
+    impl DpkiT for CountingDpki {
+        async fn key_state(&self, _key: &AgentPubKey) -> DpkiResult<KeyState> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(KeyState::Valid)
+        }
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn key_states_are_cached() {
+        let alice = fake_agent_pubkey_1();
+        let bob = fake_agent_pubkey_2();
+        let counting = Arc::new(CountingDpki::default());
+        let dpki = CachedDpki::new(counting.clone(), Duration::from_secs(60));
+        for _ in 0..3 {
+            dpki.key_state(&alice).await.unwrap();
+        }
+        dpki.key_state(&bob).await.unwrap();
+        assert_eq!(counting.0.load(Ordering::SeqCst), 2);
+
+        // Expired key states are looked up again
+        let dpki = CachedDpki::new(counting.clone(), Duration::from_secs(0));
+        dpki.key_state(&alice).await.unwrap();
+        dpki.key_state(&alice).await.unwrap();
+        assert_eq!(counting.0.load(Ordering::SeqCst), 4);
+    }
+}
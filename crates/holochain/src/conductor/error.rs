@@ -1,6 +1,8 @@
+use super::dpki::DpkiError;
 use super::interface::error::InterfaceError;
 use super::{entry_def_store::error::EntryDefStoreError, state::AppInterfaceId};
 use crate::conductor::cell::error::CellError;
+use crate::core::ribosome::error::RibosomeError;
 use crate::core::workflow::error::WorkflowError;
 use holochain_conductor_api::conductor::ConductorConfigError;
 use holochain_lmdb::error::DatabaseError;
@@ -39,6 +41,9 @@ pub enum ConductorError {
     #[error(transparent)]
     ConductorConfigError(#[from] ConductorConfigError),
 
+    #[error(transparent)]
+    DpkiError(#[from] DpkiError),
+
     #[error("Configuration consistency error: {0}")]
     ConfigError(String),
 
@@ -91,6 +96,9 @@ pub enum ConductorError {
     #[error("Tried to perform an operation on an app that was not active: {0}")]
     AppNotActive(InstalledAppId),
 
+    #[error("No installed app has the clone cell: {0:?}")]
+    CloneCellNotFound(CellId),
+
     #[error(transparent)]
     HolochainP2pError(#[from] holochain_p2p::HolochainP2pError),
 
@@ -105,6 +113,9 @@ pub enum ConductorError {
 
     #[error(transparent)]
     MrBundleError(#[from] mr_bundle::error::MrBundleError),
+
+    #[error(transparent)]
+    RibosomeError(#[from] RibosomeError),
 }
 
 #[derive(Error, Debug)]
@@ -114,6 +125,35 @@ pub enum CreateAppError {
         installed_app_id: InstalledAppId,
         errors: Vec<CellError>,
     },
+    #[error("The {installed_app_id} app can't be installed because the genesis self check of zome {zome_name} rejected cell {cell_id:?}: {reason}")]
+    GenesisSelfCheckFailed {
+        installed_app_id: InstalledAppId,
+        cell_id: CellId,
+        zome_name: ZomeName,
+        reason: String,
+    },
+}
+
+impl CreateAppError {
+    /// The app which couldn't be created
+    pub fn installed_app_id(&self) -> &InstalledAppId {
+        match self {
+            CreateAppError::Failed {
+                installed_app_id, ..
+            }
+            | CreateAppError::GenesisSelfCheckFailed {
+                installed_app_id, ..
+            } => installed_app_id,
+        }
+    }
+
+    /// Whether setting up the app again can't succeed
+    pub fn is_permanent(&self) -> bool {
+        match self {
+            CreateAppError::Failed { errors, .. } => errors.iter().any(CellError::is_permanent),
+            CreateAppError::GenesisSelfCheckFailed { .. } => true,
+        }
+    }
 }
 
 // TODO: can this be removed?
@@ -44,22 +44,29 @@
 use super::api::error::ConductorApiResult;
 use super::api::ZomeCall;
 use super::config::AdminInterfaceConfig;
+use super::config::ZomeCallConfig;
 use super::error::ConductorResult;
 use super::error::CreateAppError;
 use super::interface::SignalBroadcaster;
 use super::manager::TaskManagerRunHandle;
 use super::p2p_store::get_agent_info_signed;
+use super::p2p_store::is_new_agent_info;
+use super::p2p_store::prune_agent_info_signed;
 use super::p2p_store::put_agent_info_signed;
-use super::p2p_store::query_agent_info_signed;
+use super::p2p_store::query_and_prune_agent_info_signed;
 use super::Cell;
 use super::Conductor;
 use crate::core::workflow::CallZomeWorkspaceLock;
 use crate::core::workflow::ZomeCallResult;
 use derive_more::From;
 use futures::future::FutureExt;
+use holochain_conductor_api::AppCryptoPermissions;
+use holochain_conductor_api::AppStatusFilter;
+use holochain_conductor_api::DeactivationReason;
 use holochain_conductor_api::InstalledAppInfo;
 use holochain_p2p::event::HolochainP2pEvent::*;
 use holochain_types::prelude::*;
+use kitsune_p2p::agent_store::AgentInfo;
 use kitsune_p2p::agent_store::AgentInfoSigned;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -98,8 +105,12 @@ pub trait ConductorHandleT: Send + Sync {
     /// Should only be run once at Conductor initialization.
     async fn startup_app_interfaces(self: Arc<Self>) -> ConductorResult<()>;
 
-    /// Add an app interface
-    async fn add_app_interface(self: Arc<Self>, port: u16) -> ConductorResult<u16>;
+    /// Add an app interface, which makes crypto requests for the given app
+    async fn add_app_interface(
+        self: Arc<Self>,
+        port: u16,
+        installed_app_id: Option<InstalledAppId>,
+    ) -> ConductorResult<u16>;
 
     /// Install a [Dna] in this Conductor
     async fn register_dna(&self, dna: DnaFile) -> ConductorResult<()>;
@@ -139,6 +150,13 @@ pub trait ConductorHandleT: Send + Sync {
     /// Cue the autonomic system to perform some action early (experimental)
     async fn autonomic_cue(&self, cue: AutonomicCue, cell_id: &CellId) -> ConductorApiResult<()>;
 
+    /// Measure how well the ops a cell has authored are held on the DHT
+    async fn cell_health(&self, cell_id: &CellId) -> ConductorApiResult<CellHealth>;
+
+    /// Ask DPKI what it knows about an agent key.
+    /// Every key is valid if this conductor has no DPKI configured.
+    async fn agent_key_state(&self, key: &AgentPubKey) -> ConductorApiResult<KeyState>;
+
     /// Get a Websocket port which will
     async fn get_arbitrary_admin_websocket_port(&self) -> Option<u16>;
 
@@ -158,15 +176,43 @@ pub trait ConductorHandleT: Send + Sync {
     /// Request access to this conductor's networking handle
     fn holochain_p2p(&self) -> &holochain_p2p::HolochainP2pRef;
 
+    /// The time limits for zome calls on this conductor
+    fn zome_call_config(&self) -> ZomeCallConfig;
+
     /// Create a new Cell in an existing App based on an existing DNA
     async fn create_clone_cell(
         self: Arc<Self>,
         payload: CreateCloneCellPayload,
     ) -> ConductorResult<CellId>;
 
-    /// Destroy a cloned Cell
+    /// Destroy a cloned Cell, archived or not, removing it from its app and
+    /// deleting all of its data
     async fn destroy_clone_cell(self: Arc<Self>, cell_id: CellId) -> ConductorResult<()>;
 
+    /// Archive a cloned Cell. The Cell stops running but its data is kept
+    /// so that it can be restored later.
+    async fn archive_clone_cell(
+        &self,
+        installed_app_id: InstalledAppId,
+        slot_id: SlotId,
+        cell_id: CellId,
+    ) -> ConductorResult<()>;
+
+    /// Restore an archived Cell, running it again if its app is active
+    async fn restore_clone_cell(
+        self: Arc<Self>,
+        installed_app_id: InstalledAppId,
+        slot_id: SlotId,
+        cell_id: CellId,
+    ) -> ConductorResult<()>;
+
+    /// Get a slot of an installed app, including its clones
+    async fn get_app_slot(
+        &self,
+        installed_app_id: &InstalledAppId,
+        slot_id: &SlotId,
+    ) -> ConductorResult<AppSlot>;
+
     /// Install Cells into ConductorState based on installation info, and run
     /// genesis on all new source chains
     async fn install_app(
@@ -188,8 +234,17 @@ pub trait ConductorHandleT: Send + Sync {
     /// Activate an app
     async fn activate_app(&self, installed_app_id: InstalledAppId) -> ConductorResult<()>;
 
-    /// Deactivate an app
-    async fn deactivate_app(&self, installed_app_id: InstalledAppId) -> ConductorResult<()>;
+    /// Deactivate an app, recording why it was deactivated
+    async fn deactivate_app(
+        &self,
+        installed_app_id: InstalledAppId,
+        reason: DeactivationReason,
+    ) -> ConductorResult<()>;
+
+    /// Uninstall an app, active or inactive, shutting down its cells and
+    /// deleting their data unless another app uses them.
+    /// The app stays installed if its cells can't be cleaned up.
+    async fn uninstall_app(&self, installed_app_id: &InstalledAppId) -> ConductorResult<()>;
 
     /// List Cell Ids
     async fn list_cell_ids(&self) -> ConductorResult<Vec<CellId>>;
@@ -197,6 +252,18 @@ pub trait ConductorHandleT: Send + Sync {
     /// List Active AppIds
     async fn list_active_apps(&self) -> ConductorResult<Vec<InstalledAppId>>;
 
+    /// List the info of the installed apps, optionally filtered by status
+    async fn list_apps(
+        &self,
+        status_filter: Option<AppStatusFilter>,
+    ) -> ConductorResult<Vec<InstalledAppInfo>>;
+
+    /// Get the app, active or inactive, that contains a cell
+    async fn find_app_containing_cell(
+        &self,
+        cell_id: &CellId,
+    ) -> ConductorResult<Option<InstalledApp>>;
+
     /// Dump the cells state
     async fn dump_cell_state(&self, cell_id: &CellId) -> ConductorApiResult<String>;
 
@@ -210,6 +277,26 @@ pub trait ConductorHandleT: Send + Sync {
         installed_app_id: &InstalledAppId,
     ) -> ConductorResult<Option<InstalledAppInfo>>;
 
+    /// Get the cells of an installed App, whether active or inactive,
+    /// including its clones
+    async fn get_app_cells(
+        &self,
+        installed_app_id: &InstalledAppId,
+    ) -> ConductorResult<Option<Vec<CellId>>>;
+
+    /// Set which crypto requests an installed app may make over app interfaces
+    async fn set_app_crypto_permissions(
+        &self,
+        installed_app_id: InstalledAppId,
+        permissions: AppCryptoPermissions,
+    ) -> ConductorResult<()>;
+
+    /// Get which crypto requests an app may make over app interfaces
+    async fn get_app_crypto_permissions(
+        &self,
+        installed_app_id: &InstalledAppId,
+    ) -> ConductorResult<AppCryptoPermissions>;
+
     /// Add signed agent info to the conductor
     async fn add_agent_infos(&self, agent_infos: Vec<AgentInfoSigned>) -> ConductorApiResult<()>;
 
@@ -256,6 +343,7 @@ pub struct ConductorHandleImpl<DS: DnaStore + 'static> {
     pub(crate) conductor: RwLock<Conductor<DS>>,
     pub(crate) keystore: KeystoreSender,
     pub(crate) holochain_p2p: holochain_p2p::HolochainP2pRef,
+    pub(crate) zome_call_config: ZomeCallConfig,
 }
 
 #[async_trait::async_trait]
@@ -282,9 +370,14 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
             .await
     }
 
-    async fn add_app_interface(self: Arc<Self>, port: u16) -> ConductorResult<u16> {
+    async fn add_app_interface(
+        self: Arc<Self>,
+        port: u16,
+        installed_app_id: Option<InstalledAppId>,
+    ) -> ConductorResult<u16> {
         let mut lock = self.conductor.write().await;
-        lock.add_app_interface_via_handle(port, self.clone()).await
+        lock.add_app_interface_via_handle(port, installed_app_id, self.clone())
+            .await
     }
 
     async fn register_dna(&self, dna: DnaFile) -> ConductorResult<()> {
@@ -337,13 +430,21 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         trace!(agent = ?cell_id.agent_pubkey(), dispatch_event = ?event);
         match event {
             PutAgentInfoSigned {
+                dna_hash,
                 agent_info_signed,
                 respond,
                 ..
             } => {
                 let env = { self.conductor.read().await.p2p_env() };
+                let is_new = is_new_agent_info(env.clone(), &agent_info_signed);
+                let agent =
+                    holochain_p2p::agent_kit_to_holo(agent_info_signed.as_agent_ref().clone());
                 let res = put_agent_info_signed(env, agent_info_signed)
                     .map_err(holochain_p2p::HolochainP2pError::other);
+                if let (Ok(true), Ok(())) = (is_new, &res) {
+                    self.emit_peer_signal(SystemSignal::PeerJoined { dna_hash, agent })
+                        .await;
+                }
                 respond.respond(Ok(async move { res }.boxed().into()));
             }
             GetAgentInfoSigned {
@@ -363,8 +464,14 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
                 ..
             } => {
                 let env = { self.conductor.read().await.p2p_env() };
-                let res = query_agent_info_signed(env, kitsune_space)
-                    .map_err(holochain_p2p::HolochainP2pError::other);
+                let res = query_and_prune_agent_info_signed(env, kitsune_space);
+                let res = match res {
+                    Ok((agent_infos, pruned)) => {
+                        self.emit_peers_left(pruned).await;
+                        Ok(agent_infos)
+                    }
+                    Err(e) => Err(holochain_p2p::HolochainP2pError::other(e)),
+                };
                 respond.respond(Ok(async move { res }.boxed().into()));
             }
             SignNetworkData { respond, data, .. } => {
@@ -399,10 +506,24 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
 
     async fn autonomic_cue(&self, cue: AutonomicCue, cell_id: &CellId) -> ConductorApiResult<()> {
         let cell = self.cell_by_id(cell_id).await?;
-        let _ = cell.handle_autonomic_process(cue.into()).await;
+        cell.handle_autonomic_process(cue.into()).await?;
         Ok(())
     }
 
+    async fn cell_health(&self, cell_id: &CellId) -> ConductorApiResult<CellHealth> {
+        let cell = self.cell_by_id(cell_id).await?;
+        Ok(cell.health().await?)
+    }
+
+    async fn agent_key_state(&self, key: &AgentPubKey) -> ConductorApiResult<KeyState> {
+        // Release the lock before asking, as a DPKI cell is called through this handle
+        let dpki = self.conductor.read().await.dpki();
+        match dpki {
+            Some(dpki) => Ok(dpki.key_state(key).await?),
+            None => Ok(KeyState::Valid),
+        }
+    }
+
     async fn take_shutdown_handle(&self) -> Option<TaskManagerRunHandle> {
         self.conductor.write().await.take_shutdown_handle()
     }
@@ -422,6 +543,10 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         &self.keystore
     }
 
+    fn zome_call_config(&self) -> ZomeCallConfig {
+        self.zome_call_config.clone()
+    }
+
     fn holochain_p2p(&self) -> &holochain_p2p::HolochainP2pRef {
         &self.holochain_p2p
     }
@@ -430,32 +555,128 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         self: Arc<Self>,
         payload: CreateCloneCellPayload,
     ) -> ConductorResult<CellId> {
+        let base_cell_id = payload.cell_id();
         let CreateCloneCellPayload {
             properties,
-            dna_hash,
             installed_app_id,
-            agent_key,
             slot_id,
             membrane_proof,
+            ..
         } = payload;
-        {
+        let cell_id = {
+            let mut conductor = self.conductor.write().await;
+            let properties = properties.unwrap_or_else(|| ().into());
+            conductor
+                .add_clone_cell_to_app(&installed_app_id, &slot_id, &base_cell_id, properties)
+                .await?
+        };
+        // The clone is created from a new DNA so genesis must run for the
+        // cell of the clone
+        let genesis = {
             let conductor = self.conductor.read().await;
-            let cell_id = CellId::new(dna_hash, agent_key);
             let cells = vec![(cell_id.clone(), membrane_proof)];
-            conductor.genesis_cells(cells, self.clone()).await?;
+            conductor
+                .genesis_cells(&installed_app_id, cells, self.clone())
+                .await
+        };
+        if let Err(e) = genesis {
+            self.conductor
+                .write()
+                .await
+                .remove_clone_cell_from_db(cell_id)
+                .await?;
+            return Err(e);
+        }
+        // Only the new cell is started, the cells of other apps are untouched
+        if let Err(e) = self.clone().start_cell(cell_id.clone()).await {
+            self.conductor
+                .write()
+                .await
+                .remove_clone_cell_from_db(cell_id.clone())
+                .await?;
+            self.conductor
+                .read()
+                .await
+                .delete_cell_envs(vec![cell_id])
+                .await?;
+            return Err(e);
         }
-        {
+        Ok(cell_id)
+    }
+
+    async fn destroy_clone_cell(self: Arc<Self>, cell_id: CellId) -> ConductorResult<()> {
+        self.conductor
+            .write()
+            .await
+            .remove_clone_cell_from_db(cell_id.clone())
+            .await?;
+        self.shut_down_cells(&[cell_id.clone()]).await?;
+        self.conductor
+            .read()
+            .await
+            .delete_cell_envs(vec![cell_id])
+            .await
+    }
+
+    async fn archive_clone_cell(
+        &self,
+        installed_app_id: InstalledAppId,
+        slot_id: SlotId,
+        cell_id: CellId,
+    ) -> ConductorResult<()> {
+        self.conductor
+            .write()
+            .await
+            .archive_clone_cell_in_db(installed_app_id, slot_id, cell_id.clone())
+            .await?;
+        self.shut_down_cells(&[cell_id]).await
+    }
+
+    async fn restore_clone_cell(
+        self: Arc<Self>,
+        installed_app_id: InstalledAppId,
+        slot_id: SlotId,
+        cell_id: CellId,
+    ) -> ConductorResult<()> {
+        let is_active = {
             let mut conductor = self.conductor.write().await;
-            let properties = properties.unwrap_or_else(|| ().into());
-            let cell_id = conductor
-                .add_clone_cell_to_app(&installed_app_id, &slot_id, properties)
+            conductor
+                .restore_clone_cell_in_db(
+                    installed_app_id.clone(),
+                    slot_id.clone(),
+                    cell_id.clone(),
+                )
                 .await?;
-            Ok(cell_id)
+            conductor
+                .get_state()
+                .await?
+                .active_apps
+                .contains_key(&installed_app_id)
+        };
+        // Only the restored cell is started, and only if the app is active
+        if is_active {
+            if let Err(e) = self.clone().start_cell(cell_id.clone()).await {
+                self.conductor
+                    .write()
+                    .await
+                    .archive_clone_cell_in_db(installed_app_id, slot_id, cell_id)
+                    .await?;
+                return Err(e);
+            }
         }
+        Ok(())
     }
 
-    async fn destroy_clone_cell(self: Arc<Self>, _cell_id: CellId) -> ConductorResult<()> {
-        todo!()
+    async fn get_app_slot(
+        &self,
+        installed_app_id: &InstalledAppId,
+        slot_id: &SlotId,
+    ) -> ConductorResult<AppSlot> {
+        self.conductor
+            .read()
+            .await
+            .get_app_slot(installed_app_id, slot_id)
+            .await
     }
 
     async fn install_app(
@@ -463,16 +684,14 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         installed_app_id: InstalledAppId,
         cell_data: Vec<(InstalledCell, Option<MembraneProof>)>,
     ) -> ConductorResult<()> {
+        let cell_ids_with_proofs = cell_data
+            .iter()
+            .map(|(c, p)| (c.as_id().clone(), p.clone()))
+            .collect();
         self.conductor
             .read()
             .await
-            .genesis_cells(
-                cell_data
-                    .iter()
-                    .map(|(c, p)| (c.as_id().clone(), p.clone()))
-                    .collect(),
-                self.clone(),
-            )
+            .genesis_cells(&installed_app_id, cell_ids_with_proofs, self.clone())
             .await?;
 
         let cell_data = cell_data.into_iter().map(|(c, _)| c);
@@ -501,8 +720,9 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
 
         let installed_app_id =
             installed_app_id.unwrap_or_else(|| bundle.manifest().app_name().to_owned());
+        let gamut = self.conductor.read().await.dna_gamut().await?;
         let ops = bundle
-            .resolve_cells(agent_key.clone(), DnaGamut::placeholder(), membrane_proofs)
+            .resolve_cells(agent_key.clone(), gamut, membrane_proofs)
             .await?;
 
         let cells_to_create = ops.cells_to_create();
@@ -514,7 +734,7 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         self.conductor
             .read()
             .await
-            .genesis_cells(cells_to_create, self.clone())
+            .genesis_cells(&installed_app_id, cells_to_create, self.clone())
             .await?;
 
         let slots = ops.slots;
@@ -546,12 +766,30 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
                 Err(e) => Some(e),
             }
         });
-        let r = futures::future::join_all(add_cells_tasks)
+        let r: Vec<CreateAppError> = futures::future::join_all(add_cells_tasks)
             .await
             .into_iter()
             // Remove successful and collect the errors
             .filter_map(|r| r)
             .collect();
+        // Quarantine the apps whose cells can never be created so they are
+        // not set up again until they are activated again.
+        // Apps which failed for a reason that may clear up are left active
+        // so the next setup retries them.
+        for error in r.iter().filter(|error| error.is_permanent()) {
+            let installed_app_id = error.installed_app_id().clone();
+            if let Err(e) = self
+                .deactivate_app(
+                    installed_app_id.clone(),
+                    DeactivationReason::Quarantined {
+                        error: error.to_string(),
+                    },
+                )
+                .await
+            {
+                error!(?installed_app_id, error = ?e, "Failed to quarantine app");
+            }
+        }
         Ok(r)
     }
 
@@ -564,12 +802,16 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         // MD: Should we be doing `Conductor::add_cells()` here? (see below comment)
     }
 
-    async fn deactivate_app(&self, installed_app_id: InstalledAppId) -> ConductorResult<()> {
+    async fn deactivate_app(
+        &self,
+        installed_app_id: InstalledAppId,
+        reason: DeactivationReason,
+    ) -> ConductorResult<()> {
         let cell_ids_to_remove = self
             .conductor
             .write()
             .await
-            .deactivate_app_in_db(installed_app_id)
+            .deactivate_app_in_db(installed_app_id, reason)
             .await?;
         // MD: I'm not sure about this. We never add the cells back in after re-activating an app,
         //     so it seems either we shouldn't remove them here, or we should be sure to add them
@@ -581,6 +823,28 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         Ok(())
     }
 
+    async fn uninstall_app(&self, installed_app_id: &InstalledAppId) -> ConductorResult<()> {
+        let cell_ids_to_remove = self
+            .conductor
+            .read()
+            .await
+            .cells_removed_with_app(installed_app_id)
+            .await?;
+        // The app is only removed once its cells are cleaned up, so an
+        // uninstall which fails part way can be retried
+        self.shut_down_cells(&cell_ids_to_remove).await?;
+        self.conductor
+            .read()
+            .await
+            .delete_cell_envs(cell_ids_to_remove)
+            .await?;
+        self.conductor
+            .write()
+            .await
+            .remove_app_from_db(installed_app_id.clone())
+            .await
+    }
+
     async fn list_cell_ids(&self) -> ConductorResult<Vec<CellId>> {
         self.conductor.read().await.list_cell_ids().await
     }
@@ -589,6 +853,27 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         self.conductor.read().await.list_active_apps().await
     }
 
+    async fn list_apps(
+        &self,
+        status_filter: Option<AppStatusFilter>,
+    ) -> ConductorResult<Vec<InstalledAppInfo>> {
+        self.conductor.read().await.list_apps(status_filter).await
+    }
+
+    async fn find_app_containing_cell(
+        &self,
+        cell_id: &CellId,
+    ) -> ConductorResult<Option<InstalledApp>> {
+        Ok(self
+            .conductor
+            .read()
+            .await
+            .get_state()
+            .await?
+            .find_app_containing_cell(cell_id)
+            .cloned())
+    }
+
     async fn dump_cell_state(&self, cell_id: &CellId) -> ConductorApiResult<String> {
         self.conductor.read().await.dump_cell_state(cell_id).await
     }
@@ -610,8 +895,47 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
             .get_app_info(installed_app_id))
     }
 
+    async fn get_app_cells(
+        &self,
+        installed_app_id: &InstalledAppId,
+    ) -> ConductorResult<Option<Vec<CellId>>> {
+        Ok(self
+            .conductor
+            .read()
+            .await
+            .get_state()
+            .await?
+            .get_app(installed_app_id)
+            .map(|app| app.all_cells().cloned().collect()))
+    }
+
+    async fn set_app_crypto_permissions(
+        &self,
+        installed_app_id: InstalledAppId,
+        permissions: AppCryptoPermissions,
+    ) -> ConductorResult<()> {
+        self.conductor
+            .write()
+            .await
+            .set_app_crypto_permissions_in_db(installed_app_id, permissions)
+            .await
+    }
+
+    async fn get_app_crypto_permissions(
+        &self,
+        installed_app_id: &InstalledAppId,
+    ) -> ConductorResult<AppCryptoPermissions> {
+        Ok(self
+            .conductor
+            .read()
+            .await
+            .get_state()
+            .await?
+            .app_crypto_permissions(installed_app_id))
+    }
+
     async fn add_agent_infos(&self, agent_infos: Vec<AgentInfoSigned>) -> ConductorApiResult<()> {
-        self.conductor.read().await.add_agent_infos(agent_infos)
+        self.conductor.read().await.add_agent_infos(agent_infos).await
     }
 
     async fn get_agent_infos(
@@ -664,4 +988,76 @@ impl<DS: DnaStore + 'static> ConductorHandleImpl<DS> {
         let lock = self.conductor.read().await;
         Ok(lock.cell_by_id(cell_id)?)
     }
+
+    /// Create a single cell and add it to the conductor, leaving all
+    /// other cells untouched
+    async fn start_cell(self: Arc<Self>, cell_id: CellId) -> ConductorResult<()> {
+        let cell = {
+            let conductor = self.conductor.read().await;
+            conductor.create_cell(cell_id, self.clone()).await?
+        };
+        self.conductor.write().await.add_cells(vec![cell]);
+        Ok(())
+    }
+
+    /// Remove expired agent info from the p2p store and signal
+    /// that those peers have left
+    pub(super) async fn prune_agent_info(&self) -> ConductorResult<()> {
+        let env = { self.conductor.read().await.p2p_env() };
+        let pruned = prune_agent_info_signed(env)?;
+        self.emit_peers_left(pruned).await;
+        Ok(())
+    }
+
+    /// Signal that the peers of this expired agent info have left
+    async fn emit_peers_left(&self, pruned: Vec<AgentInfo>) {
+        for info in pruned {
+            let dna_hash = holochain_p2p::space_kit_to_holo(info.as_space_ref().clone());
+            let agent = holochain_p2p::agent_kit_to_holo(info.as_agent_ref().clone());
+            self.emit_peer_signal(SystemSignal::PeerLeft { dna_hash, agent })
+                .await;
+        }
+    }
+
+    /// Signal a peer joining or leaving a network, unless the peer is the
+    /// agent of one of our own cells, and cue the autonomic processes of
+    /// our cells in that network
+    async fn emit_peer_signal(&self, signal: SystemSignal) {
+        let (dna_hash, agent, cue) = match &signal {
+            SystemSignal::PeerJoined { dna_hash, agent } => {
+                (dna_hash, agent, AutonomicCue::PeerJoined)
+            }
+            SystemSignal::PeerLeft { dna_hash, agent } => (dna_hash, agent, AutonomicCue::PeerLeft),
+            _ => {
+                self.signal_broadcaster().await.send_system(signal);
+                return;
+            }
+        };
+        let cell_id = CellId::new(dna_hash.clone(), agent.clone());
+        if self.cell_by_id(&cell_id).await.is_ok() {
+            return;
+        }
+        let cell_ids = self.list_cell_ids().await.unwrap_or_default();
+        for cell_id in cell_ids.iter().filter(|c| c.dna_hash() == dna_hash) {
+            if let Err(e) = self.autonomic_cue(cue, cell_id).await {
+                warn!(?cell_id, ?cue, error = ?e, "Failed to cue autonomic process");
+            }
+        }
+        self.signal_broadcaster().await.send_system(signal);
+    }
+
+    /// Stop running cells and leave the network as their agents
+    async fn shut_down_cells(&self, cell_ids: &[CellId]) -> ConductorResult<()> {
+        use holochain_p2p::actor::HolochainP2pRefToCell;
+        use holochain_p2p::HolochainP2pCellT;
+
+        self.conductor.write().await.remove_cells(cell_ids.to_vec());
+        for cell_id in cell_ids {
+            self.holochain_p2p
+                .to_cell(cell_id.dna_hash().clone(), cell_id.agent_pubkey().clone())
+                .leave()
+                .await?;
+        }
+        Ok(())
+    }
 }
@@ -8,9 +8,19 @@
 use crate::conductor::api::*;
 use error::InterfaceError;
 use error::InterfaceResult;
+use holo_hash::AnyDhtHash;
+use holochain_conductor_api::signal_subscription::SignalSubscription;
+use holochain_types::app::InstalledAppId;
 use holochain_types::signal::Signal;
+use holochain_types::signal::SystemSignal;
+use holochain_zome_types::cell::CellId;
+use std::collections::HashMap;
 use std::convert::TryInto;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use tokio::sync::broadcast;
+use tokio::sync::RwLock;
 
 #[allow(missing_docs)]
 pub mod error;
@@ -50,6 +60,8 @@ impl AppInterfaceRuntime {
 #[derive(Clone, Debug)]
 pub struct SignalBroadcaster {
     senders: Vec<broadcast::Sender<Signal>>,
+    subscribed_bases: SubscribedBases,
+    apps_version: AppsVersion,
 }
 
 impl SignalBroadcaster {
@@ -63,17 +75,53 @@ impl SignalBroadcaster {
         Ok(())
     }
 
+    /// Send a system signal to every connected client.
+    /// An interface with no connected clients is not an error for system
+    /// signals, so they are sent to each interface regardless.
+    pub fn send_system(&mut self, sig: SystemSignal) {
+        let sig = Signal::from(sig);
+        for tx in self.senders.iter_mut() {
+            if tx.send(sig.clone()).is_err() {
+                tracing::trace!(?sig, "No clients connected to receive the system signal");
+            }
+        }
+    }
+
     /// internal constructor
     pub fn new(senders: Vec<broadcast::Sender<Signal>>) -> Self {
-        Self { senders }
+        Self {
+            senders,
+            subscribed_bases: SubscribedBases::default(),
+            apps_version: AppsVersion::default(),
+        }
+    }
+
+    /// Use the bases subscribed to by the connections of the interfaces
+    pub fn with_subscribed_bases(mut self, subscribed_bases: SubscribedBases) -> Self {
+        self.subscribed_bases = subscribed_bases;
+        self
+    }
+
+    /// The bases which connections have subscribed to signals about
+    pub fn subscribed_bases(&self) -> &SubscribedBases {
+        &self.subscribed_bases
+    }
+
+    /// Use the version of the conductor's installed apps
+    pub fn with_apps_version(mut self, apps_version: AppsVersion) -> Self {
+        self.apps_version = apps_version;
+        self
+    }
+
+    /// The version of the installed apps, which changes whenever they do
+    pub fn apps_version(&self) -> &AppsVersion {
+        &self.apps_version
     }
 
     #[cfg(test)]
     /// A sender with nothing to send to. A placeholder for tests
     pub fn noop() -> Self {
-        Self {
-            senders: Vec::new(),
-        }
+        Self::new(Vec::new())
     }
 
     #[cfg(any(test, feature = "test_utils"))]
@@ -98,4 +146,166 @@ impl SignalBroadcaster {
     }
 }
 
+/// The bases which connections to app interfaces have subscribed to
+/// signals about, with the number of subscriptions to each.
+/// Integrated ops are only signalled at these bases, so cells don't signal
+/// every op they integrate when nobody is listening for them.
+#[derive(Clone, Debug, Default)]
+pub struct SubscribedBases(Arc<parking_lot::Mutex<HashMap<AnyDhtHash, usize>>>);
+
+impl SubscribedBases {
+    /// Has any connection subscribed to signals about this basis?
+    pub fn contains(&self, basis: &AnyDhtHash) -> bool {
+        self.0.lock().contains_key(basis)
+    }
+
+    /// Count a subscription to each of these bases
+    pub fn add(&self, bases: &[AnyDhtHash]) {
+        let mut counts = self.0.lock();
+        for basis in bases {
+            *counts.entry(basis.clone()).or_insert(0) += 1;
+        }
+    }
+
+    /// Remove a subscription to each of these bases
+    pub fn remove(&self, bases: &[AnyDhtHash]) {
+        let mut counts = self.0.lock();
+        for basis in bases {
+            if let Some(count) = counts.get_mut(basis) {
+                *count -= 1;
+                if *count == 0 {
+                    counts.remove(basis);
+                }
+            }
+        }
+    }
+}
+
+/// Counts the changes to the conductor's installed apps, so anything
+/// caching what it knows about an app can tell when to look it up again
+#[derive(Clone, Debug, Default)]
+pub struct AppsVersion(Arc<AtomicU64>);
+
+impl AppsVersion {
+    /// The current version
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Move on to a new version after the apps changed
+    pub fn increment(&self) {
+        self.0.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// The signal subscriptions made by a single connection to an app interface.
+/// A connection without any subscription receives every signal.
+#[derive(Clone, Debug, Default)]
+pub struct SignalSubscriptions(Arc<RwLock<ConnectionSubscriptions>>);
+
+#[derive(Debug, Default)]
+struct ConnectionSubscriptions {
+    apps: HashMap<InstalledAppId, AppSubscription>,
+    /// Where the bases of these subscriptions are counted
+    subscribed_bases: SubscribedBases,
+    /// The version of the installed apps the cells are checked against
+    apps_version: AppsVersion,
+}
+
+/// A subscription with the cells of its app at a version of the apps
+#[derive(Debug)]
+struct AppSubscription {
+    subscription: SignalSubscription,
+    cells: Vec<CellId>,
+    version: u64,
+}
+
+impl Drop for ConnectionSubscriptions {
+    /// The bases are no longer subscribed to once the connection is gone
+    fn drop(&mut self) {
+        for app in self.apps.values() {
+            self.subscribed_bases
+                .remove(&app.subscription.filters().integrated_op_bases());
+        }
+    }
+}
+
+impl SignalSubscriptions {
+    /// Set the subscription for an app, replacing any previous subscription
+    /// for the app, along with the cells of the app at the given version of
+    /// the apps. The bases of the subscription are counted in the
+    /// broadcaster's subscribed bases.
+    pub async fn subscribe(
+        &self,
+        subscription: SignalSubscription,
+        cells: Vec<CellId>,
+        version: u64,
+        signal_broadcaster: &SignalBroadcaster,
+    ) {
+        let mut subscriptions = self.0.write().await;
+        let subscribed_bases = signal_broadcaster.subscribed_bases().clone();
+        subscribed_bases.add(&subscription.filters().integrated_op_bases());
+        subscriptions.subscribed_bases = subscribed_bases;
+        subscriptions.apps_version = signal_broadcaster.apps_version().clone();
+        let app = AppSubscription {
+            subscription,
+            cells,
+            version,
+        };
+        if let Some(previous) = subscriptions
+            .apps
+            .insert(app.subscription.installed_app_id().clone(), app)
+        {
+            subscriptions
+                .subscribed_bases
+                .remove(&previous.subscription.filters().integrated_op_bases());
+        }
+    }
+
+    /// Has the connection made no subscriptions?
+    pub async fn is_empty(&self) -> bool {
+        self.0.read().await.apps.is_empty()
+    }
+
+    /// The apps whose cells were looked up before the apps last changed,
+    /// with the current version of the apps
+    pub async fn stale_apps(&self) -> Vec<(InstalledAppId, u64)> {
+        let subscriptions = self.0.read().await;
+        let version = subscriptions.apps_version.get();
+        subscriptions
+            .apps
+            .iter()
+            .filter(|(_, app)| app.version != version)
+            .map(|(installed_app_id, _)| (installed_app_id.clone(), version))
+            .collect()
+    }
+
+    /// Replace the cells of a subscribed app with those at a newer version
+    /// of the apps
+    pub async fn refresh_cells(
+        &self,
+        installed_app_id: &InstalledAppId,
+        cells: Vec<CellId>,
+        version: u64,
+    ) {
+        if let Some(app) = self.0.write().await.apps.get_mut(installed_app_id) {
+            if app.version < version {
+                app.cells = cells;
+                app.version = version;
+            }
+        }
+    }
+
+    /// Is the signal about a cell of a subscribed app and allowed by the
+    /// subscription's filters?
+    pub async fn allows(&self, signal: &Signal) -> bool {
+        self.0.read().await.apps.values().any(|app| {
+            app.cells
+                .iter()
+                .any(|cell_id| signal.is_about_cell(cell_id))
+                && app.subscription.filters().allows(signal)
+        })
+    }
+}
+
 pub use holochain_conductor_api::config::InterfaceDriver;
@@ -112,7 +112,8 @@ pub fn spawn_admin_interface_task<A: InterfaceApi>(
 }
 
 /// Create an App Interface, which includes the ability to receive signals
-/// from Cells via a broadcast channel
+/// from Cells via a broadcast channel.
+/// Each connection only receives the signals its subscriptions allow.
 pub async fn spawn_app_interface_task<A: InterfaceApi>(
     port: u16,
     api: A,
@@ -137,7 +138,7 @@ pub async fn spawn_app_interface_task<A: InterfaceApi>(
             |tx_to_iface: WebsocketSender, rx_from_iface: WebsocketReceiver| {
                 let rx_from_cell = signal_broadcaster.subscribe();
                 listener_handles.push(tokio::task::spawn(recv_incoming_msgs_and_outgoing_signals(
-                    api.clone(),
+                    api.for_connection(),
                     rx_from_iface,
                     rx_from_cell,
                     tx_to_iface,
@@ -222,7 +223,8 @@ async fn recv_incoming_admin_msgs<A: InterfaceApi>(
 
 /// Polls for messages coming in from the external client while simultaneously
 /// polling for signals being broadcast from the Cells associated with this
-/// App interface.
+/// App interface. Only the signals allowed by the subscriptions of the
+/// connection are sent to the client.
 async fn recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
     api: A,
     mut rx_from_iface: WebsocketReceiver,
@@ -239,11 +241,12 @@ async fn recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
             // tx and rx together in a new spawned task
             signal = rx_from_cell.next() => {
                 if let Some(signal) = signal {
-                    trace!(msg = "Sending signal!", ?signal);
-                    let bytes = SerializedBytes::try_from(
-                        signal.map_err(InterfaceError::SignalReceive)?,
-                    )?;
-                    tx_to_iface.signal(bytes).await?;
+                    let signal = signal.map_err(InterfaceError::SignalReceive)?;
+                    if api.allows_signal(&signal).await {
+                        trace!(msg = "Sending signal!", ?signal);
+                        let bytes = SerializedBytes::try_from(signal)?;
+                        tx_to_iface.signal(bytes).await?;
+                    }
                 } else {
                     debug!("Closing interface: signal stream empty");
                     break;
@@ -336,8 +339,15 @@ pub mod test {
     use super::test_utils::setup_app;
     use super::*;
     use crate::conductor::api::error::ExternalApiWireError;
+    use crate::conductor::api::AdminInterfaceApi;
     use crate::conductor::api::AdminRequest;
     use crate::conductor::api::AdminResponse;
+    use crate::conductor::api::AppCryptoPermissions;
+    use crate::conductor::api::AppInterfaceApi;
+    use crate::conductor::api::CryptoRequest;
+    use crate::conductor::api::CryptoResponse;
+    use crate::conductor::api::DeactivationReason;
+    use crate::conductor::api::InstalledAppStatus;
     use crate::conductor::api::RealAdminInterfaceApi;
     use crate::conductor::conductor::ConductorBuilder;
     use crate::conductor::p2p_store::AgentKv;
@@ -347,16 +357,21 @@ pub mod test {
     use crate::conductor::ConductorHandle;
     use crate::fixt::RealRibosomeFixturator;
     use crate::test_utils::conductor_setup::ConductorTestData;
+    use crate::test_utils::fake_valid_dna_file;
     use ::fixt::prelude::*;
     use fallible_iterator::FallibleIterator;
     use futures::future::FutureExt;
+    use holochain_conductor_api::signal_subscription::SignalFilter;
+    use holochain_conductor_api::signal_subscription::SignalFilterSet;
+    use holochain_conductor_api::signal_subscription::SignalSubscription;
+    use holochain_keystore::AgentPubKeyExt;
+    use holochain_keystore::KeystoreSenderExt;
     use holochain_lmdb::buffer::KvStoreT;
     use holochain_lmdb::fresh_reader_test;
     use holochain_lmdb::test_utils::test_environments;
     use holochain_serialized_bytes::prelude::*;
     use holochain_types::prelude::*;
     use holochain_types::test_utils::fake_agent_pubkey_1;
-    use holochain_types::test_utils::fake_dna_file;
     use holochain_types::test_utils::fake_dna_zomes;
     use holochain_types::{app::InstallAppDnaPayload, prelude::InstallAppPayload};
     use holochain_wasm_test_utils::TestWasm;
@@ -365,7 +380,8 @@ pub mod test {
     use holochain_zome_types::test_utils::fake_agent_pubkey_2;
     use holochain_zome_types::ExternIO;
     use kitsune_p2p::agent_store::AgentInfoSigned;
-    use kitsune_p2p::fixt::AgentInfoSignedFixturator;
+    use kitsune_p2p::fixt::signed_agent_info;
+    use kitsune_p2p::fixt::KitsuneSpaceFixturator;
     use matches::assert_matches;
     use mockall::predicate;
     use observability;
@@ -544,13 +560,246 @@ pub mod test {
         shutdown.await.unwrap();
     }
 
+    #[tokio::test(threaded_scheduler)]
+    async fn signal_subscription() {
+        observability::test_run().ok();
+        let dna = fake_dna_zomes(
+            &Uuid::new_v4().to_string(),
+            vec![(TestWasm::Foo.into(), TestWasm::Foo.into())],
+        );
+        let dna_hash = dna.dna_hash().clone();
+        let cell_id = CellId::from((dna_hash.clone(), fake_agent_pubkey_1()));
+        let installed_cell = InstalledCell::new(cell_id.clone(), "handle".into());
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store
+            .expect_get()
+            .with(predicate::eq(dna_hash))
+            .returning(move |_| Some(dna.clone()));
+        dna_store
+            .expect_add_dnas::<Vec<_>>()
+            .times(1)
+            .return_const(());
+        dna_store
+            .expect_add_entry_defs::<Vec<_>>()
+            .times(1)
+            .return_const(());
+
+        let (_tmpdir, app_api, handle) = setup_app(vec![(installed_cell, None)], dna_store).await;
+        let app_api = app_api.for_connection();
+        let app_signal = Signal::App(
+            cell_id.clone(),
+            AppSignal::new(ExternIO::encode(()).unwrap()),
+        );
+        let other_cell_signal =
+            Signal::App(fixt!(CellId), AppSignal::new(ExternIO::encode(()).unwrap()));
+        let init_signal: Signal = SystemSignal::CellInitCompleted {
+            cell_id: cell_id.clone(),
+        }
+        .into();
+
+        // Without a subscription every signal is received
+        assert!(app_api.allows_signal(&other_cell_signal).await);
+
+        // Only receive app signals from the cell of the app
+        let filters = SignalFilterSet::Include(
+            vec![(cell_id.clone(), SignalFilter::kinds(vec![SignalKind::App]))]
+                .into_iter()
+                .collect(),
+        );
+        let request =
+            AppRequest::SignalSubscription(SignalSubscription::new("test app".into(), filters));
+        assert_matches!(
+            app_api.handle_app_request(request).await,
+            AppResponse::SignalSubscriptionUpdated
+        );
+        assert!(app_api.allows_signal(&app_signal).await);
+        assert!(!app_api.allows_signal(&init_signal).await);
+        assert!(!app_api.allows_signal(&other_cell_signal).await);
+
+        // Other connections keep receiving every signal
+        let other_connection = app_api.for_connection();
+        assert!(other_connection.allows_signal(&init_signal).await);
+
+        // Apps which are not installed can't be subscribed to
+        let request = AppRequest::SignalSubscription(SignalSubscription::new(
+            "not installed".into(),
+            SignalFilterSet::allow_all(),
+        ));
+        assert_matches!(
+            app_api.handle_app_request(request).await,
+            AppResponse::Error(_)
+        );
+
+        // Integrated ops are only signalled at the bases subscribed to
+        // while the connection is open
+        let basis = AnyDhtHash::from(fixt!(EntryHash));
+        let filters = SignalFilterSet::Include(
+            vec![(
+                cell_id.clone(),
+                SignalFilter::kinds(vec![SignalKind::OpIntegrated]).with_bases(vec![basis.clone()]),
+            )]
+            .into_iter()
+            .collect(),
+        );
+        let request =
+            AppRequest::SignalSubscription(SignalSubscription::new("test app".into(), filters));
+        assert_matches!(
+            app_api.handle_app_request(request).await,
+            AppResponse::SignalSubscriptionUpdated
+        );
+        let subscribed_bases = handle.signal_broadcaster().await.subscribed_bases().clone();
+        assert!(subscribed_bases.contains(&basis));
+        drop(app_api);
+        assert!(!subscribed_bases.contains(&basis));
+
+        // The cells of a subscribed app are looked up again once it changes
+        let filters = SignalFilterSet::Include(
+            vec![(cell_id.clone(), SignalFilter::kinds(vec![SignalKind::App]))]
+                .into_iter()
+                .collect(),
+        );
+        let request =
+            AppRequest::SignalSubscription(SignalSubscription::new("test app".into(), filters));
+        assert_matches!(
+            other_connection.handle_app_request(request).await,
+            AppResponse::SignalSubscriptionUpdated
+        );
+        assert!(other_connection.allows_signal(&app_signal).await);
+        handle.uninstall_app(&"test app".into()).await.unwrap();
+        assert!(!other_connection.allows_signal(&app_signal).await);
+
+        let shutdown = handle.take_shutdown_handle().await.unwrap();
+        handle.shutdown().await;
+        shutdown.await.unwrap();
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn crypto_request() {
+        observability::test_run().ok();
+        let dna = fake_dna_zomes(
+            &Uuid::new_v4().to_string(),
+            vec![(TestWasm::Foo.into(), TestWasm::Foo.into())],
+        );
+        let dna_hash = dna.dna_hash().clone();
+        let cell_id = CellId::from((dna_hash.clone(), fake_agent_pubkey_1()));
+        let installed_cell = InstalledCell::new(cell_id.clone(), "handle".into());
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store
+            .expect_get()
+            .with(predicate::eq(dna_hash))
+            .returning(move |_| Some(dna.clone()));
+        dna_store
+            .expect_add_dnas::<Vec<_>>()
+            .times(1)
+            .return_const(());
+        dna_store
+            .expect_add_entry_defs::<Vec<_>>()
+            .times(1)
+            .return_const(());
+
+        let (_tmpdir, app_api, handle) = setup_app(vec![(installed_cell, None)], dna_store).await;
+        let admin_api = RealAdminInterfaceApi::new(handle.clone());
+        let data = Bytes::from(vec![1, 2, 3]);
+        let sign = || AppRequest::Crypto(Box::new(CryptoRequest::Sign { data: data.clone() }));
+
+        // Interfaces attached without an app can't make crypto requests
+        assert_matches!(
+            app_api.handle_app_request(sign()).await,
+            AppResponse::Error(ExternalApiWireError::CryptoUnauthorized(_))
+        );
+        let app_api = app_api.with_app("test app".into());
+
+        // Nothing is permitted until the admin allows it
+        assert_matches!(
+            app_api.handle_app_request(sign()).await,
+            AppResponse::Error(ExternalApiWireError::CryptoUnauthorized(_))
+        );
+
+        // Only the sender is registered to the app
+        let sender = handle.keystore().create_x25519_keypair().await.unwrap();
+        let recipient = handle.keystore().create_x25519_keypair().await.unwrap();
+        let request = AdminRequest::SetAppCryptoPermissions {
+            installed_app_id: "test app".into(),
+            permissions: AppCryptoPermissions {
+                sign: true,
+                encrypt: true,
+                decrypt: true,
+                x25519_keys: vec![sender.clone()],
+            },
+        };
+        assert_matches!(
+            admin_api.handle_admin_request(request).await,
+            AdminResponse::AppCryptoPermissionsSet
+        );
+
+        // Signed by the agent key of the app
+        let signature = match app_api.handle_app_request(sign()).await {
+            AppResponse::Crypto(response) => match *response {
+                CryptoResponse::Signed(signature) => signature,
+                other => panic!("unexpected crypto response {:?}", other),
+            },
+            other => panic!("unexpected response {:?}", other),
+        };
+        assert!(fake_agent_pubkey_1()
+            .verify_signature_raw(&signature, data.as_ref())
+            .await
+            .unwrap());
+
+        // Encrypted from the registered key, but not decrypted
+        // to the recipient which isn't registered
+        let request = AppRequest::Crypto(Box::new(CryptoRequest::Encrypt {
+            input: X25519XSalsa20Poly1305Encrypt::new(
+                sender.clone(),
+                recipient.clone(),
+                XSalsa20Poly1305Data::from(vec![4, 5, 6]),
+            ),
+        }));
+        let encrypted_data = match app_api.handle_app_request(request).await {
+            AppResponse::Crypto(response) => match *response {
+                CryptoResponse::Encrypted(encrypted_data) => encrypted_data,
+                other => panic!("unexpected crypto response {:?}", other),
+            },
+            other => panic!("unexpected response {:?}", other),
+        };
+        let request = AppRequest::Crypto(Box::new(CryptoRequest::Decrypt {
+            input: X25519XSalsa20Poly1305Decrypt::new(
+                sender.clone(),
+                recipient.clone(),
+                encrypted_data,
+            ),
+        }));
+        assert_matches!(
+            app_api.handle_app_request(request).await,
+            AppResponse::Error(ExternalApiWireError::CryptoUnauthorized(_))
+        );
+
+        // Keys which aren't registered to the app can't be encrypted from
+        let request = AppRequest::Crypto(Box::new(CryptoRequest::Encrypt {
+            input: X25519XSalsa20Poly1305Encrypt::new(
+                recipient,
+                sender,
+                XSalsa20Poly1305Data::from(vec![4, 5, 6]),
+            ),
+        }));
+        assert_matches!(
+            app_api.handle_app_request(request).await,
+            AppResponse::Error(ExternalApiWireError::CryptoUnauthorized(_))
+        );
+
+        let shutdown = handle.take_shutdown_handle().await.unwrap();
+        handle.shutdown().await;
+        shutdown.await.unwrap();
+    }
+
     #[tokio::test(threaded_scheduler)]
     async fn activate_app() {
         observability::test_run().ok();
         let agent_key = fake_agent_pubkey_1();
         let dnas = [Uuid::new_v4(); 2]
             .iter()
-            .map(|uuid| fake_dna_file(&uuid.to_string()))
+            .map(|uuid| fake_valid_dna_file(&uuid.to_string()))
             .collect::<Vec<_>>();
         let dna_map = dnas
             .iter()
@@ -624,7 +873,7 @@ pub mod test {
         let maybe_info = state.get_app_info(&"test app".to_string());
         if let Some(info) = maybe_info {
             assert_eq!(info.installed_app_id, "test app");
-            assert!(info.active);
+            assert_eq!(info.status, InstalledAppStatus::Active);
         } else {
             assert!(false);
         }
@@ -668,7 +917,12 @@ pub mod test {
         let maybe_info = state.get_app_info(&"test app".to_string());
         if let Some(info) = maybe_info {
             assert_eq!(info.installed_app_id, "test app");
-            assert!(!info.active);
+            assert_eq!(
+                info.status,
+                InstalledAppStatus::Inactive {
+                    reason: DeactivationReason::Normal
+                }
+            );
         } else {
             assert!(false);
         }
@@ -683,7 +937,10 @@ pub mod test {
         let (_tmpdir, conductor_handle) = setup_admin().await;
         let shutdown = conductor_handle.take_shutdown_handle().await.unwrap();
         let admin_api = RealAdminInterfaceApi::new(conductor_handle.clone());
-        let msg = AdminRequest::AttachAppInterface { port: None };
+        let msg = AdminRequest::AttachAppInterface {
+            port: None,
+            installed_app_id: None,
+        };
         let msg = msg.try_into().unwrap();
         let respond = |bytes: SerializedBytes| {
             let response: AdminResponse = bytes.try_into().unwrap();
@@ -796,9 +1053,10 @@ pub mod test {
         assert_eq!(count, 4);
 
         // - Get agents and space
-        let agent_infos = AgentInfoSignedFixturator::new(Unpredictable)
-            .take(5)
-            .collect::<Vec<_>>();
+        let mut agent_infos = Vec::new();
+        for _ in 0..5 {
+            agent_infos.push(signed_agent_info(fixt!(KitsuneSpace)).await);
+        }
 
         let mut expect = to_key(agent_infos.clone());
         let k00: AgentKvKey = (dnas[0].clone(), agents[0].clone()).into();
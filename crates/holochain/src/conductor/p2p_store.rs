@@ -259,10 +259,34 @@ pub fn query_agent_info_signed(
     environ: EnvironmentWrite,
     kitsune_space: Arc<kitsune_p2p::KitsuneSpace>,
 ) -> ConductorResult<Vec<AgentInfoSigned>> {
+    Ok(query_and_prune_agent_info_signed(environ, kitsune_space)?.0)
+}
+
+/// Get agent info for a single space.
+/// Expired agent info of every space is removed from the store while querying
+/// and is returned alongside the agent info of the space.
+pub fn query_and_prune_agent_info_signed(
+    environ: EnvironmentWrite,
+    kitsune_space: Arc<kitsune_p2p::KitsuneSpace>,
+) -> ConductorResult<(Vec<AgentInfoSigned>, Vec<AgentInfo>)> {
+    query_and_prune(environ, Some(kitsune_space.as_ref()))
+}
+
+/// Remove expired agent info of every space from the store
+/// and return what was removed.
+pub fn prune_agent_info_signed(environ: EnvironmentWrite) -> ConductorResult<Vec<AgentInfo>> {
+    Ok(query_and_prune(environ, None)?.1)
+}
+
+fn query_and_prune(
+    environ: EnvironmentWrite,
+    kitsune_space: Option<&kitsune_p2p::KitsuneSpace>,
+) -> ConductorResult<(Vec<AgentInfoSigned>, Vec<AgentInfo>)> {
     let p2p_kv = AgentKv::new(environ.clone().into())?;
     let env = environ.guard();
 
     let mut out = Vec::new();
+    let mut pruned = Vec::new();
     env.with_commit(|writer| {
         let mut expired = Vec::new();
 
@@ -281,11 +305,14 @@ pub fn query_agent_info_signed(
                         let expires = info.signed_at_ms().checked_add(info.expires_after_ms());
                         match expires {
                             Some(expires) if expires > now => {
-                                if info.as_space_ref() == kitsune_space.as_ref() {
+                                if Some(info.as_space_ref()) == kitsune_space {
                                     out.push(v);
                                 }
                             }
-                            _ => expired.push(AgentKvKey::from(k)),
+                            _ => {
+                                expired.push(AgentKvKey::from(k));
+                                pruned.push(info);
+                            }
                         }
                     }
                     Ok(None) => break,
@@ -303,7 +330,22 @@ pub fn query_agent_info_signed(
         ConductorResult::Ok(())
     })?;
 
-    Ok(out)
+    Ok((out, pruned))
+}
+
+/// Is there no unexpired agent info in the store for the agent of this
+/// agent info? If so the agent is new to the space.
+pub fn is_new_agent_info(
+    environ: EnvironmentWrite,
+    agent_info_signed: &AgentInfoSigned,
+) -> ConductorResult<bool> {
+    let info = AgentInfo::try_from(agent_info_signed)?;
+    let existing = get_agent_info_signed(
+        environ,
+        Arc::new(info.as_space_ref().clone()),
+        Arc::new(info.as_agent_ref().clone()),
+    )?;
+    Ok(existing.is_none())
 }
 
 /// Put single agent info into store
@@ -2,7 +2,10 @@
 //! startups and shutdowns
 
 use holochain_conductor_api::signal_subscription::SignalSubscription;
-use holochain_conductor_api::{config::InterfaceDriver, InstalledAppInfo};
+use holochain_conductor_api::{
+    config::InterfaceDriver, AppCryptoPermissions, AppStatusFilter, DeactivationReason,
+    InstalledAppInfo, InstalledAppStatus,
+};
 use holochain_types::prelude::*;
 use serde::Deserialize;
 use serde::Serialize;
@@ -22,6 +25,14 @@ pub struct ConductorState {
     /// Apps that are active and will be loaded
     #[serde(default)]
     pub active_apps: InstalledAppMap,
+    /// Why each inactive app was deactivated.
+    /// Apps that have never been activated have no entry.
+    #[serde(default)]
+    pub deactivation_reasons: HashMap<InstalledAppId, DeactivationReason>,
+    /// Which crypto requests each app may make over app interfaces.
+    /// Apps without an entry may make none.
+    #[serde(default)]
+    pub app_crypto_permissions: HashMap<InstalledAppId, AppCryptoPermissions>,
     /// List of interfaces any UI can use to access zome functions.
     #[serde(default)]
     pub app_interfaces: HashMap<AppInterfaceId, AppInterfaceConfig>,
@@ -54,14 +65,88 @@ impl ConductorState {
     pub fn get_app_info(&self, installed_app_id: &InstalledAppId) -> Option<InstalledAppInfo> {
         self.active_apps
             .get(installed_app_id)
-            .map(|app| InstalledAppInfo::from_installed_app(app, true))
+            .map(|app| InstalledAppInfo::from_installed_app(app, InstalledAppStatus::Active))
             .or_else(|| {
-                self.inactive_apps
-                    .get(installed_app_id)
-                    .map(|app| InstalledAppInfo::from_installed_app(app, false))
+                self.inactive_apps.get(installed_app_id).map(|app| {
+                    let reason = self
+                        .deactivation_reasons
+                        .get(installed_app_id)
+                        .cloned()
+                        .unwrap_or(DeactivationReason::NeverActivated);
+                    InstalledAppInfo::from_installed_app(
+                        app,
+                        InstalledAppStatus::Inactive { reason },
+                    )
+                })
             })
     }
 
+    /// Retrieve info about every installed App whose status passes the filter,
+    /// ordered by InstalledAppId
+    pub fn list_app_info(&self, status_filter: Option<AppStatusFilter>) -> Vec<InstalledAppInfo> {
+        let mut apps: Vec<_> = self
+            .active_apps
+            .keys()
+            .chain(self.inactive_apps.keys())
+            .filter_map(|installed_app_id| self.get_app_info(installed_app_id))
+            .filter(|info| {
+                status_filter
+                    .map(|filter| filter.matches(&info.status))
+                    .unwrap_or(true)
+            })
+            .collect();
+        apps.sort_by(|a, b| a.installed_app_id.cmp(&b.installed_app_id));
+        apps
+    }
+
+    /// Retrieve an installed App, active or inactive
+    #[allow(clippy::ptr_arg)]
+    pub fn get_app(&self, installed_app_id: &InstalledAppId) -> Option<&InstalledApp> {
+        self.active_apps
+            .get(installed_app_id)
+            .or_else(|| self.inactive_apps.get(installed_app_id))
+    }
+
+    /// Retrieve an installed App, active or inactive, for modification
+    #[allow(clippy::ptr_arg)]
+    pub fn get_app_mut(&mut self, installed_app_id: &InstalledAppId) -> Option<&mut InstalledApp> {
+        if self.active_apps.contains_key(installed_app_id) {
+            self.active_apps.get_mut(installed_app_id)
+        } else {
+            self.inactive_apps.get_mut(installed_app_id)
+        }
+    }
+
+    /// Find the app, active or inactive, which contains the given clone cell,
+    /// whether the clone is archived or not
+    pub fn find_app_containing_clone(&self, cell_id: &CellId) -> Option<&InstalledApp> {
+        self.active_apps
+            .values()
+            .chain(self.inactive_apps.values())
+            .find(|app| app.clone_slot(cell_id).is_some())
+    }
+
+    /// Find the app, active or inactive, which contains the given cell
+    pub fn find_app_containing_cell(&self, cell_id: &CellId) -> Option<&InstalledApp> {
+        self.active_apps
+            .values()
+            .chain(self.inactive_apps.values())
+            .find(|app| app.all_cells().any(|id| id == cell_id))
+    }
+
+    /// The crypto permissions of an app. Nothing is allowed for apps which
+    /// have none set.
+    #[allow(clippy::ptr_arg)]
+    pub fn app_crypto_permissions(
+        &self,
+        installed_app_id: &InstalledAppId,
+    ) -> AppCryptoPermissions {
+        self.app_crypto_permissions
+            .get(installed_app_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     /// Returns the interface configuration with the given ID if present
     pub fn interface_by_id(&self, id: &AppInterfaceId) -> Option<AppInterfaceConfig> {
         self.app_interfaces.get(id).cloned()
@@ -85,6 +170,10 @@ pub struct AppInterfaceConfig {
 
     /// The driver for the interface, e.g. Websocket
     pub driver: InterfaceDriver,
+
+    /// The app which the interface makes crypto requests for, if any
+    #[serde(default)]
+    pub installed_app_id: Option<InstalledAppId>,
 }
 
 impl AppInterfaceConfig {
@@ -93,6 +182,7 @@ impl AppInterfaceConfig {
         Self {
             signal_subscriptions: HashMap::new(),
             driver: InterfaceDriver::Websocket { port },
+            installed_app_id: None,
         }
     }
 }
@@ -19,6 +19,13 @@
 //! |                 **integration, common to both paths**                 |
 //! | DhtOpIntegr.   | IntegrationLimbo | IntegratedDhtOps | Publish        |
 //! | Publish        | AuthoredDhtOps   | *n/a*            | *n/a*          |
+//! |                      **scheduled functions**                          |
+//! | Schedule       | ScheduledFns     | ChainSequence    | ProduceDhtOps  |
+//! |                      **autonomic processes**                          |
+//! | HealthCheck    | AuthoredDhtOps   | AuthoredDhtOps   | Publish        |
+//! | SlowHeal       | ValidationQueue  | IntegrationLimbo | SysValidation, |
+//! |                |                  |                  | AppValidation, |
+//! |                |                  |                  | DhtOpIntegr.   |
 //!
 //! († Auth'd + IntQ is short for: AuthoredDhtOps + IntegrationLimbo)
 //!
@@ -44,12 +51,18 @@ mod app_validation_consumer;
 use app_validation_consumer::*;
 mod produce_dht_ops_consumer;
 use produce_dht_ops_consumer::*;
+mod health_check_consumer;
 mod publish_dht_ops_consumer;
+mod schedule_consumer;
+mod slow_heal_consumer;
 use crate::conductor::api::CellConductorApiT;
 use crate::conductor::manager::ManagedTaskAdd;
+use health_check_consumer::*;
 use holochain_p2p::HolochainP2pCell;
 use holochain_state::workspace::WorkspaceError;
 use publish_dht_ops_consumer::*;
+use schedule_consumer::*;
+use slow_heal_consumer::*;
 
 /// Spawns several long-running tasks which are responsible for processing work
 /// which shows up on various databases.
@@ -74,8 +87,12 @@ pub async fn spawn_queue_consumer_tasks(
     let (create_tx_sys, get_tx_sys) = tokio::sync::oneshot::channel();
 
     // Integration
-    let (tx_integration, handle) =
-        spawn_integrate_dht_ops_consumer(env.clone(), stop.subscribe(), get_tx_sys);
+    let (tx_integration, handle) = spawn_integrate_dht_ops_consumer(
+        env.clone(),
+        stop.subscribe(),
+        get_tx_sys,
+        conductor_api.clone(),
+    );
     task_sender
         .send(ManagedTaskAdd::dont_handle(handle))
         .await
@@ -99,8 +116,8 @@ pub async fn spawn_queue_consumer_tasks(
         env.clone(),
         stop.subscribe(),
         tx_app.clone(),
-        cell_network,
-        conductor_api,
+        cell_network.clone(),
+        conductor_api.clone(),
     );
     task_sender
         .send(ManagedTaskAdd::dont_handle(handle))
@@ -118,9 +135,60 @@ pub async fn spawn_queue_consumer_tasks(
         .await
         .expect("Failed to manage workflow handle");
 
+    // Schedule
+    let (tx_schedule, handle) = spawn_schedule_consumer(
+        env.clone(),
+        stop.subscribe(),
+        conductor_api.clone(),
+        cell_network,
+        tx_produce.clone(),
+    );
+    task_sender
+        .send(ManagedTaskAdd::dont_handle(handle))
+        .await
+        .expect("Failed to manage workflow handle");
+
+    // Health check
+    let (tx_health_check, handle) = spawn_health_check_consumer(
+        env.clone(),
+        stop.subscribe(),
+        conductor_api,
+        tx_publish.clone(),
+    );
+    task_sender
+        .send(ManagedTaskAdd::dont_handle(handle))
+        .await
+        .expect("Failed to manage workflow handle");
+
+    // Slow heal
+    let (tx_slow_heal, handle) = spawn_slow_heal_consumer(
+        env.clone(),
+        stop.subscribe(),
+        tx_sys.clone(),
+        tx_app.clone(),
+        tx_integration.clone(),
+    );
+    task_sender
+        .send(ManagedTaskAdd::dont_handle(handle))
+        .await
+        .expect("Failed to manage workflow handle");
+
     (
-        QueueTriggers::new(tx_sys.clone(), tx_produce.clone()),
-        InitialQueueTriggers::new(tx_sys, tx_produce, tx_publish, tx_app, tx_integration),
+        QueueTriggers::new(
+            tx_sys.clone(),
+            tx_produce.clone(),
+            tx_schedule.clone(),
+            tx_health_check,
+            tx_slow_heal,
+        ),
+        InitialQueueTriggers::new(
+            tx_sys,
+            tx_produce,
+            tx_publish,
+            tx_app,
+            tx_integration,
+            tx_schedule,
+        ),
     )
 }
 
@@ -131,6 +199,15 @@ pub struct QueueTriggers {
     pub sys_validation: TriggerSender,
     /// Notify the ProduceDhtOps workflow to run, i.e. after InvokeCallZome
     pub produce_dht_ops: TriggerSender,
+    /// Notify the Schedule workflow to run, i.e. after InvokeCallZome
+    /// may have scheduled a function
+    pub schedule: TriggerSender,
+    /// Notify the HealthCheck workflow to run now rather than when it is
+    /// next due, i.e. when the autonomic process is cued
+    pub health_check: TriggerSender,
+    /// Notify the SlowHeal workflow to run now rather than when it is
+    /// next due, i.e. when a peer joins the network
+    pub slow_heal: TriggerSender,
 }
 
 /// The triggers to run once at the start of a cell
@@ -142,14 +219,24 @@ pub struct InitialQueueTriggers {
     publish_dht_ops: TriggerSender,
     app_validation: TriggerSender,
     integrate_dht_ops: TriggerSender,
+    schedule: TriggerSender,
 }
 
 impl QueueTriggers {
     /// Create a new queue trigger
-    pub fn new(sys_validation: TriggerSender, produce_dht_ops: TriggerSender) -> Self {
+    pub fn new(
+        sys_validation: TriggerSender,
+        produce_dht_ops: TriggerSender,
+        schedule: TriggerSender,
+        health_check: TriggerSender,
+        slow_heal: TriggerSender,
+    ) -> Self {
         Self {
             sys_validation,
             produce_dht_ops,
+            schedule,
+            health_check,
+            slow_heal,
         }
     }
 }
@@ -161,6 +248,7 @@ impl InitialQueueTriggers {
         publish_dht_ops: TriggerSender,
         app_validation: TriggerSender,
         integrate_dht_ops: TriggerSender,
+        schedule: TriggerSender,
     ) -> Self {
         Self {
             sys_validation,
@@ -168,6 +256,7 @@ impl InitialQueueTriggers {
             publish_dht_ops,
             app_validation,
             integrate_dht_ops,
+            schedule,
         }
     }
 
@@ -178,6 +267,7 @@ impl InitialQueueTriggers {
         self.publish_dht_ops.trigger();
         self.integrate_dht_ops.trigger();
         self.produce_dht_ops.trigger();
+        self.schedule.trigger();
     }
 }
 /// The means of nudging a queue consumer to tell it to look for more work
@@ -0,0 +1,57 @@
+//! The workflow and queue consumer for the health check autonomic process
+
+use super::*;
+use crate::conductor::manager::ManagedTaskResult;
+use crate::core::workflow::health_check_workflow::health_check_workflow;
+use crate::core::workflow::health_check_workflow::HealthCheckWorkspace;
+use holochain_lmdb::env::EnvironmentWrite;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tracing::*;
+
+/// How long to wait between health checks when none are triggered
+pub const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Spawn the QueueConsumer for the HealthCheck workflow
+///
+/// Like the schedule consumer this one also wakes itself up, running a check
+/// every [HEALTH_CHECK_INTERVAL] unless it is triggered sooner.
+#[instrument(skip(env, stop, conductor_api, trigger_publish))]
+pub fn spawn_health_check_consumer(
+    env: EnvironmentWrite,
+    mut stop: sync::broadcast::Receiver<()>,
+    conductor_api: impl CellConductorApiT + 'static,
+    trigger_publish: TriggerSender,
+) -> (TriggerSender, JoinHandle<ManagedTaskResult>) {
+    let (tx, mut rx) = TriggerSender::new();
+    let handle = tokio::spawn(async move {
+        loop {
+            // Wait for next job or until the next check is due
+            let job =
+                tokio::time::timeout(HEALTH_CHECK_INTERVAL, next_job_or_exit(&mut rx, &mut stop))
+                    .await
+                    .unwrap_or(Job::Run);
+            if let Job::Shutdown = job {
+                tracing::warn!(
+                    "Cell is shutting down: stopping health_check_workflow queue consumer."
+                );
+                break;
+            }
+
+            // Run the workflow
+            let workspace =
+                HealthCheckWorkspace::new(env.clone().into()).expect("Could not create Workspace");
+            health_check_workflow(
+                workspace,
+                env.clone().into(),
+                &conductor_api,
+                trigger_publish.clone(),
+            )
+            .await
+            .expect("Error running Workflow");
+        }
+        Ok(())
+    });
+    (tx, handle)
+}
@@ -0,0 +1,73 @@
+//! The workflow and queue consumer for scheduled zome functions
+
+use super::*;
+use crate::conductor::manager::ManagedTaskResult;
+use crate::core::ribosome::real_ribosome::RealRibosome;
+use crate::core::workflow::schedule_workflow::schedule_workflow;
+use crate::core::workflow::schedule_workflow::ScheduleWorkflowArgs;
+use holochain_lmdb::env::EnvironmentWrite;
+use holochain_types::prelude::*;
+
+use tokio::task::JoinHandle;
+use tracing::*;
+
+/// Spawn the QueueConsumer for the Schedule workflow
+///
+/// Unlike the other consumers this one also wakes itself up when the next
+/// scheduled function is due, so it doesn't rely on being triggered.
+#[instrument(skip(env, stop, conductor_api, network, trigger_produce_dht_ops))]
+pub fn spawn_schedule_consumer(
+    env: EnvironmentWrite,
+    mut stop: sync::broadcast::Receiver<()>,
+    conductor_api: impl CellConductorApiT + 'static,
+    network: HolochainP2pCell,
+    trigger_produce_dht_ops: TriggerSender,
+) -> (TriggerSender, JoinHandle<ManagedTaskResult>) {
+    let (tx, mut rx) = TriggerSender::new();
+    let handle = tokio::spawn(async move {
+        let mut next_due: Option<Timestamp> = None;
+        loop {
+            // Wait for next job or until the next function is due
+            let job = match next_due {
+                Some(due) => {
+                    let wait = due
+                        .checked_difference_signed(&timestamp::now())
+                        .and_then(|wait| wait.to_std().ok())
+                        .unwrap_or_default();
+                    tokio::time::timeout(wait, next_job_or_exit(&mut rx, &mut stop))
+                        .await
+                        .unwrap_or(Job::Run)
+                }
+                None => next_job_or_exit(&mut rx, &mut stop).await,
+            };
+            if let Job::Shutdown = job {
+                tracing::warn!("Cell is shutting down: stopping schedule_workflow queue consumer.");
+                break;
+            }
+
+            // Run the workflow
+            let ribosome = RealRibosome::new(
+                conductor_api
+                    .get_this_dna()
+                    .await
+                    .expect("Could not get the Dna for the scheduled functions"),
+            );
+            let args = ScheduleWorkflowArgs {
+                ribosome,
+                conductor_api: conductor_api.clone(),
+                signal_tx: conductor_api.signal_broadcaster().await,
+            };
+            next_due = schedule_workflow(
+                env.clone(),
+                network.clone(),
+                env.keystore().clone(),
+                args,
+                trigger_produce_dht_ops.clone(),
+            )
+            .await
+            .expect("Error running Workflow");
+        }
+        Ok(())
+    });
+    (tx, handle)
+}
@@ -0,0 +1,65 @@
+//! The workflow and queue consumer for the slow heal autonomic process
+
+use super::*;
+use crate::conductor::manager::ManagedTaskResult;
+use crate::core::workflow::slow_heal_workflow::slow_heal_workflow;
+use crate::core::workflow::slow_heal_workflow::SlowHealWorkspace;
+use holochain_lmdb::env::EnvironmentWrite;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tracing::*;
+
+/// How long to wait between slow heal passes when none are triggered
+pub const SLOW_HEAL_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Spawn the QueueConsumer for the SlowHeal workflow
+///
+/// Runs a pass every [SLOW_HEAL_INTERVAL] unless it is triggered sooner,
+/// i.e. when a peer joins the network.
+#[instrument(skip(
+    env,
+    stop,
+    trigger_sys_validation,
+    trigger_app_validation,
+    trigger_integration
+))]
+pub fn spawn_slow_heal_consumer(
+    env: EnvironmentWrite,
+    mut stop: sync::broadcast::Receiver<()>,
+    trigger_sys_validation: TriggerSender,
+    trigger_app_validation: TriggerSender,
+    trigger_integration: TriggerSender,
+) -> (TriggerSender, JoinHandle<ManagedTaskResult>) {
+    let (tx, mut rx) = TriggerSender::new();
+    let handle = tokio::spawn(async move {
+        loop {
+            // Wait for next job or until the next pass is due
+            let job =
+                tokio::time::timeout(SLOW_HEAL_INTERVAL, next_job_or_exit(&mut rx, &mut stop))
+                    .await
+                    .unwrap_or(Job::Run);
+            if let Job::Shutdown = job {
+                tracing::warn!(
+                    "Cell is shutting down: stopping slow_heal_workflow queue consumer."
+                );
+                break;
+            }
+
+            // Run the workflow
+            let workspace =
+                SlowHealWorkspace::new(env.clone().into()).expect("Could not create Workspace");
+            slow_heal_workflow(
+                workspace,
+                env.clone().into(),
+                trigger_sys_validation.clone(),
+                trigger_app_validation.clone(),
+                trigger_integration.clone(),
+            )
+            .await
+            .expect("Error running Workflow");
+        }
+        Ok(())
+    });
+    (tx, handle)
+}
@@ -17,12 +17,15 @@ use crate::conductor::api::CellConductorReadHandle;
 use crate::conductor::api::ZomeCall;
 use crate::conductor::interface::SignalBroadcaster;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsResult;
+use crate::core::ribosome::guest_callback::genesis_self_check::GenesisSelfCheckInvocation;
+use crate::core::ribosome::guest_callback::genesis_self_check::GenesisSelfCheckResult;
 use crate::core::ribosome::guest_callback::init::InitInvocation;
 use crate::core::ribosome::guest_callback::init::InitResult;
 use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentInvocation;
 use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitInvocation;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitResult;
+use crate::core::ribosome::guest_callback::schedule::ScheduleInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateResult;
 use crate::core::ribosome::guest_callback::validate_link::ValidateLinkHostAccess;
@@ -32,15 +35,17 @@ use crate::core::ribosome::guest_callback::validation_package::ValidationPackage
 use crate::core::ribosome::guest_callback::validation_package::ValidationPackageResult;
 use crate::core::ribosome::guest_callback::CallIterator;
 use crate::core::workflow::CallZomeWorkspaceLock;
-use derive_more::Constructor;
 use error::RibosomeResult;
 use guest_callback::entry_defs::EntryDefsHostAccess;
+use guest_callback::genesis_self_check::GenesisSelfCheckHostAccess;
 use guest_callback::init::InitHostAccess;
 use guest_callback::migrate_agent::MigrateAgentHostAccess;
 use guest_callback::post_commit::PostCommitHostAccess;
+use guest_callback::schedule::ScheduleHostAccess;
 use guest_callback::validate::ValidateHostAccess;
 use guest_callback::validation_package::ValidationPackageHostAccess;
 use holo_hash::AgentPubKey;
+use holochain_conductor_api::config::conductor::ZomeCallConfig;
 use holochain_keystore::KeystoreSender;
 use holochain_p2p::HolochainP2pCell;
 use holochain_serialized_bytes::prelude::*;
@@ -54,18 +59,27 @@ use self::guest_callback::entry_defs::EntryDefsInvocation;
 #[derive(Clone)]
 pub struct CallContext {
     pub(crate) zome: Zome,
+    pub(crate) function_name: FunctionName,
     pub(crate) host_access: HostAccess,
 }
 
 impl CallContext {
-    pub fn new(zome: Zome, host_access: HostAccess) -> Self {
-        Self { zome, host_access }
+    pub fn new(zome: Zome, function_name: FunctionName, host_access: HostAccess) -> Self {
+        Self {
+            zome,
+            function_name,
+            host_access,
+        }
     }
 
     pub fn zome(&self) -> Zome {
         self.zome.clone()
     }
 
+    pub fn function_name(&self) -> &FunctionName {
+        &self.function_name
+    }
+
     pub fn host_access(&self) -> HostAccess {
         self.host_access.clone()
     }
@@ -81,6 +95,8 @@ pub enum HostAccess {
     MigrateAgent(MigrateAgentHostAccess),
     ValidationPackage(ValidationPackageHostAccess),
     PostCommit(PostCommitHostAccess), // TODO: add emit_signal access here?
+    Schedule(ScheduleHostAccess),
+    GenesisSelfCheck(GenesisSelfCheckHostAccess),
 }
 
 impl From<&HostAccess> for HostFnAccess {
@@ -98,6 +114,10 @@ impl From<&HostAccess> for HostFnAccess {
                 validation_package_host_access.into()
             }
             HostAccess::PostCommit(post_commit_host_access) => post_commit_host_access.into(),
+            HostAccess::Schedule(schedule_host_access) => schedule_host_access.into(),
+            HostAccess::GenesisSelfCheck(genesis_self_check_host_access) => {
+                genesis_self_check_host_access.into()
+            }
         }
     }
 }
@@ -111,6 +131,7 @@ impl HostAccess {
             | Self::MigrateAgent(MigrateAgentHostAccess { workspace, .. })
             | Self::ValidationPackage(ValidationPackageHostAccess { workspace, .. })
             | Self::PostCommit(PostCommitHostAccess { workspace, .. })
+            | Self::Schedule(ScheduleHostAccess { workspace, .. })
             | Self::Validate(ValidateHostAccess { workspace, .. })
             | Self::ValidateCreateLink(ValidateLinkHostAccess { workspace, .. }) => workspace,
             _ => panic!(
@@ -124,7 +145,8 @@ impl HostAccess {
         match self {
             Self::ZomeCall(ZomeCallHostAccess { keystore, .. })
             | Self::Init(InitHostAccess { keystore, .. })
-            | Self::PostCommit(PostCommitHostAccess { keystore, .. }) => keystore,
+            | Self::PostCommit(PostCommitHostAccess { keystore, .. })
+            | Self::Schedule(ScheduleHostAccess { keystore, .. }) => keystore,
             _ => panic!(
                 "Gave access to a host function that uses the keystore without providing a keystore"
             ),
@@ -137,6 +159,7 @@ impl HostAccess {
             Self::ZomeCall(ZomeCallHostAccess { network, .. })
             | Self::Init(InitHostAccess { network, .. })
             | Self::PostCommit(PostCommitHostAccess { network, .. })
+            | Self::Schedule(ScheduleHostAccess { network, .. })
             | Self::ValidationPackage(ValidationPackageHostAccess { network, .. })
             | Self::Validate(ValidateHostAccess { network, .. })
             | Self::ValidateCreateLink(ValidateLinkHostAccess { network, .. }) => network,
@@ -149,7 +172,8 @@ impl HostAccess {
     /// Get the signal broadcaster, panics if none was provided
     pub fn signal_tx(&mut self) -> &mut SignalBroadcaster {
         match self {
-            Self::ZomeCall(ZomeCallHostAccess { signal_tx, .. }) => signal_tx,
+            Self::ZomeCall(ZomeCallHostAccess { signal_tx, .. })
+            | Self::Schedule(ScheduleHostAccess { signal_tx, .. }) => signal_tx,
             _ => panic!(
                 "Gave access to a host function that uses the signal broadcaster without providing one"
             ),
@@ -159,16 +183,44 @@ impl HostAccess {
     /// Get the associated CellId, panics if not applicable
     pub fn cell_id(&self) -> &CellId {
         match self {
-            Self::ZomeCall(ZomeCallHostAccess { cell_id, .. }) => cell_id,
+            Self::ZomeCall(ZomeCallHostAccess { cell_id, .. })
+            | Self::Schedule(ScheduleHostAccess { cell_id, .. }) => cell_id,
             _ => panic!("Gave access to a host function that references a CellId"),
         }
     }
 
+    /// Get the time limits of the call, if it is a zome call or a scheduled
+    /// function. Callbacks run by the conductor have no time limits.
+    pub fn limits(&self) -> Option<&ZomeCallLimits> {
+        match self {
+            Self::ZomeCall(ZomeCallHostAccess { limits, .. })
+            | Self::Schedule(ScheduleHostAccess { limits, .. }) => Some(limits),
+            _ => None,
+        }
+    }
+
+    /// Get the capability that authorized the current call, if it is a zome
+    /// call or a scheduled function.
+    /// Scheduled functions are always called by the author of the cell.
+    pub fn capability(&self) -> Option<CapabilityInfo> {
+        match self {
+            Self::ZomeCall(ZomeCallHostAccess { capability, .. }) => capability.clone(),
+            Self::Schedule(ScheduleHostAccess { cell_id, .. }) => Some(CapabilityInfo {
+                provenance: cell_id.agent_pubkey().clone(),
+                grant: CapGrant::ChainAuthor(cell_id.agent_pubkey().clone()),
+            }),
+            _ => None,
+        }
+    }
+
     /// Get the call zome handle, panics if none was provided
     pub fn call_zome_handle(&self) -> &CellConductorReadHandle {
         match self {
             Self::ZomeCall(ZomeCallHostAccess {
                 call_zome_handle, ..
+            })
+            | Self::Schedule(ScheduleHostAccess {
+                call_zome_handle, ..
             }) => call_zome_handle,
             _ => panic!(
                 "Gave access to a host function that uses the call zome handle without providing a call zome handle"
@@ -256,19 +308,32 @@ impl ZomeCallInvocation {
     /// - the live cap grant needs to include the invocation's provenance AND zome/function name
     #[allow(clippy::extra_unused_lifetimes)]
     pub fn is_authorized<'a>(&self, host_access: &ZomeCallHostAccess) -> RibosomeResult<bool> {
+        Ok(self.authorization(host_access)?.is_some())
+    }
+
+    /// The capability that authorizes this zome call, if there is one.
+    pub fn authorization(
+        &self,
+        host_access: &ZomeCallHostAccess,
+    ) -> RibosomeResult<Option<CapabilityInfo>> {
         let check_function = (self.zome.zome_name().clone(), self.fn_name.clone());
         let check_agent = self.provenance.clone();
         let check_secret = self.cap;
 
         tokio_safe_block_on::tokio_safe_block_forever_on(async move {
-            let maybe_grant: Option<CapGrant> = host_access
-                .workspace
-                .read()
-                .await
-                .source_chain
-                .valid_cap_grant(&check_function, &check_agent, check_secret.as_ref())?;
-
-            Ok(maybe_grant.is_some())
+            let workspace = host_access.workspace.read().await;
+            let maybe_grant: Option<CapGrant> = workspace.source_chain.valid_cap_grant(
+                &check_function,
+                &check_agent,
+                check_secret.as_ref(),
+                timestamp::now(),
+                &workspace.cap_grant_uses,
+            )?;
+
+            Ok(maybe_grant.map(|grant| CapabilityInfo {
+                provenance: check_agent,
+                grant,
+            }))
         })
     }
 }
@@ -366,7 +431,7 @@ impl From<ZomeCallInvocation> for ZomeCall {
     }
 }
 
-#[derive(Clone, Constructor)]
+#[derive(Clone)]
 pub struct ZomeCallHostAccess {
     pub workspace: CallZomeWorkspaceLock,
     pub keystore: KeystoreSender,
@@ -377,6 +442,52 @@ pub struct ZomeCallHostAccess {
     // "resource" to give access to, but rather it's a bit of data that makes sense in
     // the context of zome calls, but not every CallContext
     pub cell_id: CellId,
+    pub limits: ZomeCallLimits,
+    /// The capability that authorized the call.
+    /// This is set by the ribosome once the call has been authorized.
+    pub capability: Option<CapabilityInfo>,
+}
+
+impl ZomeCallHostAccess {
+    pub fn new(
+        workspace: CallZomeWorkspaceLock,
+        keystore: KeystoreSender,
+        network: HolochainP2pCell,
+        signal_tx: SignalBroadcaster,
+        call_zome_handle: CellConductorReadHandle,
+        cell_id: CellId,
+        limits: ZomeCallLimits,
+    ) -> Self {
+        Self {
+            workspace,
+            keystore,
+            network,
+            signal_tx,
+            call_zome_handle,
+            cell_id,
+            limits,
+            capability: None,
+        }
+    }
+}
+
+/// The time limits that apply to a single zome call
+#[derive(Clone, Copy, Debug)]
+pub struct ZomeCallLimits {
+    /// The conductor abandons the call if it is still running at this instant
+    pub deadline: std::time::Instant,
+    /// The longest a single call to `sleep` may wait for
+    pub max_sleep: std::time::Duration,
+}
+
+impl ZomeCallLimits {
+    /// Limits for a zome call starting now
+    pub fn new(config: &ZomeCallConfig) -> Self {
+        Self {
+            deadline: std::time::Instant::now() + config.timeout(),
+            max_sleep: config.max_sleep(),
+        }
+    }
 }
 
 impl From<ZomeCallHostAccess> for HostAccess {
@@ -463,6 +574,14 @@ pub trait RibosomeT: Sized + std::fmt::Debug {
         invocation: MigrateAgentInvocation,
     ) -> RibosomeResult<MigrateAgentResult>;
 
+    /// Runs the genesis self check callbacks before an agent joins.
+    /// An invalid result means genesis must not go ahead.
+    fn run_genesis_self_check(
+        &self,
+        access: GenesisSelfCheckHostAccess,
+        invocation: GenesisSelfCheckInvocation,
+    ) -> RibosomeResult<GenesisSelfCheckResult>;
+
     fn run_entry_defs(
         &self,
         access: EntryDefsHostAccess,
@@ -481,6 +600,14 @@ pub trait RibosomeT: Sized + std::fmt::Debug {
         invocation: PostCommitInvocation,
     ) -> RibosomeResult<PostCommitResult>;
 
+    /// Runs a function that a zome scheduled for itself.
+    /// Errors if the function does not exist or the guest returns an error.
+    fn run_schedule(
+        &self,
+        access: ScheduleHostAccess,
+        invocation: ScheduleInvocation,
+    ) -> RibosomeResult<()>;
+
     /// Helper function for running a validation callback. Just calls
     /// [`run_callback`][] under the hood.
     /// [`run_callback`]: #method.run_callback
@@ -41,6 +41,10 @@ pub enum RibosomeError {
     #[error("An error with entry defs: {0}")]
     EntryDefs(ZomeName, String),
 
+    /// A grant curries a payload into a call whose payload isn't a map
+    #[error("The payload of a call to {0}::{1} must be a map to have the grant's payload curried into it")]
+    CurryPayloadNotMap(ZomeName, FunctionName),
+
     /// a mandatory dependency for an element doesn't exist
     /// for example a remove link ribosome call needs to find the add link in order to infer the
     /// correct base and this dependent relationship exists before even subconscious validation
@@ -1,7 +1,9 @@
 pub mod entry_defs;
+pub mod genesis_self_check;
 pub mod init;
 pub mod migrate_agent;
 pub mod post_commit;
+pub mod schedule;
 pub mod validate;
 pub mod validate_link;
 pub mod validation_package;
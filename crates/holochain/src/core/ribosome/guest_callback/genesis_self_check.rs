@@ -0,0 +1,146 @@
+use crate::core::ribosome::FnComponents;
+use crate::core::ribosome::HostAccess;
+use crate::core::ribosome::Invocation;
+use crate::core::ribosome::ZomesToInvoke;
+use derive_more::Constructor;
+use holochain_serialized_bytes::prelude::*;
+use holochain_types::dna::zome::HostFnAccess;
+use holochain_types::prelude::*;
+
+#[derive(Clone, Debug)]
+pub struct GenesisSelfCheckInvocation {
+    pub payload: GenesisSelfCheckData,
+}
+
+impl GenesisSelfCheckInvocation {
+    pub fn new(membrane_proof: Option<MembraneProof>, agent_key: AgentPubKey) -> Self {
+        Self {
+            payload: GenesisSelfCheckData {
+                membrane_proof,
+                agent_key,
+            },
+        }
+    }
+}
+
+/// The self check runs before there is a source chain
+/// or a network so the guest gets no host access.
+#[derive(Clone, Constructor)]
+pub struct GenesisSelfCheckHostAccess;
+
+impl From<GenesisSelfCheckHostAccess> for HostAccess {
+    fn from(genesis_self_check_host_access: GenesisSelfCheckHostAccess) -> Self {
+        Self::GenesisSelfCheck(genesis_self_check_host_access)
+    }
+}
+
+impl From<&GenesisSelfCheckHostAccess> for HostFnAccess {
+    fn from(_: &GenesisSelfCheckHostAccess) -> Self {
+        Self::none()
+    }
+}
+
+impl Invocation for GenesisSelfCheckInvocation {
+    fn zomes(&self) -> ZomesToInvoke {
+        ZomesToInvoke::All
+    }
+    fn fn_components(&self) -> FnComponents {
+        vec!["genesis_self_check".into()].into()
+    }
+    fn host_input(self) -> Result<ExternIO, SerializedBytesError> {
+        ExternIO::encode(self.payload)
+    }
+}
+
+/// the aggregate result of all zome callbacks for checking
+/// an agent can join before genesis
+#[derive(PartialEq, Debug)]
+pub enum GenesisSelfCheckResult {
+    /// all implemented genesis self check callbacks in all zomes passed
+    Valid,
+    /// some genesis self check callback failed
+    /// ZomeName is the first zome that failed
+    /// String is some human readable string explaining the failure
+    Invalid(ZomeName, String),
+}
+
+impl From<Vec<(ZomeName, ValidateCallbackResult)>> for GenesisSelfCheckResult {
+    fn from(callback_results: Vec<(ZomeName, ValidateCallbackResult)>) -> Self {
+        callback_results
+            .into_iter()
+            .fold(Self::Valid, |acc, (zome_name, x)| match x {
+                // invalid always overrides the acc
+                ValidateCallbackResult::Invalid(reason) => Self::Invalid(zome_name, reason),
+                // the self check can't fetch anything so missing
+                // dependencies mean the agent can't be checked
+                ValidateCallbackResult::UnresolvedDependencies(deps) => match acc {
+                    Self::Invalid(_, _) => acc,
+                    Self::Valid => Self::Invalid(
+                        zome_name,
+                        format!("genesis self check has unresolved dependencies {:?}", deps),
+                    ),
+                },
+                // valid allows the acc to continue
+                ValidateCallbackResult::Valid => acc,
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixt::ZomeNameFixturator;
+    use holochain_types::test_utils::fake_agent_pubkey_1;
+
+    #[test]
+    fn genesis_self_check_callback_result_fold() {
+        let zome_name = || ZomeNameFixturator::new(::fixt::Empty).next().unwrap();
+        let invalid = || (zome_name(), ValidateCallbackResult::Invalid("".into()));
+        let valid = || (zome_name(), ValidateCallbackResult::Valid);
+        let unresolved = || {
+            (
+                zome_name(),
+                ValidateCallbackResult::UnresolvedDependencies(vec![]),
+            )
+        };
+
+        assert_eq!(
+            GenesisSelfCheckResult::from(vec![]),
+            GenesisSelfCheckResult::Valid
+        );
+        assert_eq!(
+            GenesisSelfCheckResult::from(vec![valid(), valid()]),
+            GenesisSelfCheckResult::Valid
+        );
+        assert_eq!(
+            GenesisSelfCheckResult::from(vec![valid(), invalid(), valid()]),
+            GenesisSelfCheckResult::Invalid(zome_name(), "".into())
+        );
+        assert!(matches!(
+            GenesisSelfCheckResult::from(vec![unresolved(), valid()]),
+            GenesisSelfCheckResult::Invalid(_, _)
+        ));
+    }
+
+    #[test]
+    fn genesis_self_check_invocation() {
+        let invocation = GenesisSelfCheckInvocation::new(None, fake_agent_pubkey_1());
+        assert_eq!(ZomesToInvoke::All, invocation.zomes());
+        let mut expected = vec!["genesis_self_check"];
+        for fn_component in invocation.fn_components() {
+            assert_eq!(fn_component, expected.pop().unwrap());
+        }
+        assert_eq!(
+            invocation.clone().host_input().unwrap(),
+            ExternIO::encode(GenesisSelfCheckData {
+                membrane_proof: None,
+                agent_key: fake_agent_pubkey_1(),
+            })
+            .unwrap()
+        );
+        assert_eq!(
+            HostFnAccess::from(&GenesisSelfCheckHostAccess),
+            HostFnAccess::none()
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use crate::conductor::api::CellConductorReadHandle;
+use crate::conductor::interface::SignalBroadcaster;
+use crate::core::ribosome::FnComponents;
+use crate::core::ribosome::HostAccess;
+use crate::core::ribosome::Invocation;
+use crate::core::ribosome::ZomeCallLimits;
+use crate::core::ribosome::ZomesToInvoke;
+use crate::core::workflow::CallZomeWorkspaceLock;
+use derive_more::Constructor;
+use holochain_keystore::KeystoreSender;
+use holochain_p2p::HolochainP2pCell;
+use holochain_serialized_bytes::prelude::*;
+use holochain_types::prelude::*;
+
+/// A call to a function that a zome scheduled for itself with the
+/// `schedule` host fn.
+#[derive(Clone)]
+pub struct ScheduleInvocation {
+    zome: Zome,
+    fn_name: FunctionName,
+    payload: ExternIO,
+}
+
+impl ScheduleInvocation {
+    pub fn new(zome: Zome, fn_name: FunctionName, payload: ExternIO) -> Self {
+        Self {
+            zome,
+            fn_name,
+            payload,
+        }
+    }
+
+    pub fn zome(&self) -> &Zome {
+        &self.zome
+    }
+
+    pub fn fn_name(&self) -> &FunctionName {
+        &self.fn_name
+    }
+}
+
+/// Scheduled functions run as the author of the cell so they get the same
+/// access as a zome call.
+#[derive(Clone, Constructor)]
+pub struct ScheduleHostAccess {
+    pub workspace: CallZomeWorkspaceLock,
+    pub keystore: KeystoreSender,
+    pub network: HolochainP2pCell,
+    pub signal_tx: SignalBroadcaster,
+    pub call_zome_handle: CellConductorReadHandle,
+    pub cell_id: CellId,
+    pub limits: ZomeCallLimits,
+}
+
+impl From<ScheduleHostAccess> for HostAccess {
+    fn from(schedule_host_access: ScheduleHostAccess) -> Self {
+        Self::Schedule(schedule_host_access)
+    }
+}
+
+impl From<&ScheduleHostAccess> for HostFnAccess {
+    fn from(_: &ScheduleHostAccess) -> Self {
+        Self::all()
+    }
+}
+
+impl Invocation for ScheduleInvocation {
+    fn zomes(&self) -> ZomesToInvoke {
+        ZomesToInvoke::One(self.zome.to_owned())
+    }
+    fn fn_components(&self) -> FnComponents {
+        vec![self.fn_name.to_owned().into()].into()
+    }
+    fn host_input(self) -> Result<ExternIO, SerializedBytesError> {
+        Ok(self.payload)
+    }
+}
+
+impl TryFrom<ScheduleInvocation> for ExternIO {
+    type Error = SerializedBytesError;
+    fn try_from(schedule_invocation: ScheduleInvocation) -> Result<Self, Self::Error> {
+        Ok(schedule_invocation.payload)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::ribosome::Invocation;
+    use crate::core::ribosome::ZomesToInvoke;
+    use crate::fixt::ScheduleHostAccessFixturator;
+    use crate::fixt::ScheduleInvocationFixturator;
+    use ::fixt::prelude::*;
+    use holochain_types::dna::zome::HostFnAccess;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn schedule_host_access() {
+        let schedule_host_access = ScheduleHostAccessFixturator::new(::fixt::Unpredictable)
+            .next()
+            .unwrap();
+        assert_eq!(
+            HostFnAccess::from(&schedule_host_access),
+            HostFnAccess::all(),
+        );
+    }
+
+    #[test]
+    fn schedule_invocation_zomes() {
+        let schedule_invocation = ScheduleInvocationFixturator::new(::fixt::Unpredictable)
+            .next()
+            .unwrap();
+        let zome = schedule_invocation.zome.clone();
+        assert_eq!(ZomesToInvoke::One(zome), schedule_invocation.zomes(),);
+    }
+
+    #[test]
+    fn schedule_invocation_fn_components() {
+        let schedule_invocation = ScheduleInvocationFixturator::new(::fixt::Unpredictable)
+            .next()
+            .unwrap();
+        let fn_name = schedule_invocation.fn_name.0.clone();
+        let mut fn_components = schedule_invocation.fn_components();
+        assert_eq!(fn_components.next(), Some(fn_name));
+        assert_eq!(fn_components.next(), None);
+    }
+
+    #[test]
+    fn schedule_invocation_host_input() {
+        let schedule_invocation = ScheduleInvocationFixturator::new(::fixt::Unpredictable)
+            .next()
+            .unwrap();
+        let payload = schedule_invocation.payload.clone();
+        assert_eq!(schedule_invocation.host_input().unwrap(), payload);
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+mod slow_tests {
+    use crate::core::ribosome::guest_callback::schedule::ScheduleHostAccess;
+    use crate::core::ribosome::RibosomeT;
+    use crate::fixt::curve::Zomes;
+    use crate::fixt::RealRibosomeFixturator;
+    use crate::fixt::ScheduleHostAccessFixturator;
+    use crate::fixt::ScheduleInvocationFixturator;
+    use ::fixt::prelude::*;
+    use holochain_types::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_schedule_missing_fn() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace =
+            crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+
+        let ribosome = RealRibosomeFixturator::new(Zomes(vec![TestWasm::Schedule]))
+            .next()
+            .unwrap();
+        let mut schedule_invocation = ScheduleInvocationFixturator::new(::fixt::Empty)
+            .next()
+            .unwrap();
+        schedule_invocation.zome = TestWasm::Schedule.into();
+        schedule_invocation.fn_name = "not_a_fn".into();
+
+        let mut host_access: ScheduleHostAccess = fixt!(ScheduleHostAccess);
+        host_access.workspace = workspace_lock;
+
+        assert!(ribosome
+            .run_schedule(host_access, schedule_invocation)
+            .is_err());
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_schedule_ping() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace =
+            crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+
+        let ribosome = RealRibosomeFixturator::new(Zomes(vec![TestWasm::Schedule]))
+            .next()
+            .unwrap();
+        let mut schedule_invocation = ScheduleInvocationFixturator::new(::fixt::Empty)
+            .next()
+            .unwrap();
+        schedule_invocation.zome = TestWasm::Schedule.into();
+        schedule_invocation.fn_name = "ping".into();
+        schedule_invocation.payload = ExternIO::encode(2_u32).unwrap();
+
+        let mut host_access: ScheduleHostAccess = fixt!(ScheduleHostAccess);
+        host_access.workspace = workspace_lock;
+
+        ribosome
+            .run_schedule(host_access, schedule_invocation)
+            .unwrap();
+    }
+}
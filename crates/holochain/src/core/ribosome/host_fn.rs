@@ -50,13 +50,13 @@ host_fn_api_impls! {
     // Info about the calling agent.
     fn agent_info (()) -> zt::info::AgentInfo;
 
-    // @todo
+    // Info about the app the current cell is installed in.
     fn app_info (()) -> zt::info::AppInfo;
 
-    // @todo
+    // Info about the current dna.
     fn dna_info (()) -> zt::info::DnaInfo;
 
-    // @todo
+    // Info about the current zome call.
     fn call_info (()) -> zt::info::CallInfo;
 
     fn call (zt::call::Call) -> zt::ZomeCallResponse;
@@ -64,14 +64,14 @@ host_fn_api_impls! {
     // Header hash of the DeleteLink element.
     fn call_remote (zt::call_remote::CallRemote) -> zt::ZomeCallResponse;
 
-    // @todo List all the local capability claims.
-    fn capability_claims (()) -> ();
+    // List all the live local capability claims, optionally filtered by tag.
+    fn capability_claims (zt::capability::CapQuery) -> Vec<zt::capability::CapClaimInfo>;
 
-    // @todo List all the local capability grants.
-    fn capability_grants (()) -> ();
+    // List all the live local capability grants, optionally filtered by tag.
+    fn capability_grants (zt::capability::CapQuery) -> Vec<zt::capability::CapGrantInfo>;
 
-    // @todo Get the capability for the current zome call.
-    fn capability_info (()) -> ();
+    // Get the capability that authorized the current zome call.
+    fn capability_info (()) -> zt::capability::CapabilityInfo;
 
     // The EntryDefId determines how a create is handled on the host side.
     // CapGrant and CapClaim are handled natively.
@@ -139,8 +139,8 @@ host_fn_api_impls! {
     // // @todo
     // fn send (()) -> ();
 
-    // @todo
-    fn schedule (core::time::Duration) -> ();
+    // Schedule a function in the current zome to be called after a duration.
+    fn schedule (zt::schedule::Schedule) -> ();
 
     // @todo
     fn sleep (core::time::Duration) -> ();
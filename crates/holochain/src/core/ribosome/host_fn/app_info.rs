@@ -1,14 +1,83 @@
+use crate::core::ribosome::guest_callback::schedule::ScheduleHostAccess;
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostAccess;
 use crate::core::ribosome::RibosomeT;
+use crate::core::ribosome::ZomeCallHostAccess;
+use holochain_types::prelude::*;
 use std::sync::Arc;
 use holochain_wasmer_host::prelude::WasmError;
-use holochain_zome_types::info::AppInfo;
 
+/// The app that the current cell is installed in, with all its cells.
 pub fn app_info(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
+    call_context: Arc<CallContext>,
     _input: (),
 ) -> Result<AppInfo, WasmError> {
-    unimplemented!()
+    let call_zome_handle = match &call_context.host_access {
+        HostAccess::ZomeCall(ZomeCallHostAccess {
+            call_zome_handle, ..
+        })
+        | HostAccess::Schedule(ScheduleHostAccess {
+            call_zome_handle, ..
+        }) => call_zome_handle.clone(),
+        _ => {
+            return Err(WasmError::Host(
+                "app_info is only available to zome calls and scheduled functions".into(),
+            ))
+        }
+    };
+    let app = tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        call_zome_handle.get_this_app().await
+    })
+    .map_err(|conductor_api_error| WasmError::Host(conductor_api_error.to_string()))?
+    .ok_or_else(|| WasmError::Host("The cell is not installed in any app".into()))?;
+
+    let cells = app
+        .slots()
+        .iter()
+        .flat_map(|(nick, slot)| {
+            slot.provisioned_cell()
+                .into_iter()
+                .chain(slot.clones())
+                .map(move |cell_id| (nick.clone(), cell_id.clone()))
+        })
+        .collect();
+    Ok(AppInfo {
+        installed_app_id: app.installed_app_id().clone(),
+        cells,
+    })
 }
 
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::test_utils::sweetest::SweetConductor;
+    use crate::test_utils::sweetest::SweetDnaFile;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn app_info_lists_sibling_cells() {
+        let (dna_a, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::ZomeInfo])
+            .await
+            .unwrap();
+        let (dna_b, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::ZomeInfo])
+            .await
+            .unwrap();
+
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", &[dna_a, dna_b]).await;
+        let cell_ids: Vec<CellId> = app.cells().iter().map(|c| c.cell_id().clone()).collect();
+
+        let app_info: AppInfo = conductor
+            .call(&app.cells()[0].zome(TestWasm::ZomeInfo), "app_info", ())
+            .await;
+        assert_eq!(app_info.installed_app_id, "app");
+        assert_eq!(app_info.cells.len(), 2);
+        for cell_id in cell_ids {
+            assert!(app_info.cells.iter().any(|(_, id)| *id == cell_id));
+        }
+
+        conductor.shutdown().await;
+    }
+}
@@ -1,14 +1,73 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
 use std::sync::Arc;
 use holochain_wasmer_host::prelude::WasmError;
-use holochain_zome_types::info::CallInfo;
 
 pub fn call_info(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
+    call_context: Arc<CallContext>,
     _input: (),
 ) -> Result<CallInfo, WasmError> {
-    unimplemented!()
+    let CapabilityInfo { provenance, grant } =
+        call_context.host_access.capability().ok_or_else(|| {
+            WasmError::Host("call_info is only available to zome calls and scheduled functions".into())
+        })?;
+    let as_at = tokio_safe_block_on::tokio_safe_block_forever_on(async {
+        call_context
+            .host_access
+            .workspace()
+            .read()
+            .await
+            .source_chain
+            .persisted_head()
+            .map(Clone::clone)
+            .map_err(|source_chain_error| WasmError::Host(source_chain_error.to_string()))
+    })?;
+    Ok(CallInfo {
+        provenance,
+        cap_grant: grant,
+        function_name: call_context.function_name().clone(),
+        as_at,
+    })
 }
 
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod test {
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use holochain_lmdb::prelude::WriteManager;
+    use holochain_state::workspace::Workspace;
+    use holochain_wasm_test_utils::TestWasm;
+    use holochain_zome_types::prelude::*;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn invoke_import_call_info_test() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace =
+            crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+        let chain_head = workspace.source_chain.chain_head().unwrap().clone();
+        // as_at is the persisted chain head so genesis needs to be written
+        env.with_commit(|writer| workspace.flush_to_txn_ref(writer))
+            .unwrap();
+        let workspace = crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock;
+        let call_info: CallInfo =
+            crate::call_test_ribosome!(host_access, TestWasm::ZomeInfo, "call_info", ());
+        assert_eq!(call_info.function_name, "call_info".into());
+        assert_eq!(
+            call_info.cap_grant,
+            CapGrant::ChainAuthor(call_info.provenance.clone())
+        );
+        assert_eq!(call_info.as_at, chain_head);
+    }
+}
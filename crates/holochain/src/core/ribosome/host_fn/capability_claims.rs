@@ -1,13 +1,24 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
 use std::sync::Arc;
 use holochain_wasmer_host::prelude::WasmError;
 
 /// lists all the local claims filtered by tag
+/// this is only the current claims as per local CRUD
 pub fn capability_claims(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: (),
-) -> Result<(), WasmError> {
-    unimplemented!();
+    call_context: Arc<CallContext>,
+    input: CapQuery,
+) -> Result<Vec<CapClaimInfo>, WasmError> {
+    tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        call_context
+            .host_access
+            .workspace()
+            .read()
+            .await
+            .source_chain
+            .cap_claims(&input)
+            .map_err(|source_chain_error| WasmError::Host(source_chain_error.to_string()))
+    })
 }
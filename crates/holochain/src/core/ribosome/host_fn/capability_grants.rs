@@ -1,5 +1,6 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::WasmError;
 use std::sync::Arc;
 
@@ -7,10 +8,19 @@ use std::sync::Arc;
 /// this is only the current grants as per local CRUD
 pub fn capability_grants(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: (),
-) -> Result<(), WasmError> {
-    unimplemented!();
+    call_context: Arc<CallContext>,
+    input: CapQuery,
+) -> Result<Vec<CapGrantInfo>, WasmError> {
+    tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        call_context
+            .host_access
+            .workspace()
+            .read()
+            .await
+            .source_chain
+            .cap_grants(&input)
+            .map_err(|source_chain_error| WasmError::Host(source_chain_error.to_string()))
+    })
 }
 
 #[cfg(test)]
@@ -124,6 +134,12 @@ pub mod wasm_test {
         let alice = alice.zome(TestWasm::Capability);
         let bobbo = bobbo.zome(TestWasm::Capability);
 
+        // ALICE CALLING HERSELF IS AUTHORIZED AS THE CHAIN AUTHOR
+
+        let info: CapabilityInfo = conductor.call(&alice, "cap_info", ()).await;
+        assert_eq!(info.provenance, alice_agent_id);
+        assert_eq!(info.grant, CapGrant::ChainAuthor(alice_agent_id.clone()));
+
         // ALICE FAILING AN UNAUTHED CALL
 
         #[derive(serde::Serialize, serde::Deserialize, SerializedBytes, Debug)]
@@ -147,6 +163,18 @@ pub mod wasm_test {
             .call(&bobbo, "transferable_cap_grant", original_secret)
             .await;
 
+        // BOB CAN LIST THE GRANT BY ITS TAG
+
+        let grants: Vec<CapGrantInfo> = conductor
+            .call(
+                &bobbo,
+                "list_cap_grants",
+                CapQuery::tag("needs_cap_claim".into()),
+            )
+            .await;
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].header_hash, original_grant_hash);
+
         // ALICE CAN NOW CALL THE AUTHED REMOTE FN
 
         let response: ZomeCallResponse = conductor
@@ -172,6 +200,18 @@ pub mod wasm_test {
             .call(&bobbo, "get_entry", new_grant_header_hash.clone())
             .await;
 
+        // ONLY THE ROLLED GRANT IS LIVE
+
+        let grants: Vec<CapGrantInfo> = conductor
+            .call(
+                &bobbo,
+                "list_cap_grants",
+                CapQuery::tag("needs_cap_claim".into()),
+            )
+            .await;
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].header_hash, new_grant_header_hash);
+
         let new_secret: CapSecret = match output {
             Some(element) => match element.entry().to_grant_option() {
                 Some(zome_call_cap_grant) => match zome_call_cap_grant.access {
@@ -208,6 +248,13 @@ pub mod wasm_test {
             .call(&bobbo, "delete_cap_grant", new_grant_header_hash)
             .await;
 
+        // only the unrestricted grant from init is still live
+        let grants: Vec<CapGrantInfo> = conductor
+            .call(&bobbo, "list_cap_grants", CapQuery::all())
+            .await;
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].grant.access, CapAccess::Unrestricted);
+
         let output: ZomeCallResponse = conductor
             .call(
                 &alice,
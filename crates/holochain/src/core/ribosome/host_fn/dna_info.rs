@@ -1,14 +1,49 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
+use holo_hash::HasHash;
+use holochain_types::prelude::*;
 use std::sync::Arc;
 use holochain_wasmer_host::prelude::WasmError;
-use holochain_zome_types::info::DnaInfo;
 
 pub fn dna_info(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     _call_context: Arc<CallContext>,
     _input: (),
 ) -> Result<DnaInfo, WasmError> {
-    unimplemented!()
+    let dna_def = ribosome.dna_def();
+    Ok(DnaInfo {
+        name: dna_def.name.clone(),
+        hash: dna_def.as_hash().clone(),
+        properties: dna_def.properties.clone(),
+        zome_names: dna_def.zomes.iter().map(|(zome_name, _)| zome_name.clone()).collect(),
+    })
 }
 
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod test {
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+    use holochain_zome_types::prelude::*;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn invoke_import_dna_info_test() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace =
+            crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock;
+        let dna_info: DnaInfo =
+            crate::call_test_ribosome!(host_access, TestWasm::ZomeInfo, "dna_info", ());
+        assert_eq!(dna_info.name, "test");
+        assert_eq!(dna_info.zome_names, vec![TestWasm::ZomeInfo.into()]);
+    }
+}
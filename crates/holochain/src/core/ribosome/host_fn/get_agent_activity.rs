@@ -22,6 +22,12 @@ pub fn get_agent_activity(
             include_rejected_activity: true,
             ..Default::default()
         },
+        ActivityRequest::Elements => GetActivityOptions {
+            include_valid_activity: true,
+            include_rejected_activity: true,
+            include_full_headers: true,
+            ..Default::default()
+        },
     };
 
     // Get the network from the context
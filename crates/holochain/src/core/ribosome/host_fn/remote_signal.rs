@@ -93,6 +93,8 @@ mod tests {
                     // empty access converts to unrestricted
                     access: ().into(),
                     functions,
+                    curry_payloads: CurryPayloads::default(),
+                    limits: CapGrantLimits::default(),
                 };
                 api.create(EntryWithDefId::new(
                     EntryDefId::CapGrant,
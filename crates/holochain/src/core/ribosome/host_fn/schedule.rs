@@ -1,12 +1,76 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
+use holochain_state::scheduled_fn_db::ScheduledFn;
+use holochain_types::prelude::*;
 use std::sync::Arc;
 use holochain_wasmer_host::prelude::WasmError;
 
+/// Persist a call to a function in the current zome, to be made once the
+/// requested duration has elapsed.
+/// The schedule is written with the rest of the workspace so it is dropped
+/// if the zome call fails.
 pub fn schedule(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: core::time::Duration,
+    call_context: Arc<CallContext>,
+    input: Schedule,
 ) -> Result<(), WasmError> {
-    unimplemented!()
+    let due = timestamp::now()
+        .checked_add(&input.execute_after)
+        .ok_or_else(|| WasmError::Host("Scheduled time is out of range".to_string()))?;
+    let scheduled_fn = ScheduledFn {
+        zome_name: call_context.zome.zome_name().clone(),
+        fn_name: input.scheduled_fn,
+        payload: input.payload,
+        due,
+    };
+    tokio_safe_block_on::tokio_safe_block_forever_on(async move {
+        call_context
+            .host_access
+            .workspace()
+            .write()
+            .await
+            .scheduled_fns
+            .schedule(scheduled_fn)
+            .map_err(|database_error| WasmError::Host(database_error.to_string()))
+    })
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use holochain_lmdb::env::ReadManager;
+    use holochain_types::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn schedule_persists_scheduled_fn() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace =
+            crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock.clone();
+        let before = timestamp::now();
+        let _: () =
+            crate::call_test_ribosome!(host_access, TestWasm::Schedule, "schedule_ping", ());
+
+        let workspace = workspace_lock.read().await;
+        let env_ref = env.guard();
+        let reader = env_ref.reader().unwrap();
+        let next_due = workspace.scheduled_fns.next_due(&reader).unwrap().unwrap();
+        assert!(next_due >= before);
+
+        let due = workspace.scheduled_fns.due(&reader, next_due).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].1.zome_name, TestWasm::Schedule.into());
+        assert_eq!(due[0].1.fn_name, "ping".into());
+    }
 }
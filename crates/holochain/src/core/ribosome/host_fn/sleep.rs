@@ -1,12 +1,112 @@
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
+use crate::core::ribosome::ZomeCallLimits;
 use std::sync::Arc;
+use std::time::Instant;
 use holochain_wasmer_host::prelude::WasmError;
 
+/// Suspend the zome call for the requested duration.
+///
+/// The guest runs on a blocking thread so waiting here never holds up a tokio
+/// worker. A single sleep may not be longer than the configured maximum, and
+/// may not run past the deadline of the zome call.
+/// Callbacks such as validation can't sleep.
 pub fn sleep(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: core::time::Duration,
+    call_context: Arc<CallContext>,
+    input: core::time::Duration,
 ) -> Result<(), WasmError> {
-    unimplemented!()
+    let limits = call_context.host_access.limits().ok_or_else(|| {
+        WasmError::Host("sleep is only available to zome calls and scheduled functions".into())
+    })?;
+    let wake_at = wake_at(limits, input, Instant::now())?;
+    tokio_safe_block_on::tokio_safe_block_forever_on(tokio::time::delay_until(
+        tokio::time::Instant::from_std(wake_at),
+    ));
+    Ok(())
+}
+
+/// When a sleep starting at `now` should end, if the limits allow it.
+fn wake_at(
+    limits: &ZomeCallLimits,
+    duration: core::time::Duration,
+    now: Instant,
+) -> Result<Instant, WasmError> {
+    if duration > limits.max_sleep {
+        return Err(WasmError::Host(format!(
+            "Cannot sleep for {:?}, the maximum is {:?}",
+            duration, limits.max_sleep
+        )));
+    }
+    let wake_at = now + duration;
+    if wake_at > limits.deadline {
+        return Err(WasmError::Host(format!(
+            "Sleeping for {:?} would exceed the zome call timeout",
+            duration
+        )));
+    }
+    Ok(wake_at)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn sleep_is_limited() {
+        let now = Instant::now();
+        let limits = ZomeCallLimits {
+            deadline: now + Duration::from_secs(10),
+            max_sleep: Duration::from_secs(5),
+        };
+
+        assert_eq!(
+            wake_at(&limits, Duration::from_secs(1), now).unwrap(),
+            now + Duration::from_secs(1)
+        );
+        // Longer than the maximum
+        assert!(wake_at(&limits, Duration::from_secs(6), now).is_err());
+        // Within the maximum but past the deadline
+        assert!(wake_at(
+            &limits,
+            Duration::from_secs(4),
+            now + Duration::from_secs(7)
+        )
+        .is_err());
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn invoke_import_sleep_test() {
+        let test_env = holochain_lmdb::test_utils::test_cell_env();
+        let env = test_env.env();
+        let mut workspace =
+            crate::core::workflow::CallZomeWorkspace::new(env.clone().into()).unwrap();
+        crate::core::workflow::fake_genesis(&mut workspace.source_chain)
+            .await
+            .unwrap();
+
+        let workspace_lock = crate::core::workflow::CallZomeWorkspaceLock::new(workspace);
+
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace_lock;
+        let start = Instant::now();
+        let _: () = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::SysTime,
+            "sleep",
+            Duration::from_millis(100)
+        );
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
 }
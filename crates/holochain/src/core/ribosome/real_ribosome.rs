@@ -1,7 +1,9 @@
 use super::guest_callback::entry_defs::EntryDefsHostAccess;
+use super::guest_callback::genesis_self_check::GenesisSelfCheckHostAccess;
 use super::guest_callback::init::InitHostAccess;
 use super::guest_callback::migrate_agent::MigrateAgentHostAccess;
 use super::guest_callback::post_commit::PostCommitHostAccess;
+use super::guest_callback::schedule::ScheduleHostAccess;
 use super::guest_callback::validate::ValidateHostAccess;
 use super::guest_callback::validation_package::ValidationPackageHostAccess;
 use super::host_fn::get_agent_activity::get_agent_activity;
@@ -12,12 +14,15 @@ use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::error::RibosomeResult;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsInvocation;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsResult;
+use crate::core::ribosome::guest_callback::genesis_self_check::GenesisSelfCheckInvocation;
+use crate::core::ribosome::guest_callback::genesis_self_check::GenesisSelfCheckResult;
 use crate::core::ribosome::guest_callback::init::InitInvocation;
 use crate::core::ribosome::guest_callback::init::InitResult;
 use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentInvocation;
 use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitInvocation;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitResult;
+use crate::core::ribosome::guest_callback::schedule::ScheduleInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateResult;
 use crate::core::ribosome::guest_callback::validate_link::ValidateLinkHostAccess;
@@ -27,7 +32,9 @@ use crate::core::ribosome::guest_callback::validation_package::ValidationPackage
 use crate::core::ribosome::guest_callback::validation_package::ValidationPackageResult;
 use crate::core::ribosome::guest_callback::CallIterator;
 use crate::core::ribosome::host_fn::agent_info::agent_info;
+use crate::core::ribosome::host_fn::app_info::app_info;
 use crate::core::ribosome::host_fn::call::call;
+use crate::core::ribosome::host_fn::call_info::call_info;
 use crate::core::ribosome::host_fn::call_remote::call_remote;
 use crate::core::ribosome::host_fn::capability_claims::capability_claims;
 use crate::core::ribosome::host_fn::capability_grants::capability_grants;
@@ -37,6 +44,7 @@ use crate::core::ribosome::host_fn::create_link::create_link;
 use crate::core::ribosome::host_fn::create_x25519_keypair::create_x25519_keypair;
 use crate::core::ribosome::host_fn::delete::delete;
 use crate::core::ribosome::host_fn::delete_link::delete_link;
+use crate::core::ribosome::host_fn::dna_info::dna_info;
 use crate::core::ribosome::host_fn::emit_signal::emit_signal;
 use crate::core::ribosome::host_fn::get::get;
 use crate::core::ribosome::host_fn::get_details::get_details;
@@ -48,6 +56,7 @@ use crate::core::ribosome::host_fn::random_bytes::random_bytes;
 use crate::core::ribosome::host_fn::remote_signal::remote_signal;
 use crate::core::ribosome::host_fn::schedule::schedule;
 use crate::core::ribosome::host_fn::sign::sign;
+use crate::core::ribosome::host_fn::sleep::sleep;
 use crate::core::ribosome::host_fn::sys_time::sys_time;
 use crate::core::ribosome::host_fn::trace::trace;
 use crate::core::ribosome::host_fn::unreachable::unreachable;
@@ -237,8 +246,10 @@ impl RealRibosome {
         } = host_fn_access
         {
             ns.insert("__zome_info", func!(invoke_host_function!(zome_info)));
+            ns.insert("__dna_info", func!(invoke_host_function!(dna_info)));
         } else {
             ns.insert("__zome_info", func!(invoke_host_function!(unreachable)));
+            ns.insert("__dna_info", func!(invoke_host_function!(unreachable)));
         }
 
         if let HostFnAccess {
@@ -259,6 +270,8 @@ impl RealRibosome {
         } = host_fn_access
         {
             ns.insert("__agent_info", func!(invoke_host_function!(agent_info)));
+            ns.insert("__app_info", func!(invoke_host_function!(app_info)));
+            ns.insert("__call_info", func!(invoke_host_function!(call_info)));
             ns.insert(
                 "__capability_claims",
                 func!(invoke_host_function!(capability_claims)),
@@ -273,6 +286,8 @@ impl RealRibosome {
             );
         } else {
             ns.insert("__agent_info", func!(invoke_host_function!(unreachable)));
+            ns.insert("__app_info", func!(invoke_host_function!(unreachable)));
+            ns.insert("__call_info", func!(invoke_host_function!(unreachable)));
             ns.insert(
                 "__capability_claims",
                 func!(invoke_host_function!(unreachable)),
@@ -347,6 +362,7 @@ impl RealRibosome {
             ns.insert("__update", func!(invoke_host_function!(update)));
             ns.insert("__delete", func!(invoke_host_function!(delete)));
             ns.insert("__schedule", func!(invoke_host_function!(schedule)));
+            ns.insert("__sleep", func!(invoke_host_function!(sleep)));
         } else {
             ns.insert("__call", func!(invoke_host_function!(unreachable)));
             ns.insert("__create", func!(invoke_host_function!(unreachable)));
@@ -356,6 +372,7 @@ impl RealRibosome {
             ns.insert("__update", func!(invoke_host_function!(unreachable)));
             ns.insert("__delete", func!(invoke_host_function!(unreachable)));
             ns.insert("__schedule", func!(invoke_host_function!(unreachable)));
+            ns.insert("__sleep", func!(invoke_host_function!(unreachable)));
         }
         imports.register("env", ns);
 
@@ -402,6 +419,7 @@ impl RibosomeT for RealRibosome {
     ) -> Result<Option<ExternIO>, RibosomeError> {
         let call_context = CallContext {
             zome: zome.clone(),
+            function_name: to_call.clone(),
             host_access,
         };
 
@@ -452,10 +470,15 @@ impl RibosomeT for RealRibosome {
     /// so that it can be passed on to source chain manager for transactional writes
     fn call_zome_function(
         &self,
-        host_access: ZomeCallHostAccess,
+        mut host_access: ZomeCallHostAccess,
         invocation: ZomeCallInvocation,
     ) -> RibosomeResult<ZomeCallResponse> {
-        Ok(if invocation.is_authorized(&host_access)? {
+        // A call the call zome workflow has already authorized, and counted
+        // against its grant, is not checked again
+        if host_access.capability.is_none() {
+            host_access.capability = invocation.authorization(&host_access)?;
+        }
+        Ok(if host_access.capability.is_some() {
             // make a copy of these for the error handling below
             let zome_name = invocation.zome.zome_name().clone();
             let fn_name = invocation.fn_name.clone();
@@ -517,6 +540,14 @@ impl RibosomeT for RealRibosome {
         do_callback!(self, access, invocation, MigrateAgentCallbackResult)
     }
 
+    fn run_genesis_self_check(
+        &self,
+        access: GenesisSelfCheckHostAccess,
+        invocation: GenesisSelfCheckInvocation,
+    ) -> RibosomeResult<GenesisSelfCheckResult> {
+        do_callback!(self, access, invocation, ValidateCallbackResult)
+    }
+
     fn run_validation_package(
         &self,
         access: ValidationPackageHostAccess,
@@ -532,6 +563,19 @@ impl RibosomeT for RealRibosome {
     ) -> RibosomeResult<PostCommitResult> {
         do_callback!(self, access, invocation, PostCommitCallbackResult)
     }
+
+    fn run_schedule(
+        &self,
+        access: ScheduleHostAccess,
+        invocation: ScheduleInvocation,
+    ) -> RibosomeResult<()> {
+        let zome_name = invocation.zome().zome_name().clone();
+        let fn_name = invocation.fn_name().clone();
+        match self.call_iterator(access.into(), invocation).next()? {
+            Some(_) => Ok(()),
+            None => Err(RibosomeError::ZomeFnNotExists(zome_name, fn_name)),
+        }
+    }
 }
 
 #[cfg(test)]
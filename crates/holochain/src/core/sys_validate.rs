@@ -50,11 +50,26 @@ pub async fn verify_header_signature(
     }
 }
 
-/// Verify the author key was valid at the time
-/// of signing with dpki
-/// TODO: This is just a stub until we have dpki.
-pub async fn author_key_is_valid(_author: &AgentPubKey) -> SysValidationResult<bool> {
-    Ok(true)
+/// Check with dpki that the author key was valid when the header was made.
+/// Keys which were never registered are rejected, as are keys which were
+/// revoked before the header's timestamp. Headers made before a revocation
+/// stay valid.
+/// If dpki can't be asked, for example while its app is not installed yet,
+/// the check is tried again later.
+pub async fn check_author_key(
+    header: &Header,
+    conductor_api: &impl CellConductorApiT,
+) -> SysValidationResult<()> {
+    let author = header.author();
+    let key_state = conductor_api
+        .agent_key_state(author)
+        .await
+        .map_err(|e| ValidationOutcome::AuthorKeyUnavailable(author.clone(), e.to_string()))?;
+    if key_state.is_valid_at(header.timestamp()) {
+        Ok(())
+    } else {
+        Err(ValidationOutcome::AuthorKey(author.clone(), key_state).into())
+    }
 }
 
 /// Check that previous header makes sense
@@ -96,14 +111,14 @@ pub async fn check_valid_if_dna(
     })
 }
 
-// TODO: I think this can be removed now as rollbacks are detected when inserting
-// metadata into the metadata buf.
-/// Check if there are other headers at this
-/// sequence number
+/// Find the other headers by this author at the same
+/// sequence number, which would fork or roll back their chain.
+/// Headers that have been integrated or are pending validation
+/// are both checked.
 pub async fn check_chain_rollback(
     header: &Header,
     workspace: &SysValidationWorkspace,
-) -> SysValidationResult<()> {
+) -> SysValidationResult<Vec<SignedHeaderHashed>> {
     let header_hash = HeaderHash::with_data_sync(header);
     let k = ChainItemKey::AgentStatusSequence(
         header.author().clone(),
@@ -113,37 +128,40 @@ pub async fn check_chain_rollback(
     let env = workspace.meta_vault.env();
     // Check there are no conflicting chain items
     // at any valid or potentially valid stores.
-    let count = fresh_reader!(env, |r| {
-        let vault_count = workspace
+    let (vault_hashes, pending_hashes) = fresh_reader!(env, |r| {
+        let vault_hashes: Vec<HeaderHash> = workspace
             .meta_vault
             .get_activity(&r, k.clone())?
-            .filter(|thh| Ok(thh.header_hash != header_hash))
-            .count()?;
-        let pending_count = workspace
+            .map(|thh| Ok(thh.header_hash))
+            .filter(|h| Ok(*h != header_hash))
+            .collect()?;
+        let pending_hashes: Vec<HeaderHash> = workspace
             .meta_pending
             .get_activity(&r, k.clone())?
-            .filter(|thh| Ok(thh.header_hash != header_hash))
-            .count()?;
-        DatabaseResult::Ok(vault_count + pending_count)
+            .map(|thh| Ok(thh.header_hash))
+            .filter(|h| Ok(*h != header_hash && !vault_hashes.contains(h)))
+            .collect()?;
+        DatabaseResult::Ok((vault_hashes, pending_hashes))
     })?;
 
-    // Ok or log warning
-    if count == 0 {
-        return Ok(());
-    } else {
-        let s = tracing::warn_span!("agent_activity");
-        let _g = s.enter();
-        // TODO: implement real rollback detection once we know what that looks like
-        tracing::error!(
-            "Chain rollback detected at position {} for agent {:?} from header {:?}
-            There were {} headers at this position",
+    let mut forks = Vec::with_capacity(vault_hashes.len() + pending_hashes.len());
+    for hash in vault_hashes {
+        forks.extend(workspace.element_vault.get_header(&hash)?);
+    }
+    for hash in pending_hashes {
+        forks.extend(workspace.element_pending.get_header(&hash)?);
+    }
+    if !forks.is_empty() {
+        tracing::warn!(
+            "Chain fork detected at position {} for agent {:?} from header {:?}
+            There were {} other headers at this position",
             header.header_seq(),
             header.author(),
             header,
-            count,
+            forks.len(),
         );
     }
-    Ok(())
+    Ok(forks)
 }
 
 /// Placeholder for future spam check.
@@ -456,7 +474,7 @@ impl IncomingDhtOpSender {
     ) -> SysValidationResult<()> {
         if let Some(op) = make_op(element) {
             let ops = vec![op];
-            incoming_dht_ops_workflow(&self.env, self.sys_validation_trigger, ops, None)
+            incoming_dht_ops_workflow(&self.env, self.sys_validation_trigger, ops, None, false)
                 .await
                 .map_err(Box::new)?;
         }
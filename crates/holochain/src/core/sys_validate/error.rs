@@ -86,6 +86,10 @@ impl<E> TryFrom<OutcomeOrError<ValidationOutcome, E>> for ValidationOutcome {
 /// failed validation.
 #[derive(Error, Debug)]
 pub enum ValidationOutcome {
+    #[error("The author key {0} is {1:?} according to dpki")]
+    AuthorKey(AgentPubKey, KeyState),
+    #[error("The state of the author key {0} could not be looked up in dpki: {1}")]
+    AuthorKeyUnavailable(AgentPubKey, String),
     #[error("The element with signature {0:?} and header {1:?} was found to be counterfeit")]
     Counterfeit(Signature, Header),
     #[error("The dependency {0:?} was not found on the DHT")]
@@ -36,6 +36,54 @@ async fn verify_header_signature_test() {
     );
 }
 
+#[tokio::test(threaded_scheduler)]
+async fn check_author_key_test() {
+    let mut create_link = fixt!(CreateLink);
+    create_link.timestamp = Timestamp(100, 0);
+    let header = Header::from(create_link);
+    let mut conductor_api = MockCellConductorApi::new();
+    conductor_api
+        .expect_sync_agent_key_state()
+        .returning(|_| Ok(KeyState::Valid));
+    assert_matches!(check_author_key(&header, &conductor_api).await, Ok(()));
+
+    let rejected = vec![
+        KeyState::Revoked(None),
+        KeyState::Revoked(Some(Timestamp(100, 0))),
+        KeyState::Unknown,
+    ];
+    for state in rejected {
+        conductor_api.checkpoint();
+        conductor_api
+            .expect_sync_agent_key_state()
+            .returning(move |_| Ok(state));
+        assert_matches!(
+            check_author_key(&header, &conductor_api).await,
+            Err(SysValidationError::ValidationOutcome(ValidationOutcome::AuthorKey(_, s)))
+            if s == state
+        );
+    }
+
+    // Headers made before the key was revoked stay valid
+    conductor_api.checkpoint();
+    conductor_api
+        .expect_sync_agent_key_state()
+        .returning(|_| Ok(KeyState::Revoked(Some(Timestamp(101, 0)))));
+    assert_matches!(check_author_key(&header, &conductor_api).await, Ok(()));
+
+    // Failing to ask dpki is not a reason to reject
+    conductor_api.checkpoint();
+    conductor_api
+        .expect_sync_agent_key_state()
+        .returning(|_| Err(ConductorApiError::DnaMissing(fixt!(DnaHash))));
+    assert_matches!(
+        check_author_key(&header, &conductor_api).await,
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::AuthorKeyUnavailable(_, _)
+        ))
+    );
+}
+
 #[tokio::test(threaded_scheduler)]
 async fn check_previous_header() {
     let mut header = fixt!(CreateLink);
@@ -1,6 +1,7 @@
 //! The workflow and queue consumer for sys validation
 
 use std::collections::BinaryHeap;
+use std::convert::TryFrom;
 use std::convert::TryInto;
 use std::sync::Arc;
 
@@ -43,6 +44,7 @@ use holo_hash::DhtOpHash;
 use holochain_cascade::Cascade;
 use holochain_cascade::DbPair;
 use holochain_cascade::DbPairMut;
+use holochain_keystore::KeystoreSender;
 use holochain_lmdb::buffer::BufferedStore;
 use holochain_lmdb::buffer::KvBufFresh;
 use holochain_lmdb::db::INTEGRATED_DHT_OPS;
@@ -53,6 +55,7 @@ use holochain_p2p::actor::GetActivityOptions;
 use holochain_p2p::HolochainP2pCell;
 use holochain_p2p::HolochainP2pCellT;
 use holochain_state::prelude::*;
+use holochain_state::validation_receipts_db::ValidationResult;
 use holochain_types::prelude::*;
 use holochain_zome_types::Entry;
 use holochain_zome_types::HeaderHashed;
@@ -77,7 +80,10 @@ pub async fn app_validation_workflow(
     conductor_api: impl CellConductorApiT,
     network: HolochainP2pCell,
 ) -> WorkflowResult<WorkComplete> {
-    let complete = app_validation_workflow_inner(&mut workspace, conductor_api, &network).await?;
+    let keystore = conductor_api.keystore().clone();
+    let validator = conductor_api.cell_id().agent_pubkey().clone();
+    let (complete, receipts) =
+        app_validation_workflow_inner(&mut workspace, conductor_api, &network).await?;
     // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
 
     // commit the workspace
@@ -86,13 +92,48 @@ pub async fn app_validation_workflow(
     // trigger other workflows
     trigger_integration.trigger();
 
+    // Let the authors know their ops are held now that they are committed
+    send_validation_receipts(receipts, validator, &keystore, network).await;
+
     Ok(complete)
 }
+
+/// Sign and send a receipt for each valid op whose author asked for one.
+/// A receipt which can't be sent is only logged, the author publishes the
+/// op again until it has enough of them.
+async fn send_validation_receipts(
+    receipts: Vec<(AgentPubKey, DhtOpHash)>,
+    validator: AgentPubKey,
+    keystore: &KeystoreSender,
+    mut network: HolochainP2pCell,
+) {
+    for (author, dht_op_hash) in receipts {
+        let receipt = ValidationReceipt {
+            dht_op_hash,
+            validation_result: ValidationResult::Valid,
+            validator: validator.clone(),
+        };
+        let result = async {
+            let receipt = receipt.sign(keystore).await?;
+            let receipt = SerializedBytes::try_from(receipt)?;
+            network
+                .send_validation_receipt(author.clone(), receipt)
+                .await
+                .map_err(WorkflowError::from)
+        }
+        .await;
+        if let Err(e) = result {
+            warn!(?author, ?e, "Failed to send validation receipt");
+        }
+    }
+}
+
 async fn app_validation_workflow_inner(
     workspace: &mut AppValidationWorkspace,
     conductor_api: impl CellConductorApiT,
     network: &HolochainP2pCell,
-) -> WorkflowResult<WorkComplete> {
+) -> WorkflowResult<(WorkComplete, Vec<(AgentPubKey, DhtOpHash)>)> {
+    let mut receipts = Vec::new();
     let env = workspace.validation_limbo.env().clone();
 
     // Drain the ops into a sorted binary heap
@@ -108,7 +149,8 @@ async fn app_validation_workflow_inner(
                         ValidationLimboStatus::SysValidated
                         | ValidationLimboStatus::AwaitingAppDeps(_) => Ok(true),
                         ValidationLimboStatus::Pending
-                        | ValidationLimboStatus::AwaitingSysDeps(_) => Ok(false),
+                        | ValidationLimboStatus::AwaitingSysDeps(_)
+                        | ValidationLimboStatus::AwaitingAuthorKey => Ok(false),
                     }
                 })?
                 .map_err(WorkflowError::from)
@@ -157,6 +199,9 @@ async fn app_validation_workflow_inner(
 
                 match outcome {
                     Outcome::Accepted => {
+                        if vlv.send_receipt {
+                            receipts.push((op.header().author().clone(), hash.clone()));
+                        }
                         let iv = IntegrationLimboValue {
                             validation_status: ValidationStatus::Valid,
                             op: vlv.op,
@@ -167,7 +212,19 @@ async fn app_validation_workflow_inner(
                         vlv.status = ValidationLimboStatus::AwaitingAppDeps(deps);
                         workspace.put_val_limbo(hash, vlv)?;
                     }
-                    Outcome::Rejected(_) => {
+                    Outcome::Rejected(failure) => {
+                        let proof = WarrantProof::InvalidOp {
+                            header: SignedHeader(op.header(), op.signature().clone()),
+                            failure,
+                        };
+                        workspace
+                            .warrants
+                            .issue(
+                                proof,
+                                conductor_api.cell_id().agent_pubkey().clone(),
+                                conductor_api.keystore(),
+                            )
+                            .await?;
                         let iv = IntegrationLimboValue {
                             op: vlv.op,
                             validation_status: ValidationStatus::Rejected,
@@ -179,7 +236,7 @@ async fn app_validation_workflow_inner(
             _ => unreachable!("Should not contain any other status"),
         }
     }
-    Ok(WorkComplete::Complete)
+    Ok((WorkComplete::Complete, receipts))
 }
 
 fn to_single_zome(zomes_to_invoke: ZomesToInvoke) -> AppValidationResult<Zome> {
@@ -689,11 +746,15 @@ async fn get_validation_package_remote(
                 cascade.get_agent_activity(agent_id, query, options).await?
             };
             match activity {
+                // A package is only complete if every public entry was found
                 AgentActivityResponse {
                     status: ChainStatus::Valid(_),
                     valid_activity: ChainItems::Full(elements),
                     ..
-                } => {
+                } if !elements
+                    .iter()
+                    .any(|el| matches!(el.entry(), ElementEntry::NotStored)) =>
+                {
                     // Cache this as a validation package
                     workspace.meta_cache.register_validation_package(
                         element.header_address(),
@@ -914,6 +975,8 @@ pub struct AppValidationWorkspace {
     // Cached data
     pub element_cache: ElementBuf,
     pub meta_cache: MetadataBuf,
+    // Warrants issued against the authors of rejected ops
+    pub warrants: WarrantStore,
     pub call_zome_workspace_lock: Option<CallZomeWorkspaceLock>,
 }
 
@@ -945,7 +1008,9 @@ impl AppValidationWorkspace {
         let element_authored = ElementBuf::authored(env.clone(), false)?;
         let meta_authored = MetadataBuf::authored(env.clone())?;
         let element_rejected = ElementBuf::rejected(env.clone())?;
-        let meta_rejected = MetadataBuf::rejected(env)?;
+        let meta_rejected = MetadataBuf::rejected(env.clone())?;
+
+        let warrants = WarrantStore::new(env)?;
 
         Ok(Self {
             integrated_dht_ops,
@@ -961,6 +1026,7 @@ impl AppValidationWorkspace {
             meta_rejected,
             element_cache,
             meta_cache,
+            warrants,
             call_zome_workspace_lock,
         })
     }
@@ -1024,6 +1090,7 @@ impl AppValidationWorkspace {
             .with_pending(pending_data)
             .with_cache(cache_data)
             .with_rejected(rejected_data)
+            .with_warrants(&mut self.warrants)
             .with_network(network)
     }
 }
@@ -1034,6 +1101,7 @@ impl Workspace for AppValidationWorkspace {
         self.integration_limbo.flush_to_txn_ref(writer)?;
         self.element_pending.flush_to_txn_ref(writer)?;
         self.meta_pending.flush_to_txn_ref(writer)?;
+        self.warrants.flush_to_txn_ref(writer)?;
 
         // Flush for cascade
         self.element_cache.flush_to_txn_ref(writer)?;
@@ -230,6 +230,7 @@ async fn get_agent_activity_test() {
             status,
             highest_observed,
             agent: alice_agent_id.clone(),
+            warrants: Vec::new(),
         }
     };
 
@@ -272,6 +273,7 @@ async fn get_agent_activity_test() {
             rejected_activity,
             status: activity.status,
             highest_observed: activity.highest_observed,
+            warrants: activity.warrants,
         };
         activity
     };
@@ -605,9 +607,12 @@ async fn get_agent_activity_host_fn_test() {
         holochain_zome_types::query::AgentActivity {
             valid_activity: valid_activity,
             rejected_activity: Vec::new(),
+            valid_elements: Vec::new(),
+            rejected_elements: Vec::new(),
             status,
             highest_observed,
             warrants: Vec::new(),
+            claims: Vec::new(),
         }
     };
 
@@ -653,6 +658,45 @@ async fn get_agent_activity_host_fn_test() {
             .decode()
             .unwrap();
     assert_eq!(agent_activity, expected_activity);
+
+    // Request the elements with their entries
+    let agent_activity = alice_call_data
+        .get_api(TestWasm::Create)
+        .get_agent_activity(
+            alice_agent_id,
+            &ChainQueryFilter::new().include_entries(true),
+            ActivityRequest::Elements,
+        )
+        .await;
+    let alice_source_chain = SourceChain::public_only(alice_env.clone().into()).unwrap();
+    let expected_elements: Vec<_> = expected_activity
+        .valid_activity
+        .iter()
+        .filter_map(|(_, hash)| alice_source_chain.get_element(hash).unwrap())
+        .collect();
+    assert_eq!(
+        agent_activity.valid_activity,
+        expected_activity.valid_activity
+    );
+    assert_eq!(agent_activity.valid_elements, expected_elements);
+    assert!(agent_activity.rejected_elements.is_empty());
+
+    // Page through the chain
+    let mut paged = Vec::new();
+    let chain_len = expected_activity.valid_activity.len() as u32;
+    for start in (0..chain_len).step_by(3) {
+        let page = alice_call_data
+            .get_api(TestWasm::Create)
+            .get_agent_activity(
+                alice_agent_id,
+                &ChainQueryFilter::new().sequence_range(start..start + 3),
+                ActivityRequest::Full,
+            )
+            .await;
+        assert!(page.valid_activity.len() <= 3);
+        paged.extend(page.valid_activity);
+    }
+    assert_eq!(paged, expected_activity.valid_activity);
     conductor_test.shutdown_conductor().await;
 }
 
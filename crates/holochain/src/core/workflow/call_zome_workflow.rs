@@ -10,6 +10,7 @@ use crate::core::ribosome::error::RibosomeResult;
 use crate::core::ribosome::RibosomeT;
 use crate::core::ribosome::ZomeCallHostAccess;
 use crate::core::ribosome::ZomeCallInvocation;
+use crate::core::ribosome::ZomeCallLimits;
 pub use call_zome_workspace_lock::CallZomeWorkspaceLock;
 use either::Either;
 use holochain_cascade::Cascade;
@@ -18,11 +19,14 @@ use holochain_cascade::DbPairMut;
 use holochain_keystore::KeystoreSender;
 use holochain_lmdb::prelude::*;
 use holochain_p2p::HolochainP2pCell;
+use holochain_state::cap_grant_use_db::CapGrantUseStore;
 use holochain_state::element_buf::ElementBuf;
 use holochain_state::metadata::MetadataBuf;
 use holochain_state::metadata::MetadataBufT;
+use holochain_state::scheduled_fn_db::ScheduledFnStore;
 use holochain_state::source_chain::SourceChain;
 use holochain_state::source_chain::SourceChainError;
+use holochain_state::warrant_db::WarrantStore;
 use holochain_state::workspace::Workspace;
 use holochain_state::workspace::WorkspaceResult;
 use holochain_zome_types::element::Element;
@@ -52,9 +56,10 @@ pub struct CallZomeWorkflowArgs<Ribosome: RibosomeT + Send, C: CellConductorApiT
     workspace_lock,
     network,
     keystore,
-    writer,
+    env,
     args,
-    trigger_produce_dht_ops
+    trigger_produce_dht_ops,
+    trigger_schedule
 ))]
 pub async fn call_zome_workflow<
     'env,
@@ -64,12 +69,15 @@ pub async fn call_zome_workflow<
     workspace_lock: CallZomeWorkspaceLock,
     network: HolochainP2pCell,
     keystore: KeystoreSender,
-    writer: OneshotWriter,
+    env: EnvironmentWrite,
     args: CallZomeWorkflowArgs<Ribosome, C>,
     mut trigger_produce_dht_ops: TriggerSender,
+    mut trigger_schedule: TriggerSender,
 ) -> WorkflowResult<ZomeCallResult> {
     let should_write = args.is_root_zome_call;
-    let result = call_zome_workflow_inner(workspace_lock.clone(), network, keystore, args).await?;
+    let result =
+        call_zome_workflow_inner(workspace_lock.clone(), network, keystore, env.clone(), args)
+            .await?;
 
     // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
 
@@ -77,10 +85,13 @@ pub async fn call_zome_workflow<
     if should_write {
         let mut guard = workspace_lock.write().await;
         let workspace = &mut guard;
+        let writer = OneshotWriter::from(env);
         writer.with_writer(|writer| Ok(workspace.flush_to_txn_ref(writer)?))?;
     }
 
     trigger_produce_dht_ops.trigger();
+    // The call may have scheduled functions
+    trigger_schedule.trigger();
 
     Ok(result)
 }
@@ -93,6 +104,7 @@ async fn call_zome_workflow_inner<
     workspace_lock: CallZomeWorkspaceLock,
     network: HolochainP2pCell,
     keystore: KeystoreSender,
+    env: EnvironmentWrite,
     args: CallZomeWorkflowArgs<Ribosome, C>,
 ) -> WorkflowResult<ZomeCallResult> {
     let CallZomeWorkflowArgs {
@@ -104,6 +116,7 @@ async fn call_zome_workflow_inner<
     } = args;
 
     let call_zome_handle = conductor_api.clone().into_call_zome_handle();
+    let limits = ZomeCallLimits::new(&conductor_api.zome_call_config());
     let zome = invocation.zome.clone();
 
     // Get the current head
@@ -111,25 +124,154 @@ async fn call_zome_workflow_inner<
 
     tracing::trace!(line = line!());
     // Create the unsafe sourcechain for use with wasm closure
-    let (ribosome, result) = tokio::task::spawn_blocking({
+    let (ribosome, result, counted) = tokio::task::spawn_blocking({
         let workspace_lock = workspace_lock.clone();
         let network = network.clone();
+        let env = env.clone();
         move || {
-            let host_access = ZomeCallHostAccess::new(
+            let mut host_access = ZomeCallHostAccess::new(
                 workspace_lock,
                 keystore,
                 network,
                 signal_tx,
                 call_zome_handle,
                 invocation.cell_id.clone(),
+                limits,
             );
-            let result = ribosome.call_zome_function(host_access, invocation);
-            (ribosome, result)
+            let mut counted = None;
+            let result = invocation
+                .authorization(&host_access)
+                .and_then(|authorization| match authorization {
+                    Some(authorization) if use_grant(&env, &authorization)? => {
+                        counted = Some(authorization.clone());
+                        let invocation = curry_invocation(invocation, &authorization)?;
+                        // The ribosome runs the call with this authorization
+                        // rather than check the grant again
+                        host_access.capability = Some(authorization);
+                        ribosome.call_zome_function(host_access, invocation)
+                    }
+                    _ => Ok(ZomeCallResponse::Unauthorized(
+                        invocation.cell_id,
+                        invocation.zome.zome_name().clone(),
+                        invocation.fn_name,
+                        invocation.provenance,
+                    )),
+                });
+            (ribosome, result, counted)
         }
     })
     .await?;
     tracing::trace!(line = line!());
 
+    let validation = validate_new_elements(
+        chain_head_start_len,
+        zome,
+        &ribosome,
+        workspace_lock,
+        network,
+        &conductor_api,
+    )
+    .await;
+
+    // Only successful calls use up their grant
+    let succeeded = validation.is_ok() && matches!(result, Ok(ZomeCallResponse::Ok(_)));
+    if let (false, Some(authorization)) = (succeeded, counted) {
+        refund_grant(&env, &authorization)?;
+    }
+    validation?;
+
+    Ok(result)
+}
+
+/// If the grant that authorizes the call curries a payload into the called
+/// function then merge it into the payload of the invocation.
+fn curry_invocation(
+    mut invocation: ZomeCallInvocation,
+    authorization: &CapabilityInfo,
+) -> RibosomeResult<ZomeCallInvocation> {
+    if let CapGrant::RemoteAgent(grant) = &authorization.grant {
+        let function = (
+            invocation.zome.zome_name().clone(),
+            invocation.fn_name.clone(),
+        );
+        if let Some(curried) = grant.curry_payloads.get(&function) {
+            invocation.payload = merge_payloads(&invocation.payload, curried)?
+                .ok_or_else(|| RibosomeError::CurryPayloadNotMap(function.0, function.1))?;
+        }
+    }
+    Ok(invocation)
+}
+
+/// Count the call against the grant that authorizes it, returning whether
+/// the grant had a use left.
+///
+/// The count is checked and written in its own transaction before the call
+/// runs, so calls running at the same time can't use a grant more often than
+/// it allows, and nested calls, whose workspace isn't flushed by their own
+/// workflow, are counted too. The use is given back by [refund_grant] if the
+/// call fails.
+fn use_grant(env: &EnvironmentWrite, authorization: &CapabilityInfo) -> RibosomeResult<bool> {
+    match &authorization.grant {
+        CapGrant::RemoteAgent(grant) => {
+            let uses = CapGrantUseStore::new(env.clone().into())?;
+            Ok(env
+                .guard()
+                .with_commit(|writer| uses.try_use(writer, grant))?)
+        }
+        _ => Ok(true),
+    }
+}
+
+/// Give back the use of the grant that authorized a call which failed
+fn refund_grant(env: &EnvironmentWrite, authorization: &CapabilityInfo) -> RibosomeResult<()> {
+    match &authorization.grant {
+        CapGrant::RemoteAgent(grant) => {
+            let uses = CapGrantUseStore::new(env.clone().into())?;
+            Ok(env
+                .guard()
+                .with_commit(|writer| uses.refund(writer, grant))?)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Merge a curried payload into the payload given by the caller.
+/// Every caller entry with a curried key is removed before the curried
+/// entries are added, so a caller can't override a curried key by repeating it.
+/// Returns `None` unless both payloads are maps.
+fn merge_payloads(
+    payload: &ExternIO,
+    curried: &SerializedBytes,
+) -> Result<Option<ExternIO>, SerializedBytesError> {
+    let read_value = |bytes: &[u8]| {
+        rmpv::decode::read_value(&mut &bytes[..])
+            .map_err(|e| SerializedBytesError::Deserialize(e.to_string()))
+    };
+    let (mut entries, curried_entries) =
+        match (read_value(payload.as_bytes()), read_value(curried.bytes())?) {
+            (Ok(rmpv::Value::Map(entries)), rmpv::Value::Map(curried_entries)) => {
+                (entries, curried_entries)
+            }
+            _ => return Ok(None),
+        };
+    entries.retain(|(key, _)| !curried_entries.iter().any(|(k, _)| k == key));
+    entries.extend(curried_entries);
+    let mut bytes = Vec::new();
+    rmpv::encode::write_value(&mut bytes, &rmpv::Value::Map(entries))
+        .map_err(|e| SerializedBytesError::Serialize(e.to_string()))?;
+    Ok(Some(ExternIO::from(bytes)))
+}
+
+/// Sys and app validate every element that was committed after
+/// `chain_head_start_len`, failing on the first invalid element.
+pub(crate) async fn validate_new_elements(
+    chain_head_start_len: usize,
+    zome: Zome,
+    ribosome: &impl RibosomeT,
+    workspace_lock: CallZomeWorkspaceLock,
+    network: HolochainP2pCell,
+    conductor_api: &impl CellConductorApiT,
+) -> WorkflowResult<()> {
     let to_app_validate = {
         let mut workspace = workspace_lock.write().await;
         // Get the new head
@@ -144,7 +286,7 @@ async fn call_zome_workflow_inner<
             // Loop forwards through all the new elements
             let mut i = chain_head_start_len;
             while let Some(element) = workspace.source_chain.get_at_index(i as u32)? {
-                sys_validate_element(&element, &mut workspace, network.clone(), &conductor_api)
+                sys_validate_element(&element, &mut workspace, network.clone(), conductor_api)
                     .await
                     // If the was en error exit
                     // If the validation failed, exit with an InvalidCommit
@@ -200,7 +342,7 @@ async fn call_zome_workflow_inner<
                             link_add,
                             base,
                             target,
-                            &ribosome,
+                            ribosome,
                             workspace_lock.clone(),
                             network.clone(),
                         )?,
@@ -210,7 +352,7 @@ async fn call_zome_workflow_inner<
                     app_validation_workflow::run_delete_link_validation_callback(
                         zome.clone(),
                         delete_link.clone(),
-                        &ribosome,
+                        ribosome,
                         workspace_lock.clone(),
                         network.clone(),
                     )?,
@@ -219,10 +361,10 @@ async fn call_zome_workflow_inner<
                     app_validation_workflow::run_validation_callback_direct(
                         zome.clone(),
                         chain_element,
-                        &ribosome,
+                        ribosome,
                         workspace_lock.clone(),
                         network.clone(),
-                        &conductor_api,
+                        conductor_api,
                     )
                     .await?,
                 ),
@@ -257,7 +399,7 @@ async fn call_zome_workflow_inner<
         }
     }
 
-    Ok(result)
+    Ok(())
 }
 
 pub struct CallZomeWorkspace {
@@ -269,6 +411,10 @@ pub struct CallZomeWorkspace {
     pub meta_rejected: MetadataBuf<RejectedPrefix>,
     pub element_cache: ElementBuf,
     pub meta_cache: MetadataBuf,
+    pub scheduled_fns: ScheduledFnStore,
+    pub cap_grant_uses: CapGrantUseStore,
+    /// Warrants fetched along with agent activity
+    pub warrants: WarrantStore,
 }
 
 impl<'a> CallZomeWorkspace {
@@ -280,7 +426,10 @@ impl<'a> CallZomeWorkspace {
         let element_rejected = ElementBuf::rejected(env.clone())?;
         let meta_rejected = MetadataBuf::rejected(env.clone())?;
         let element_cache = ElementBuf::cache(env.clone())?;
-        let meta_cache = MetadataBuf::cache(env)?;
+        let meta_cache = MetadataBuf::cache(env.clone())?;
+        let scheduled_fns = ScheduledFnStore::new(env.clone())?;
+        let cap_grant_uses = CapGrantUseStore::new(env.clone())?;
+        let warrants = WarrantStore::new(env)?;
 
         Ok(CallZomeWorkspace {
             source_chain,
@@ -291,6 +440,9 @@ impl<'a> CallZomeWorkspace {
             meta_rejected,
             element_cache,
             meta_cache,
+            scheduled_fns,
+            cap_grant_uses,
+            warrants,
         })
     }
 
@@ -307,6 +459,7 @@ impl<'a> CallZomeWorkspace {
             &mut self.meta_cache,
             network,
         )
+        .with_warrants(&mut self.warrants)
     }
 
     /// Cascade without a network connection
@@ -331,6 +484,9 @@ impl Workspace for CallZomeWorkspace {
         self.meta_authored.flush_to_txn_ref(writer)?;
         self.element_cache.flush_to_txn_ref(writer)?;
         self.meta_cache.flush_to_txn_ref(writer)?;
+        self.scheduled_fns.flush_to_txn_ref(writer)?;
+        self.cap_grant_uses.flush_to_txn_ref(writer)?;
+        self.warrants.flush_to_txn_ref(writer)?;
         Ok(())
     }
 }
@@ -362,7 +518,225 @@ pub mod tests {
         a: u32,
     }
 
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+    struct ChannelPost {
+        channel: String,
+        content: String,
+    }
+
+    #[test]
+    fn curried_payload_is_merged() {
+        let payload = ExternIO::encode(ChannelPost {
+            channel: "general".into(),
+            content: "hello".into(),
+        })
+        .unwrap();
+        #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
+        struct Channel {
+            channel: String,
+        }
+        let curried = SerializedBytes::try_from(Channel {
+            channel: "announcements".into(),
+        })
+        .unwrap();
+
+        // The curried channel replaces the caller's channel
+        let merged: ChannelPost = merge_payloads(&payload, &curried)
+            .unwrap()
+            .unwrap()
+            .decode()
+            .unwrap();
+        assert_eq!(
+            merged,
+            ChannelPost {
+                channel: "announcements".into(),
+                content: "hello".into(),
+            }
+        );
+
+        // Repeating the curried key doesn't get the caller's channel through
+        let repeated = rmpv::Value::Map(vec![
+            ("channel".into(), "general".into()),
+            ("content".into(), "hello".into()),
+            ("channel".into(), "general".into()),
+        ]);
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, &repeated).unwrap();
+        let merged: ChannelPost = merge_payloads(&ExternIO::from(bytes), &curried)
+            .unwrap()
+            .unwrap()
+            .decode()
+            .unwrap();
+        assert_eq!(merged.channel, "announcements");
+
+        // Payloads that aren't maps can't be merged
+        assert!(merge_payloads(&ExternIO::encode(()).unwrap(), &curried)
+            .unwrap()
+            .is_none());
+        let curried = SerializedBytes::try_from(Payload { a: 1 }).unwrap();
+        assert!(merge_payloads(&ExternIO::encode(()).unwrap(), &curried)
+            .unwrap()
+            .is_none());
+    }
+
+    /// A call authorized by a grant with a curried payload runs the zome
+    /// function with the curried payload merged into the caller's payload
+    #[tokio::test(threaded_scheduler)]
+    async fn curried_grant_changes_payload() {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let mut invocation =
+            crate::fixt::ZomeCallInvocationFixturator::new(crate::fixt::NamedInvocation(
+                holochain_types::fixt::CellIdFixturator::new(::fixt::Unpredictable)
+                    .next()
+                    .unwrap(),
+                TestWasm::Foo.into(),
+                "post".into(),
+                ExternIO::encode(ChannelPost {
+                    channel: "general".into(),
+                    content: "hello".into(),
+                })
+                .unwrap(),
+            ))
+            .next()
+            .unwrap();
+        // The call is made by an agent other than the chain author
+        invocation.provenance = fixt!(AgentPubKey);
+        let function: GrantedFunction = (
+            invocation.zome.zome_name().clone(),
+            invocation.fn_name.clone(),
+        );
+
+        // Grant anyone access to the function, always posting to the
+        // announcements channel
+        #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
+        struct Channel {
+            channel: String,
+        }
+        let mut curry_payloads = CurryPayloads::default();
+        curry_payloads.0.insert(
+            function.clone(),
+            SerializedBytes::try_from(Channel {
+                channel: "announcements".into(),
+            })
+            .unwrap(),
+        );
+        let grant = ZomeCallCapGrant::new(
+            "post".into(),
+            ().into(),
+            vec![function].into_iter().collect(),
+            curry_payloads,
+            CapGrantLimits::default(),
+        );
+        commit_grant(&env, grant).await;
+
+        let workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+        let mut ribosome = MockRibosomeT::new();
+        let dna_def = fixt!(DnaFile).dna().clone();
+        ribosome.expect_dna_def().return_const(dna_def);
+        // Respond with the payload the zome function receives
+        ribosome
+            .expect_call_zome_function()
+            .returning(|_, invocation| Ok(ZomeCallResponse::Ok(invocation.payload)));
+
+        let result = run_call_zome(env.clone(), workspace, ribosome, invocation)
+            .await
+            .unwrap()
+            .unwrap();
+        let received: ChannelPost = match result {
+            ZomeCallResponse::Ok(payload) => payload.decode().unwrap(),
+            r => panic!("unexpected response: {:?}", r),
+        };
+        assert_eq!(
+            received,
+            ChannelPost {
+                channel: "announcements".into(),
+                content: "hello".into(),
+            }
+        );
+    }
+
+    /// A call which fails doesn't use up its grant
+    #[tokio::test(threaded_scheduler)]
+    async fn failed_call_does_not_use_grant() {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let mut invocation =
+            crate::fixt::ZomeCallInvocationFixturator::new(crate::fixt::NamedInvocation(
+                holochain_types::fixt::CellIdFixturator::new(::fixt::Unpredictable)
+                    .next()
+                    .unwrap(),
+                TestWasm::Foo.into(),
+                "fun_times".into(),
+                ExternIO::encode(Payload { a: 1 }).unwrap(),
+            ))
+            .next()
+            .unwrap();
+        invocation.provenance = fixt!(AgentPubKey);
+        let function: GrantedFunction = (
+            invocation.zome.zome_name().clone(),
+            invocation.fn_name.clone(),
+        );
+        let grant = ZomeCallCapGrant::new(
+            "once".into(),
+            ().into(),
+            vec![function].into_iter().collect(),
+            CurryPayloads::default(),
+            CapGrantLimits::new(None, Some(1)),
+        );
+        commit_grant(&env, grant).await;
+
+        let call = |fail: bool| {
+            let workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+            let mut ribosome = MockRibosomeT::new();
+            let dna_def = fixt!(DnaFile).dna().clone();
+            ribosome.expect_dna_def().return_const(dna_def);
+            ribosome
+                .expect_call_zome_function()
+                .returning(move |_, invocation| {
+                    if fail {
+                        Err(RibosomeError::ZomeFnNotExists(
+                            invocation.zome.zome_name().clone(),
+                            invocation.fn_name,
+                        ))
+                    } else {
+                        Ok(ZomeCallResponse::Ok(invocation.payload))
+                    }
+                });
+            run_call_zome(env.clone(), workspace, ribosome, invocation.clone())
+        };
+
+        // The failed call gives its use back so the grant still allows one call
+        assert!(call(true).await.unwrap().is_err());
+        assert_matches!(call(false).await.unwrap(), Ok(ZomeCallResponse::Ok(_)));
+        assert_matches!(
+            call(false).await.unwrap(),
+            Ok(ZomeCallResponse::Unauthorized(..))
+        );
+    }
+
+    /// Commit genesis and a grant to the source chain of the cell
+    async fn commit_grant(env: &EnvironmentWrite, grant: ZomeCallCapGrant) {
+        let mut workspace = CallZomeWorkspace::new(env.clone().into()).unwrap();
+        fake_genesis(&mut workspace.source_chain).await.unwrap();
+        let (entry, entry_hash) =
+            EntryHashed::from_content_sync(Entry::CapGrant(grant)).into_inner();
+        let header_builder = builder::Create {
+            entry_type: EntryType::CapGrant,
+            entry_hash,
+        };
+        workspace
+            .source_chain
+            .put(header_builder, Some(entry))
+            .await
+            .unwrap();
+        env.guard()
+            .with_commit(|mut writer| workspace.flush_to_txn(&mut writer))
+            .unwrap();
+    }
+
     async fn run_call_zome<'env, Ribosome: RibosomeT + Send + Sync + 'static>(
+        env: EnvironmentWrite,
         workspace: CallZomeWorkspace,
         ribosome: Ribosome,
         invocation: ZomeCallInvocation,
@@ -379,7 +753,7 @@ pub mod tests {
             conductor_api,
             is_root_zome_call: true,
         };
-        call_zome_workflow_inner(workspace.into(), network, keystore, args).await
+        call_zome_workflow_inner(workspace.into(), network, keystore, env, args).await
     }
 
     // 1.  Check if there is a Capability token secret in the parameters.
@@ -408,7 +782,7 @@ pub mod tests {
             .next()
             .unwrap();
         invocation.cap = todo!("Make secret cap token");
-        let error = run_call_zome(workspace, ribosome, invocation)
+        let error = run_call_zome(env.clone(), workspace, ribosome, invocation)
             .await
             .unwrap_err();
         assert_matches!(error, WorkflowError::CapabilityMissing);
@@ -488,7 +862,7 @@ pub mod tests {
             .returning(|_entry_hash| Ok(()));
         */
 
-        let _result = run_call_zome(workspace, ribosome, invocation)
+        let _result = run_call_zome(env.clone(), workspace, ribosome, invocation)
             .await
             .unwrap();
     }
@@ -517,7 +891,7 @@ pub mod tests {
         // TODO: B-01093: Mock the app validation and check it's called
         // TODO: B-01093: How can I pass a app validation into this?
         // These are just static calls
-        let _result = run_call_zome(workspace, ribosome, invocation)
+        let _result = run_call_zome(env.clone(), workspace, ribosome, invocation)
             .await
             .unwrap();
     }
@@ -548,7 +922,7 @@ pub mod tests {
             ))
             .next()
             .unwrap();
-        let _result = run_call_zome(workspace, ribosome, invocation)
+        let _result = run_call_zome(env.clone(), workspace, ribosome, invocation)
             .await
             .unwrap();
         // TODO: Check the workspace has changes
@@ -4,6 +4,7 @@
 use super::app_validation_workflow::AppValidationError;
 use super::produce_dht_ops_workflow::dht_op_light::error::DhtOpConvertError;
 use crate::conductor::api::error::ConductorApiError;
+use crate::conductor::entry_def_store::error::EntryDefStoreError;
 use crate::conductor::CellError;
 use crate::core::queue_consumer::QueueTriggerClosedError;
 use crate::core::ribosome::error::RibosomeError;
@@ -24,6 +25,9 @@ pub enum WorkflowError {
     #[error("Agent is invalid: {0:?}")]
     AgentInvalid(AgentPubKey),
 
+    #[error("The genesis self check of zome {0} failed: {1}")]
+    GenesisSelfCheckFailed(ZomeName, String),
+
     #[error("Conductor API error: {0}")]
     ConductorApi(#[from] Box<ConductorApiError>),
 
@@ -68,6 +72,9 @@ pub enum WorkflowError {
 
     #[error(transparent)]
     SysValidationError(#[from] SysValidationError),
+
+    #[error(transparent)]
+    EntryDefStoreError(#[from] EntryDefStoreError),
 }
 
 /// Internal type to handle running workflows
@@ -11,6 +11,10 @@ use super::error::WorkflowError;
 use super::error::WorkflowResult;
 use crate::conductor::api::CellConductorApiT;
 use crate::core::queue_consumer::OneshotWriter;
+use crate::core::ribosome::guest_callback::genesis_self_check::GenesisSelfCheckHostAccess;
+use crate::core::ribosome::guest_callback::genesis_self_check::GenesisSelfCheckInvocation;
+use crate::core::ribosome::guest_callback::genesis_self_check::GenesisSelfCheckResult;
+use crate::core::ribosome::RibosomeT;
 use derive_more::Constructor;
 use holochain_lmdb::prelude::*;
 use holochain_state::source_chain::SourceChainBuf;
@@ -21,18 +25,21 @@ use tracing::*;
 
 /// The struct which implements the genesis Workflow
 #[derive(Constructor, Debug)]
-pub struct GenesisWorkflowArgs {
+pub struct GenesisWorkflowArgs<Ribosome: RibosomeT> {
     dna_file: DnaFile,
     agent_pubkey: AgentPubKey,
     membrane_proof: Option<SerializedBytes>,
+    ribosome: Ribosome,
+    /// The cell belongs to the DPKI app, so DPKI can't be asked about its agent
+    is_dpki_cell: bool,
 }
 
 #[instrument(skip(workspace, writer, api))]
-pub async fn genesis_workflow<'env, Api: CellConductorApiT>(
+pub async fn genesis_workflow<'env, Api: CellConductorApiT, Ribosome: RibosomeT>(
     mut workspace: GenesisWorkspace,
     writer: OneshotWriter,
     api: Api,
-    args: GenesisWorkflowArgs,
+    args: GenesisWorkflowArgs<Ribosome>,
 ) -> WorkflowResult<()> {
     genesis_workflow_inner(&mut workspace, args, api).await?;
 
@@ -44,27 +51,38 @@ pub async fn genesis_workflow<'env, Api: CellConductorApiT>(
     Ok(())
 }
 
-async fn genesis_workflow_inner<Api: CellConductorApiT>(
+async fn genesis_workflow_inner<Api: CellConductorApiT, Ribosome: RibosomeT>(
     workspace: &mut GenesisWorkspace,
-    args: GenesisWorkflowArgs,
+    args: GenesisWorkflowArgs<Ribosome>,
     api: Api,
 ) -> WorkflowResult<()> {
     let GenesisWorkflowArgs {
         dna_file,
         agent_pubkey,
         membrane_proof,
+        ribosome,
+        is_dpki_cell,
     } = args;
 
-    // TODO: this is a placeholder for a real DPKI request to show intent
-    if api
-        .dpki_request("is_agent_pubkey_valid".into(), agent_pubkey.to_string())
-        .await
-        .expect("TODO: actually implement this")
-        == "INVALID"
+    // Agents whose keys DPKI doesn't accept can't join
+    if !is_dpki_cell
+        && !api
+            .agent_key_state(&agent_pubkey)
+            .await
+            .map_err(Box::new)?
+            .is_valid()
     {
         return Err(WorkflowError::AgentInvalid(agent_pubkey.clone()));
     }
 
+    // The DNA gets a chance to turn the agent away before anything is committed
+    let invocation = GenesisSelfCheckInvocation::new(membrane_proof.clone(), agent_pubkey.clone());
+    if let GenesisSelfCheckResult::Invalid(zome_name, reason) =
+        ribosome.run_genesis_self_check(GenesisSelfCheckHostAccess, invocation)?
+    {
+        return Err(WorkflowError::GenesisSelfCheckFailed(zome_name, reason));
+    }
+
     workspace
         .source_chain
         .genesis(
@@ -104,6 +122,7 @@ pub mod tests {
     use super::*;
 
     use crate::conductor::api::MockCellConductorApi;
+    use crate::core::ribosome::MockRibosomeT;
     use crate::core::SourceChainResult;
     use fallible_iterator::FallibleIterator;
     use holochain_lmdb::test_utils::test_cell_env;
@@ -133,12 +152,18 @@ pub mod tests {
         {
             let workspace = GenesisWorkspace::new(arc.clone().into()).await?;
             let mut api = MockCellConductorApi::new();
-            api.expect_sync_dpki_request()
-                .returning(|_, _| Ok("mocked dpki request response".to_string()));
+            api.expect_sync_agent_key_state()
+                .returning(|_| Ok(KeyState::Valid));
+            let mut ribosome = MockRibosomeT::new();
+            ribosome
+                .expect_run_genesis_self_check()
+                .returning(|_, _| Ok(GenesisSelfCheckResult::Valid));
             let args = GenesisWorkflowArgs {
                 dna_file: dna.clone(),
                 agent_pubkey: agent_pubkey.clone(),
                 membrane_proof: None,
+                ribosome,
+                is_dpki_cell: false,
             };
             let _: () = genesis_workflow(workspace, arc.clone().into(), api, args).await?;
         }
@@ -165,6 +190,97 @@ pub mod tests {
 
         Ok(())
     }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn genesis_rejects_revoked_agent() {
+        observability::test_run().ok();
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let workspace = GenesisWorkspace::new(arc.clone().into()).await.unwrap();
+        let mut api = MockCellConductorApi::new();
+        api.expect_sync_agent_key_state()
+            .returning(|_| Ok(KeyState::Revoked(None)));
+        let args = GenesisWorkflowArgs {
+            dna_file: fake_dna_file("a"),
+            agent_pubkey: fake_agent_pubkey_1(),
+            membrane_proof: None,
+            ribosome: MockRibosomeT::new(),
+            is_dpki_cell: false,
+        };
+        assert_matches!(
+            genesis_workflow(workspace, arc.clone().into(), api, args).await,
+            Err(WorkflowError::AgentInvalid(_))
+        );
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn genesis_of_dpki_cell_skips_key_check() {
+        observability::test_run().ok();
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let workspace = GenesisWorkspace::new(arc.clone().into()).await.unwrap();
+        // DPKI isn't there to ask, the mock panics if it is asked anyway
+        let api = MockCellConductorApi::new();
+        let mut ribosome = MockRibosomeT::new();
+        ribosome
+            .expect_run_genesis_self_check()
+            .returning(|_, _| Ok(GenesisSelfCheckResult::Valid));
+        let args = GenesisWorkflowArgs {
+            dna_file: fake_dna_file("a"),
+            agent_pubkey: fake_agent_pubkey_1(),
+            membrane_proof: None,
+            ribosome,
+            is_dpki_cell: true,
+        };
+        genesis_workflow(workspace, arc.clone().into(), api, args)
+            .await
+            .unwrap();
+
+        let source_chain = SourceChainBuf::new(arc.clone().into()).unwrap();
+        assert!(source_chain.chain_head().is_some());
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn genesis_rejected_by_self_check() {
+        observability::test_run().ok();
+        let test_env = test_cell_env();
+        let arc = test_env.env();
+        let workspace = GenesisWorkspace::new(arc.clone().into()).await.unwrap();
+        let mut api = MockCellConductorApi::new();
+        api.expect_sync_agent_key_state()
+            .returning(|_| Ok(KeyState::Valid));
+        let membrane_proof = SerializedBytes::try_from(()).unwrap();
+        let expected_proof = membrane_proof.clone();
+        let mut ribosome = MockRibosomeT::new();
+        ribosome
+            .expect_run_genesis_self_check()
+            .returning(move |_, invocation| {
+                assert_eq!(invocation.payload.agent_key, fake_agent_pubkey_1());
+                assert_eq!(
+                    invocation.payload.membrane_proof,
+                    Some(expected_proof.clone())
+                );
+                Ok(GenesisSelfCheckResult::Invalid(
+                    "test".into(),
+                    "bad membrane proof".into(),
+                ))
+            });
+        let args = GenesisWorkflowArgs {
+            dna_file: fake_dna_file("a"),
+            agent_pubkey: fake_agent_pubkey_1(),
+            membrane_proof: Some(membrane_proof),
+            ribosome,
+            is_dpki_cell: false,
+        };
+        assert_matches!(
+            genesis_workflow(workspace, arc.clone().into(), api, args).await,
+            Err(WorkflowError::GenesisSelfCheckFailed(_, reason)) if reason == "bad membrane proof"
+        );
+
+        // Nothing was committed
+        let source_chain = SourceChainBuf::new(arc.clone().into()).unwrap();
+        assert!(source_chain.chain_head().is_none());
+    }
 }
 
 /* TODO: make doc-able
@@ -197,7 +313,7 @@ Data X (data & structure) from Store Y:
 
 Functions / Workflows:
 
-- check that agent key is valid [MOCKED dpki] (via real dpki [LEAPFROG])
+- check that agent key is valid with dpki
 
 - retrieve DNA from file path [in the future from HCHC]
 
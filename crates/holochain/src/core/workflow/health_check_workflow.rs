@@ -0,0 +1,348 @@
+//! # Health Check Workflow
+//!
+//! Measures how well the ops this cell has authored are held on the DHT.
+//! Each published op is covered once it has as many valid validation receipts
+//! as the [RequiredValidations] of its entry def, or the default for ops
+//! which are not about an app entry.
+//!
+//! Ops which are not covered are handed back to the publish workflow,
+//! which publishes them again. Each op is handed back at most
+//! [MAX_REPUBLISHES] times, waiting twice as long as the last time before
+//! each one, so ops whose holders never send receipts don't flood the
+//! network.
+
+use super::error::WorkflowResult;
+use crate::conductor::api::CellConductorApiT;
+use crate::conductor::entry_def_store::get_entry_def_from_ids;
+use crate::core::queue_consumer::OneshotWriter;
+use crate::core::queue_consumer::TriggerSender;
+use fallible_iterator::FallibleIterator;
+use holo_hash::*;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::AUTHORED_DHT_OPS;
+use holochain_lmdb::fresh_reader;
+use holochain_lmdb::prelude::*;
+use holochain_lmdb::transaction::Writer;
+use holochain_state::prelude::*;
+use holochain_types::prelude::*;
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::*;
+
+/// How many times an uncovered op is handed back to the publish workflow
+pub const MAX_REPUBLISHES: u32 = 5;
+
+/// How long after its last publish an uncovered op is first handed back.
+/// The wait doubles with each republish.
+pub const REPUBLISH_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// Database buffers required for checking the health of a cell
+pub struct HealthCheckWorkspace {
+    /// Database of authored DhtOps, with data about prior publishing
+    authored_dht_ops: AuthoredDhtOpsStore,
+    /// The validation receipts sent back by the holders of our ops
+    validation_receipts: ValidationReceiptsBuf,
+    /// Element store for looking up the headers of the ops
+    elements: ElementBuf<AuthoredPrefix>,
+}
+
+/// A published op which doesn't have enough valid receipts
+struct UncoveredOp {
+    op_hash: DhtOpHash,
+    value: AuthoredDhtOpsValue,
+}
+
+#[instrument(skip(workspace, writer, conductor_api, trigger_publish))]
+pub async fn health_check_workflow(
+    mut workspace: HealthCheckWorkspace,
+    writer: OneshotWriter,
+    conductor_api: &impl CellConductorApiT,
+    mut trigger_publish: TriggerSender,
+) -> WorkflowResult<CellHealth> {
+    let (health, uncovered) = health_check_workflow_inner(&workspace, conductor_api).await?;
+
+    let now = timestamp::now();
+    let mut republish = false;
+    for UncoveredOp { op_hash, mut value } in uncovered {
+        if !republish_due(&value, now) {
+            continue;
+        }
+        value.republish = true;
+        value.republish_count += 1;
+        workspace.authored_dht_ops.put(op_hash, value)?;
+        republish = true;
+    }
+
+    // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
+
+    // commit the workspace
+    writer.with_writer(|writer| Ok(workspace.flush_to_txn(writer)?))?;
+
+    if republish {
+        trigger_publish.trigger();
+    }
+
+    info!(
+        cell_id = ?conductor_api.cell_id(),
+        published_ops = health.published_ops,
+        covered_ops = health.covered_ops,
+        missing_receipts = health.missing_receipts,
+        "cell health checked"
+    );
+
+    Ok(health)
+}
+
+/// Has an uncovered op waited long enough since it was last published to be
+/// published again
+fn republish_due(value: &AuthoredDhtOpsValue, now: Timestamp) -> bool {
+    if value.republish_count >= MAX_REPUBLISHES {
+        return false;
+    }
+    let backoff = REPUBLISH_BACKOFF * 2u32.pow(value.republish_count);
+    // chrono cannot create const durations
+    let backoff = chrono::Duration::from_std(backoff).expect("backoff must be positive");
+    value
+        .last_publish_time
+        .and_then(|last| now.checked_difference_signed(&last))
+        .map(|since| since >= backoff)
+        .unwrap_or(false)
+}
+
+/// Measure the health of a cell without repairing it
+pub async fn check_cell_health(
+    workspace: &HealthCheckWorkspace,
+    conductor_api: &impl CellConductorApiT,
+) -> WorkflowResult<CellHealth> {
+    Ok(health_check_workflow_inner(workspace, conductor_api)
+        .await?
+        .0)
+}
+
+async fn health_check_workflow_inner(
+    workspace: &HealthCheckWorkspace,
+    conductor_api: &impl CellConductorApiT,
+) -> WorkflowResult<(CellHealth, Vec<UncoveredOp>)> {
+    let mut health = CellHealth::new(timestamp::now());
+
+    // one of many ways to access the env
+    let env = workspace.elements.headers().env().clone();
+
+    // Only ops which have been published can have receipts
+    let published = fresh_reader!(env, |r| {
+        let published = workspace
+            .authored_dht_ops
+            .iter(&r)?
+            .filter(|(_, value)| Ok(value.last_publish_time.is_some()))
+            .map(|(k, value)| Ok((DhtOpHash::from_raw_39_panicky(k.to_vec()), value)))
+            .collect::<Vec<_>>()?;
+        published
+            .into_iter()
+            .map(|(op_hash, value)| {
+                let receipts = workspace.validation_receipts.count_valid(&r, &op_hash)? as u32;
+                DatabaseResult::Ok((op_hash, value, receipts))
+            })
+            .collect::<DatabaseResult<Vec<_>>>()
+    })?;
+
+    let dna_file = conductor_api.get_this_dna().await.map_err(Box::new)?;
+    let mut required_by_type = HashMap::new();
+    let mut uncovered = Vec::new();
+
+    for (op_hash, value, receipts) in published {
+        let header = workspace
+            .elements
+            .get_header(value.op.header_hash())?
+            .map(|header| header.header().clone());
+        let app_entry_type = match header.as_ref().and_then(|h| h.entry_type()) {
+            Some(EntryType::App(app_entry_type)) => Some(app_entry_type.clone()),
+            _ => None,
+        };
+
+        // Private entries are never published
+        if let (DhtOpLight::StoreEntry(..), Some(app_entry_type)) = (&value.op, &app_entry_type) {
+            if !app_entry_type.visibility().is_public() {
+                continue;
+            }
+        }
+
+        let required = match app_entry_type {
+            Some(app_entry_type) => match required_by_type.get(&app_entry_type) {
+                Some(required) => *required,
+                None => {
+                    let required = get_entry_def_from_ids(
+                        app_entry_type.zome_id(),
+                        app_entry_type.id(),
+                        dna_file.dna(),
+                        conductor_api,
+                    )
+                    .await?
+                    .map(|entry_def| entry_def.required_validations)
+                    .unwrap_or_default();
+                    required_by_type.insert(app_entry_type, required);
+                    required
+                }
+            },
+            None => RequiredValidations::default(),
+        };
+        let required = u8::from(required) as u32;
+
+        health.published_ops += 1;
+        if receipts >= required {
+            health.covered_ops += 1;
+        } else {
+            health.missing_receipts += (required - receipts) as usize;
+            uncovered.push(UncoveredOp { op_hash, value });
+        }
+    }
+
+    Ok((health, uncovered))
+}
+
+impl Workspace for HealthCheckWorkspace {
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> WorkspaceResult<()> {
+        self.authored_dht_ops.flush_to_txn_ref(writer)?;
+        Ok(())
+    }
+}
+
+impl HealthCheckWorkspace {
+    pub fn new(env: EnvironmentRead) -> WorkspaceResult<Self> {
+        let db = env.get_db(&*AUTHORED_DHT_OPS)?;
+        let authored_dht_ops = KvBufFresh::new(env.clone(), db);
+        let validation_receipts = ValidationReceiptsBuf::new(&env)?;
+        let elements = ElementBuf::authored(env, true)?;
+        Ok(Self {
+            authored_dht_ops,
+            validation_receipts,
+            elements,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conductor::api::MockCellConductorApi;
+    use crate::core::workflow::publish_dht_ops_workflow::DEFAULT_RECEIPT_BUNDLE_SIZE;
+    use crate::fixt::CreateLinkFixturator;
+    use ::fixt::prelude::*;
+    use holochain_keystore::KeystoreSenderExt;
+    use holochain_lmdb::test_utils::test_cell_env;
+    use holochain_lmdb::test_utils::test_keystore;
+    use holochain_state::validation_receipts_db::ValidationResult;
+    use holochain_types::test_utils::fake_dna_file;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn uncovered_ops_are_republished() {
+        observability::test_run().ok();
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let keystore = test_keystore();
+        let required = u8::from(RequiredValidations::default()) as u32;
+
+        // Four published link ops, the first has all its receipts, the
+        // second is due to be published again, the third was published
+        // too recently and the last has been republished too often.
+        let due = timestamp::now().checked_sub(&REPUBLISH_BACKOFF).unwrap();
+        let published = vec![
+            (due, 0),
+            (due, 0),
+            (timestamp::now(), 0),
+            (due, MAX_REPUBLISHES),
+        ];
+        let mut op_hashes = Vec::new();
+        {
+            let mut workspace = HealthCheckWorkspace::new(env.clone().into()).unwrap();
+            for (last_publish_time, republish_count) in published {
+                let sig = fixt!(Signature);
+                let link_add = fixt!(CreateLink);
+                let op = DhtOp::RegisterAddLink(sig.clone(), link_add.clone());
+                let op_hash = DhtOpHashed::from_content_sync(op).into_hash();
+                let header_hash = HeaderHashed::from_content_sync(link_add.clone().into());
+                let op_light = DhtOpLight::RegisterAddLink(
+                    header_hash.as_hash().clone(),
+                    link_add.base_address.into(),
+                );
+                let mut value = AuthoredDhtOpsValue::from_light(op_light);
+                value.receipt_count = DEFAULT_RECEIPT_BUNDLE_SIZE;
+                value.last_publish_time = Some(last_publish_time);
+                value.republish_count = republish_count;
+                workspace
+                    .authored_dht_ops
+                    .put(op_hash.clone(), value)
+                    .unwrap();
+                workspace
+                    .elements
+                    .put(SignedHeaderHashed::with_presigned(header_hash, sig), None)
+                    .unwrap();
+                op_hashes.push(op_hash);
+            }
+            for _ in 0..required {
+                let validator = keystore
+                    .generate_sign_keypair_from_pure_entropy()
+                    .await
+                    .unwrap();
+                let receipt = ValidationReceipt {
+                    dht_op_hash: op_hashes[0].clone(),
+                    validation_result: ValidationResult::Valid,
+                    validator,
+                };
+                workspace
+                    .validation_receipts
+                    .add_if_unique(receipt.sign(&keystore).await.unwrap())
+                    .unwrap();
+            }
+            env.guard()
+                .with_commit::<DatabaseError, _, _>(|writer| {
+                    workspace.authored_dht_ops.flush_to_txn(writer)?;
+                    workspace.elements.flush_to_txn(writer)?;
+                    workspace.validation_receipts.flush_to_txn(writer)?;
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        let dna_file = fake_dna_file("health");
+        let cell_id = fixt!(CellId);
+        let mut conductor_api = MockCellConductorApi::new();
+        conductor_api
+            .expect_sync_get_this_dna()
+            .returning(move || Ok(dna_file.clone()));
+        conductor_api.expect_cell_id().return_const(cell_id);
+
+        let (trigger_publish, mut publish_rx) = TriggerSender::new();
+        let workspace = HealthCheckWorkspace::new(env.clone().into()).unwrap();
+        let health = health_check_workflow(
+            workspace,
+            env.clone().into(),
+            &conductor_api,
+            trigger_publish,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(health.published_ops, 4);
+        assert_eq!(health.covered_ops, 1);
+        assert_eq!(health.missing_receipts, 3 * required as usize);
+        assert!(!health.is_healthy());
+
+        // Only the due op is handed back to the publish workflow,
+        // without touching its receipt count
+        publish_rx.listen().await.unwrap();
+        let workspace = HealthCheckWorkspace::new(env.clone().into()).unwrap();
+        let counts = |op_hash: &DhtOpHash| {
+            let value = workspace.authored_dht_ops.get(op_hash).unwrap().unwrap();
+            (value.republish, value.republish_count)
+        };
+        assert_eq!(counts(&op_hashes[0]), (false, 0));
+        assert_eq!(counts(&op_hashes[1]), (true, 1));
+        assert_eq!(counts(&op_hashes[2]), (false, 0));
+        assert_eq!(counts(&op_hashes[3]), (false, MAX_REPUBLISHES));
+        for op_hash in &op_hashes {
+            let value = workspace.authored_dht_ops.get(op_hash).unwrap().unwrap();
+            assert_eq!(value.receipt_count, DEFAULT_RECEIPT_BUNDLE_SIZE);
+        }
+    }
+}
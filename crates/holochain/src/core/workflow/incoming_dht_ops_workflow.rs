@@ -33,6 +33,7 @@ pub async fn incoming_dht_ops_workflow(
     mut sys_validation_trigger: TriggerSender,
     ops: Vec<(holo_hash::DhtOpHash, holochain_types::dht_op::DhtOp)>,
     from_agent: Option<AgentPubKey>,
+    request_validation_receipt: bool,
 ) -> WorkflowResult<()> {
     // set up our workspace
     let mut workspace = IncomingDhtOpsWorkspace::new(state_env.clone().into())?;
@@ -42,7 +43,12 @@ pub async fn incoming_dht_ops_workflow(
         if !workspace.op_exists(&hash)? {
             tracing::debug!(?hash, ?op);
             if should_keep(&op).await? {
-                workspace.add_to_pending(hash, op, from_agent.clone())?;
+                workspace.add_to_pending(
+                    hash,
+                    op,
+                    from_agent.clone(),
+                    request_validation_receipt,
+                )?;
             } else {
                 tracing::warn!(
                     msg = "Dropping op because it failed counterfeit checks",
@@ -121,6 +127,7 @@ impl IncomingDhtOpsWorkspace {
         hash: DhtOpHash,
         op: DhtOp,
         from_agent: Option<AgentPubKey>,
+        send_receipt: bool,
     ) -> DhtOpConvertResult<()> {
         let basis = op.dht_basis();
         let op_light = op.to_light();
@@ -151,6 +158,7 @@ impl IncomingDhtOpsWorkspace {
             last_try: None,
             num_tries: 0,
             from_agent,
+            send_receipt,
         };
         self.validation_limbo.put(hash, vlv)?;
         Ok(())
@@ -2,6 +2,8 @@
 
 use super::incoming_dht_ops_workflow::IncomingDhtOpsWorkspace;
 use super::*;
+use crate::conductor::interface::SignalBroadcaster;
+use crate::conductor::interface::SubscribedBases;
 use crate::core::queue_consumer::OneshotWriter;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
@@ -43,11 +45,13 @@ mod disintegrate;
 #[cfg(feature = "test_utils")]
 mod tests;
 
-#[instrument(skip(workspace, writer, trigger_sys))]
+#[instrument(skip(workspace, writer, trigger_sys, signal_tx))]
 pub async fn integrate_dht_ops_workflow(
     mut workspace: IntegrateDhtOpsWorkspace,
     writer: OneshotWriter,
     trigger_sys: &mut TriggerSender,
+    cell_id: &CellId,
+    mut signal_tx: SignalBroadcaster,
 ) -> WorkflowResult<WorkComplete> {
     // one of many possible ways to access the env
     let env = workspace.elements.headers().env().clone();
@@ -75,6 +79,7 @@ pub async fn integrate_dht_ops_workflow(
     }
 
     let mut total_integrated: usize = 0;
+    let mut signals = Vec::new();
 
     // Try to process the queue over and over again, until we either exhaust
     // the queue, or we can no longer integrate anything in the queue.
@@ -91,10 +96,19 @@ pub async fn integrate_dht_ops_workflow(
                 value,
                 order,
             } = so;
+            let op_signals = integration_signals(
+                cell_id,
+                &hash,
+                &op,
+                value.validation_status,
+                signal_tx.subscribed_bases(),
+            );
             // Check validation status and put in correct dbs
-            let outcome = integrate_single_dht_op(value.clone(), op, &mut workspace).await?;
+            let outcome =
+                integrate_single_dht_op(value.clone(), op, &mut workspace, cell_id).await?;
             match outcome {
                 Outcome::Integrated(integrated) => {
+                    signals.extend(op_signals);
                     // TODO We could create a prefix for the integrated ops db
                     // and separate rejected ops from valid ops.
                     // Currently you need to check the IntegratedDhtOpsValue for
@@ -144,43 +158,105 @@ pub async fn integrate_dht_ops_workflow(
         trigger_sys.trigger();
     }
 
+    // Only signal once the integrated ops are committed
+    for signal in signals {
+        signal_tx.send_system(signal);
+    }
+
     Ok(result)
 }
 
+/// The system signals for an op once it is integrated into this cell.
+/// An integrated op is only signalled if its basis is subscribed to.
+fn integration_signals(
+    cell_id: &CellId,
+    op_hash: &DhtOpHash,
+    op: &DhtOp,
+    validation_status: ValidationStatus,
+    subscribed_bases: &SubscribedBases,
+) -> Vec<SystemSignal> {
+    match validation_status {
+        ValidationStatus::Valid => {
+            let mut signals = Vec::new();
+            let basis = op.dht_basis();
+            if subscribed_bases.contains(&basis) {
+                signals.push(SystemSignal::OpIntegrated {
+                    cell_id: cell_id.clone(),
+                    basis,
+                    op_hash: op_hash.clone(),
+                });
+            }
+            if let DhtOp::RegisterAddLink(_, link_add) = op {
+                signals.push(SystemSignal::LinkAdded {
+                    cell_id: cell_id.clone(),
+                    base: link_add.base_address.clone(),
+                    target: link_add.target_address.clone(),
+                    tag: link_add.tag.clone(),
+                    create_link_hash: HeaderHash::with_data_sync(&Header::CreateLink(
+                        link_add.clone(),
+                    )),
+                });
+            }
+            signals
+        }
+        ValidationStatus::Rejected => {
+            let header = op.header();
+            if header.author() == cell_id.agent_pubkey() {
+                vec![SystemSignal::AuthoredOpRejected {
+                    cell_id: cell_id.clone(),
+                    op_hash: op_hash.clone(),
+                    header_hash: HeaderHash::with_data_sync(&header),
+                }]
+            } else {
+                Vec::new()
+            }
+        }
+        ValidationStatus::Abandoned => Vec::new(),
+    }
+}
+
 /// Integrate a single DhtOp to the stores based on the
 /// validation status.
 ///
 /// Check for dependencies in any of our other stores.
-#[instrument(skip(iv, workspace))]
+#[instrument(skip(iv, workspace, cell_id))]
 async fn integrate_single_dht_op(
     iv: IntegrationLimboValue,
     op: DhtOp,
     workspace: &mut IntegrateDhtOpsWorkspace,
+    cell_id: &CellId,
 ) -> WorkflowResult<Outcome> {
+    if let ValidationStatus::Abandoned = iv.validation_status {
+        // Throwing away abandoned ops.
+        // They are usually abandoned because their dependencies
+        // never arrived, so don't wait for them here either.
+        // TODO: keep abandoned ops but remove the entries
+        // and put them in a AbandonedPrefix db
+        let integrated = IntegratedDhtOpsValue {
+            validation_status: iv.validation_status,
+            op: iv.op,
+            when_integrated: timestamp::now(),
+            header_time: Some(op.header().timestamp()),
+        };
+        return Ok(Outcome::Integrated(integrated));
+    }
     if op_dependencies_held(&op, workspace).await? {
         match iv.validation_status {
-            ValidationStatus::Valid => Ok(integrate_data_and_meta(
-                iv,
-                op,
-                &mut workspace.elements,
-                &mut workspace.meta,
-            )?),
+            ValidationStatus::Valid => {
+                warrant_chain_fork(&op, workspace, cell_id).await?;
+                Ok(integrate_data_and_meta(
+                    iv,
+                    op,
+                    &mut workspace.elements,
+                    &mut workspace.meta,
+                )?)
+            }
             ValidationStatus::Rejected => {
                 update_activity_status(&op, &mut workspace.meta)?;
                 update_validation_status(&op, &mut workspace.meta)?;
                 Ok(integrate_data(iv, op, &mut workspace.element_rejected)?)
             }
-            ValidationStatus::Abandoned => {
-                // Throwing away abandoned ops
-                // TODO: keep abandoned ops but remove the entries
-                // and put them in a AbandonedPrefix db
-                let integrated = IntegratedDhtOpsValue {
-                    validation_status: iv.validation_status,
-                    op: iv.op,
-                    when_integrated: timestamp::now(),
-                };
-                Ok(Outcome::Integrated(integrated))
-            }
+            ValidationStatus::Abandoned => unreachable!("Abandoned ops are integrated above"),
         }
     } else {
         debug!("deferring");
@@ -194,12 +270,14 @@ fn integrate_data_and_meta<P: PrefixType>(
     element_store: &mut ElementBuf<P>,
     meta_store: &mut MetadataBuf<P>,
 ) -> DhtOpConvertResult<Outcome> {
+    let header_time = Some(op.header().timestamp());
     integrate_single_data(op, element_store)?;
     integrate_single_metadata(iv.op.clone(), element_store, meta_store)?;
     let integrated = IntegratedDhtOpsValue {
         validation_status: iv.validation_status,
         op: iv.op,
         when_integrated: timestamp::now(),
+        header_time,
     };
     debug!("integrating");
     Ok(Outcome::Integrated(integrated))
@@ -211,11 +289,13 @@ fn integrate_data<P: PrefixType>(
     op: DhtOp,
     element_store: &mut ElementBuf<P>,
 ) -> DhtOpConvertResult<Outcome> {
+    let header_time = Some(op.header().timestamp());
     integrate_single_data(op, element_store)?;
     let integrated = IntegratedDhtOpsValue {
         validation_status: iv.validation_status,
         op: iv.op,
         when_integrated: timestamp::now(),
+        header_time,
     };
     debug!("integrating");
     Ok(Outcome::Integrated(integrated))
@@ -238,6 +318,50 @@ fn update_activity_status(
     Ok(())
 }
 
+/// Issue a warrant if this valid agent activity is a different header
+/// at the same position as one we already hold for the author.
+async fn warrant_chain_fork(
+    op: &DhtOp,
+    workspace: &mut IntegrateDhtOpsWorkspace,
+    cell_id: &CellId,
+) -> WorkflowResult<()> {
+    if let DhtOp::RegisterAgentActivity(signature, header) = op {
+        let hash = HeaderHash::with_data_sync(header);
+        let env = workspace.elements.headers().env().clone();
+        let others: Vec<HeaderHash> = fresh_reader!(env, |r| workspace
+            .meta
+            .get_activity(
+                &r,
+                ChainItemKey::AgentStatusSequence(
+                    header.author().clone(),
+                    ValidationStatus::Valid,
+                    header.header_seq(),
+                ),
+            )?
+            .map(|h| Ok(h.header_hash))
+            .filter(|h| Ok(*h != hash))
+            .collect())?;
+        for other in others {
+            if let Some(other) = workspace.elements.get_header(&other)? {
+                warn!(
+                    msg = "Chain fork detected during integration",
+                    author = ?header.author(),
+                    header_seq = header.header_seq(),
+                );
+                let proof = WarrantProof::chain_fork(
+                    other.into_inner().0,
+                    SignedHeader(header.clone(), signature.clone()),
+                );
+                workspace
+                    .warrants
+                    .issue(proof, cell_id.agent_pubkey().clone(), env.keystore())
+                    .await?;
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Rejected headers still need to be stored in the metadata vault so
 /// they can be served for a get details call.
 fn update_validation_status(
@@ -468,6 +592,8 @@ pub struct IntegrateDhtOpsWorkspace {
     pub meta_pending: MetadataBuf<PendingPrefix>,
     pub element_rejected: ElementBuf<RejectedPrefix>,
     pub meta_rejected: MetadataBuf<RejectedPrefix>,
+    /// Warrants issued against authors with forked chains
+    pub warrants: WarrantStore,
     /// Ops to disintegrate
     pub to_disintegrate_pending: Vec<DhtOpLight>,
     /// READ ONLY
@@ -491,6 +617,7 @@ impl Workspace for IntegrateDhtOpsWorkspace {
         self.meta_pending.flush_to_txn_ref(writer)?;
         self.element_rejected.flush_to_txn_ref(writer)?;
         self.meta_rejected.flush_to_txn_ref(writer)?;
+        self.warrants.flush_to_txn_ref(writer)?;
         Ok(())
     }
 }
@@ -513,7 +640,9 @@ impl IntegrateDhtOpsWorkspace {
         let meta_pending = MetadataBuf::pending(env.clone())?;
 
         let element_rejected = ElementBuf::rejected(env.clone())?;
-        let meta_rejected = MetadataBuf::rejected(env)?;
+        let meta_rejected = MetadataBuf::rejected(env.clone())?;
+
+        let warrants = WarrantStore::new(env)?;
 
         Ok(Self {
             integration_limbo,
@@ -524,6 +653,7 @@ impl IntegrateDhtOpsWorkspace {
             meta_pending,
             element_rejected,
             meta_rejected,
+            warrants,
             validation_limbo,
             to_disintegrate_pending: Vec::new(),
         })
@@ -3,6 +3,7 @@
 
 use super::*;
 
+use crate::conductor::interface::SignalBroadcaster;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsResult;
 use crate::core::ribosome::host_fn;
@@ -196,6 +197,7 @@ impl Db {
                         validation_status: ValidationStatus::Valid,
                         op: op.to_light(),
                         when_integrated: timestamp::now().into(),
+                        header_time: Some(op.header().timestamp()),
                     };
                     let mut r = workspace
                         .integrated_dht_ops
@@ -528,9 +530,17 @@ impl Db {
 }
 
 async fn call_workflow<'env>(env: EnvironmentWrite) {
+    call_workflow_with_signals(env, &fixt!(CellId), SignalBroadcaster::noop()).await
+}
+
+async fn call_workflow_with_signals<'env>(
+    env: EnvironmentWrite,
+    cell_id: &CellId,
+    signal_tx: SignalBroadcaster,
+) {
     let workspace = IntegrateDhtOpsWorkspace::new(env.clone().into()).unwrap();
     let (mut qt, _rx) = TriggerSender::new();
-    integrate_dht_ops_workflow(workspace, env.clone().into(), &mut qt)
+    integrate_dht_ops_workflow(workspace, env.clone().into(), &mut qt, cell_id, signal_tx)
         .await
         .unwrap();
 }
@@ -835,6 +845,85 @@ async fn test_ops_state() {
     }
 }
 
+#[tokio::test(threaded_scheduler)]
+async fn test_integration_signals() {
+    observability::test_run().ok();
+    let test_env = test_cell_env();
+    let env = test_env.env();
+    let td = TestData::new().await;
+    let cell_id = fixt!(CellId);
+    let (signal_tx, mut signal_rx) = tokio::sync::broadcast::channel(10);
+    let signal_tx = SignalBroadcaster::new(vec![signal_tx]);
+
+    // A valid link is signalled as added even when its base isn't subscribed to
+    let op = DhtOp::RegisterAddLink(td.signature.clone(), td.link_add.clone());
+    let op_hash = DhtOpHash::with_data_sync(&op);
+    let (pre_state, _, _) = register_add_link(td.clone());
+    Db::set(pre_state.clone(), env.clone()).await;
+    call_workflow_with_signals(env.clone(), &cell_id, signal_tx.clone()).await;
+
+    let link_added: Signal = SystemSignal::LinkAdded {
+        cell_id: cell_id.clone(),
+        base: td.link_add.base_address.clone(),
+        target: td.link_add.target_address.clone(),
+        tag: td.link_add.tag.clone(),
+        create_link_hash: HeaderHash::with_data_sync(&Header::CreateLink(td.link_add.clone())),
+    }
+    .into();
+    assert_eq!(signal_rx.recv().await.unwrap(), link_added);
+
+    // Once subscribed to it is also signalled as integrated
+    signal_tx.subscribed_bases().add(&[op.dht_basis()]);
+    clear_dbs(env.clone());
+    Db::set(pre_state, env.clone()).await;
+    call_workflow_with_signals(env.clone(), &cell_id, signal_tx.clone()).await;
+
+    let expected: Signal = SystemSignal::OpIntegrated {
+        cell_id: cell_id.clone(),
+        basis: op.dht_basis(),
+        op_hash,
+    }
+    .into();
+    assert_eq!(signal_rx.recv().await.unwrap(), expected);
+    assert_eq!(signal_rx.recv().await.unwrap(), link_added);
+
+    // A rejected op is only signalled to the cell of its author
+    let op = DhtOp::RegisterAgentActivity(td.signature.clone(), td.dna_header.clone());
+    let op_hash = DhtOpHash::with_data_sync(&op);
+    let author_cell_id = CellId::new(cell_id.dna_hash().clone(), td.dna_header.author().clone());
+    for (cell_id, expect_signal) in vec![(cell_id, false), (author_cell_id, true)] {
+        clear_dbs(env.clone());
+        Db::set(add_op_to_judged(vec![], &op), env.clone()).await;
+        let mut workspace = IntegrateDhtOpsWorkspace::new(env.clone().into()).unwrap();
+        let value = IntegrationLimboValue {
+            validation_status: ValidationStatus::Rejected,
+            op: op.to_light(),
+        };
+        workspace
+            .integration_limbo
+            .put(op_hash.clone(), value)
+            .unwrap();
+        env.guard()
+            .with_commit::<WorkspaceError, _, _>(|writer| {
+                workspace.flush_to_txn(writer)?;
+                Ok(())
+            })
+            .unwrap();
+        call_workflow_with_signals(env.clone(), &cell_id, signal_tx.clone()).await;
+
+        if expect_signal {
+            let expected: Signal = SystemSignal::AuthoredOpRejected {
+                cell_id: cell_id.clone(),
+                op_hash: op_hash.clone(),
+                header_hash: HeaderHash::with_data_sync(&td.dna_header),
+            }
+            .into();
+            assert_eq!(signal_rx.recv().await.unwrap(), expected);
+        }
+    }
+    assert!(signal_rx.try_recv().is_err());
+}
+
 /// Call the produce dht ops workflow
 async fn produce_dht_ops<'env>(env: EnvironmentWrite) {
     let (mut qt, _rx) = TriggerSender::new();
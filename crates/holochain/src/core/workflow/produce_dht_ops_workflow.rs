@@ -47,6 +47,8 @@ async fn produce_dht_ops_workflow_inner(
                 op: op.to_light(),
                 receipt_count: 0,
                 last_publish_time: None,
+                republish_count: 0,
+                republish: false,
             };
             workspace.authored_dht_ops.put(hash, value)?;
         }
@@ -70,7 +70,8 @@ pub async fn publish_dht_ops_workflow(
     Ok(WorkComplete::Complete)
 }
 
-/// Read the authored for ops with receipt count < R
+/// Read the authored for ops with receipt count < R, or which the health
+/// check has asked to be published again
 pub async fn publish_dht_ops_workflow_inner(
     workspace: &mut PublishDhtOpsWorkspace,
 ) -> WorkflowResult<HashMap<AnyDhtHash, Vec<(DhtOpHash, DhtOp)>>> {
@@ -89,7 +90,8 @@ pub async fn publish_dht_ops_workflow_inner(
         .authored()
         .iter(&r)?
         .filter_map(|(k, mut r)| {
-            Ok(if r.receipt_count < DEFAULT_RECEIPT_BUNDLE_SIZE {
+            let wants_publish = r.republish || r.receipt_count < DEFAULT_RECEIPT_BUNDLE_SIZE;
+            Ok(if wants_publish {
                 let needs_publish = r
                     .last_publish_time
                     .and_then(|last| now.checked_difference_signed(&last))
@@ -97,11 +99,16 @@ pub async fn publish_dht_ops_workflow_inner(
                     .unwrap_or(true);
                 if needs_publish {
                     r.last_publish_time = Some(now);
-                    // HACK: Incrementing the receipt count to prevent publishing
-                    // forever although without receipts this could lead to data loss
-                    // and relies on gossip for data integrity.
-                    // This should be removed when receipts are implemented.
-                    r.receipt_count += 1;
+                    if r.republish {
+                        // A republish is a single extra publish
+                        r.republish = false;
+                    } else {
+                        // HACK: Incrementing the receipt count to prevent publishing
+                        // forever although without receipts this could lead to data loss
+                        // and relies on gossip for data integrity.
+                        // This should be removed when receipts are implemented.
+                        r.receipt_count += 1;
+                    }
                     Some((DhtOpHash::from_raw_39_panicky(k.to_vec()), r))
                 } else {
                     None
@@ -443,6 +450,65 @@ mod tests {
         });
     }
 
+    /// An op which the health check hands back is published once more
+    /// without counting as a receipt
+    #[test]
+    fn test_republish_flag() {
+        crate::conductor::tokio_runtime().block_on(async {
+            observability::test_run().ok();
+
+            // Create test env
+            let test_env = test_cell_env();
+            let env = test_env.env();
+            let env_ref = env.guard();
+
+            // Setup
+            let (_network, _cell_network, _recv_task, _) = setup(env.clone(), 1, 1, false).await;
+
+            // Flag the ops which already have all their receipts
+            let op_hashes = {
+                let reader = env_ref.reader().unwrap();
+                let mut workspace = PublishDhtOpsWorkspace::new(env.clone().into()).unwrap();
+                let values = workspace
+                    .authored_dht_ops
+                    .iter(&reader)
+                    .unwrap()
+                    .map(|(k, mut v)| {
+                        v.receipt_count = DEFAULT_RECEIPT_BUNDLE_SIZE;
+                        v.republish = true;
+                        Ok((DhtOpHash::from_raw_39_panicky(k.to_vec()), v))
+                    })
+                    .collect::<Vec<_>>()
+                    .unwrap();
+                let op_hashes = values
+                    .iter()
+                    .map(|(hash, _)| hash.clone())
+                    .collect::<Vec<_>>();
+                for (hash, v) in values.into_iter() {
+                    workspace.authored_dht_ops.put(hash, v).unwrap();
+                }
+                env_ref
+                    .with_commit::<DatabaseError, _, _>(|writer| {
+                        workspace.authored_dht_ops.flush_to_txn(writer)?;
+                        Ok(())
+                    })
+                    .unwrap();
+                op_hashes
+            };
+
+            let mut workspace = PublishDhtOpsWorkspace::new(env.clone().into()).unwrap();
+            let to_publish = publish_dht_ops_workflow_inner(&mut workspace)
+                .await
+                .unwrap();
+            assert_eq!(to_publish.values().map(Vec::len).sum::<usize>(), 1);
+            for op_hash in &op_hashes {
+                let value = workspace.authored_dht_ops.get(op_hash).unwrap().unwrap();
+                assert!(!value.republish);
+                assert_eq!(value.receipt_count, DEFAULT_RECEIPT_BUNDLE_SIZE);
+            }
+        });
+    }
+
     /// There is a test to shows that DHTOps that were produced on private entries are not published.
     /// Some do get published
     /// Current private constraints:
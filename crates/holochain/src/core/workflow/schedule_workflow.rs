@@ -0,0 +1,173 @@
+//! Calls the zome functions that have been scheduled with the `schedule`
+//! host fn once they are due.
+//!
+//! Each scheduled function runs in its own workspace, as the author of the
+//! cell, and anything it commits is validated exactly like a zome call.
+//! A scheduled function is removed from the schedule once it has run,
+//! whether or not it succeeded. If it failed, its changes are discarded.
+
+use super::call_zome_workflow::validate_new_elements;
+use super::error::WorkflowResult;
+use super::CallZomeWorkspace;
+use super::CallZomeWorkspaceLock;
+use crate::conductor::api::CellConductorApiT;
+use crate::conductor::interface::SignalBroadcaster;
+use crate::core::queue_consumer::OneshotWriter;
+use crate::core::queue_consumer::TriggerSender;
+use crate::core::ribosome::error::RibosomeError;
+use crate::core::ribosome::guest_callback::schedule::ScheduleHostAccess;
+use crate::core::ribosome::guest_callback::schedule::ScheduleInvocation;
+use crate::core::ribosome::RibosomeT;
+use crate::core::ribosome::ZomeCallLimits;
+use holochain_keystore::KeystoreSender;
+use holochain_lmdb::fresh_reader;
+use holochain_lmdb::prelude::*;
+use holochain_p2p::HolochainP2pCell;
+use holochain_state::scheduled_fn_db::ScheduledFn;
+use holochain_state::scheduled_fn_db::ScheduledFnStore;
+use holochain_state::workspace::Workspace;
+use holochain_types::prelude::*;
+use tracing::*;
+
+pub struct ScheduleWorkflowArgs<Ribosome: RibosomeT + Send, C: CellConductorApiT> {
+    pub ribosome: Ribosome,
+    pub conductor_api: C,
+    pub signal_tx: SignalBroadcaster,
+}
+
+/// Run every scheduled function that is due and return the time the next
+/// one will be due, if any.
+#[instrument(skip(env, network, keystore, args, trigger_produce_dht_ops))]
+pub async fn schedule_workflow<Ribosome: RibosomeT + Send + 'static, C: CellConductorApiT>(
+    env: EnvironmentWrite,
+    network: HolochainP2pCell,
+    keystore: KeystoreSender,
+    args: ScheduleWorkflowArgs<Ribosome, C>,
+    mut trigger_produce_dht_ops: TriggerSender,
+) -> WorkflowResult<Option<Timestamp>> {
+    let ScheduleWorkflowArgs {
+        mut ribosome,
+        conductor_api,
+        signal_tx,
+    } = args;
+
+    let due = {
+        let store = ScheduledFnStore::new(env.clone().into())?;
+        fresh_reader!(env, |r| store.due(&r, timestamp::now()))?
+    };
+
+    let mut committed = false;
+    for (key, scheduled_fn) in due {
+        let workspace_lock =
+            CallZomeWorkspaceLock::new(CallZomeWorkspace::new(env.clone().into())?);
+        let chain_head_start_len = workspace_lock.read().await.source_chain.len();
+
+        let (returned_ribosome, result) = schedule_workflow_inner(
+            workspace_lock.clone(),
+            chain_head_start_len,
+            network.clone(),
+            keystore.clone(),
+            ribosome,
+            &conductor_api,
+            signal_tx.clone(),
+            scheduled_fn.clone(),
+        )
+        .await?;
+        ribosome = returned_ribosome;
+
+        // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
+
+        let mut guard = workspace_lock.write().await;
+        let workspace: &mut CallZomeWorkspace = &mut guard;
+        match result {
+            Ok(()) => {
+                committed |= workspace.source_chain.len() > chain_head_start_len;
+            }
+            Err(error) => {
+                warn!(
+                    zome_name = ?scheduled_fn.zome_name,
+                    fn_name = ?scheduled_fn.fn_name,
+                    ?error,
+                    "Scheduled function failed, discarding its changes"
+                );
+                *workspace = CallZomeWorkspace::new(env.clone().into())?;
+            }
+        }
+        // Scheduled functions only ever run once
+        workspace.scheduled_fns.delete(key)?;
+        OneshotWriter::new(env.clone())
+            .with_writer(|writer| Ok(workspace.flush_to_txn_ref(writer)?))?;
+    }
+
+    if committed {
+        trigger_produce_dht_ops.trigger();
+    }
+
+    let store = ScheduledFnStore::new(env.clone().into())?;
+    Ok(fresh_reader!(env, |r| store.next_due(&r))?)
+}
+
+/// Call a single scheduled function and validate anything it committed.
+/// The ribosome is handed back so it can be reused for the next function.
+#[allow(clippy::too_many_arguments)]
+async fn schedule_workflow_inner<Ribosome: RibosomeT + Send + 'static>(
+    workspace_lock: CallZomeWorkspaceLock,
+    chain_head_start_len: usize,
+    network: HolochainP2pCell,
+    keystore: KeystoreSender,
+    ribosome: Ribosome,
+    conductor_api: &impl CellConductorApiT,
+    signal_tx: SignalBroadcaster,
+    scheduled_fn: ScheduledFn,
+) -> WorkflowResult<(Ribosome, WorkflowResult<()>)> {
+    let ScheduledFn {
+        zome_name,
+        fn_name,
+        payload,
+        ..
+    } = scheduled_fn;
+    let zome = match ribosome.dna_def().get_zome(&zome_name) {
+        Ok(zome) => zome,
+        Err(e) => return Ok((ribosome, Err(RibosomeError::from(e).into()))),
+    };
+    let call_zome_handle = conductor_api.clone().into_call_zome_handle();
+    let cell_id = conductor_api.cell_id().clone();
+    let limits = ZomeCallLimits::new(&conductor_api.zome_call_config());
+
+    let (ribosome, result) = tokio::task::spawn_blocking({
+        let workspace_lock = workspace_lock.clone();
+        let network = network.clone();
+        let zome = zome.clone();
+        move || {
+            let host_access = ScheduleHostAccess::new(
+                workspace_lock,
+                keystore,
+                network,
+                signal_tx,
+                call_zome_handle,
+                cell_id,
+                limits,
+            );
+            let invocation = ScheduleInvocation::new(zome, fn_name, payload);
+            let result = ribosome.run_schedule(host_access, invocation);
+            (ribosome, result)
+        }
+    })
+    .await?;
+
+    let result = match result {
+        Ok(()) => {
+            validate_new_elements(
+                chain_head_start_len,
+                zome,
+                &ribosome,
+                workspace_lock,
+                network,
+                conductor_api,
+            )
+            .await
+        }
+        Err(e) => Err(e.into()),
+    };
+    Ok((ribosome, result))
+}
@@ -0,0 +1,258 @@
+//! # Slow Heal Workflow
+//!
+//! Ops waiting in the validation limbo are only tried again when the
+//! validation workflows next run, which may never happen if no new ops arrive.
+//! This workflow finds the ops which haven't been tried for a while and
+//! triggers the validation workflows to try them again. Sys validation fetches
+//! the missing dependencies of the ops from the network again as it does so.
+//!
+//! Ops which have waited in the limbo for longer than [ABANDON_AFTER] are
+//! abandoned, so the ops whose dependencies never arrive don't stay in the
+//! limbo forever. Ops waiting for DPKI to answer for their author are not
+//! abandoned, as it is DPKI on this conductor that is missing, not the op's
+//! dependencies.
+
+use super::error::WorkflowResult;
+use crate::core::queue_consumer::OneshotWriter;
+use crate::core::queue_consumer::TriggerSender;
+use fallible_iterator::FallibleIterator;
+use holo_hash::*;
+use holochain_lmdb::buffer::BufferedStore;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::INTEGRATION_LIMBO;
+use holochain_lmdb::fresh_reader;
+use holochain_lmdb::prelude::*;
+use holochain_lmdb::transaction::Writer;
+use holochain_state::prelude::*;
+use holochain_types::prelude::*;
+use holochain_zome_types::ValidationStatus;
+use std::time;
+use tracing::*;
+
+/// Try to validate an op again if it hasn't been tried for this long
+pub const RETRY_AFTER: time::Duration = time::Duration::from_secs(5 * 60);
+
+/// Abandon an op which has been waiting in the validation limbo for this long
+pub const ABANDON_AFTER: time::Duration = time::Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Database buffers required for healing the validation limbo
+pub struct SlowHealWorkspace {
+    /// The ops waiting to be validated
+    validation_limbo: ValidationLimboStore,
+    /// Where abandoned ops go to be integrated
+    integration_limbo: IntegrationLimboStore,
+}
+
+/// What a slow heal pass found in the validation limbo
+#[derive(Debug, Default, PartialEq, Eq)]
+struct SlowHealOutcome {
+    /// Ops to try to sys validate again
+    sys_retries: usize,
+    /// Ops to try to app validate again
+    app_retries: usize,
+    /// Ops which have been abandoned
+    abandoned: usize,
+}
+
+#[instrument(skip(
+    workspace,
+    writer,
+    trigger_sys_validation,
+    trigger_app_validation,
+    trigger_integration
+))]
+pub async fn slow_heal_workflow(
+    mut workspace: SlowHealWorkspace,
+    writer: OneshotWriter,
+    mut trigger_sys_validation: TriggerSender,
+    mut trigger_app_validation: TriggerSender,
+    mut trigger_integration: TriggerSender,
+) -> WorkflowResult<()> {
+    let outcome = slow_heal_workflow_inner(&mut workspace, timestamp::now())?;
+
+    // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
+
+    // commit the workspace
+    writer.with_writer(|writer| Ok(workspace.flush_to_txn(writer)?))?;
+
+    // trigger other workflows
+    if outcome.sys_retries > 0 {
+        trigger_sys_validation.trigger();
+    }
+    if outcome.app_retries > 0 {
+        trigger_app_validation.trigger();
+    }
+    if outcome.abandoned > 0 {
+        trigger_integration.trigger();
+    }
+
+    debug!(?outcome, "slow heal");
+    Ok(())
+}
+
+fn slow_heal_workflow_inner(
+    workspace: &mut SlowHealWorkspace,
+    now: Timestamp,
+) -> WorkflowResult<SlowHealOutcome> {
+    // chrono cannot create const durations
+    let retry_after =
+        chrono::Duration::from_std(RETRY_AFTER).expect("const interval must be positive");
+    let abandon_after =
+        chrono::Duration::from_std(ABANDON_AFTER).expect("const interval must be positive");
+    let waited = |since: &Timestamp| now.checked_difference_signed(since);
+
+    let env = workspace.validation_limbo.env().clone();
+    let ops: Vec<(DhtOpHash, ValidationLimboValue)> = fresh_reader!(env, |r| workspace
+        .validation_limbo
+        .iter(&r)?
+        .map(|(k, vlv)| Ok((DhtOpHash::from_raw_39_panicky(k.to_vec()), vlv)))
+        .collect())?;
+
+    let mut outcome = SlowHealOutcome::default();
+    for (op_hash, vlv) in ops {
+        let too_old = waited(&vlv.time_added).map_or(false, |waited| waited > abandon_after);
+        if too_old && vlv.status != ValidationLimboStatus::AwaitingAuthorKey {
+            warn!(
+                ?op_hash,
+                status = ?vlv.status,
+                "Abandoning op which has waited too long to be validated"
+            );
+            workspace.validation_limbo.delete(op_hash.clone())?;
+            workspace.integration_limbo.put(
+                op_hash,
+                IntegrationLimboValue {
+                    op: vlv.op,
+                    validation_status: ValidationStatus::Abandoned,
+                },
+            )?;
+            outcome.abandoned += 1;
+            continue;
+        }
+
+        let last_try = vlv.last_try.as_ref().unwrap_or(&vlv.time_added);
+        if waited(last_try).map_or(false, |waited| waited > retry_after) {
+            match vlv.status {
+                ValidationLimboStatus::Pending
+                | ValidationLimboStatus::AwaitingSysDeps(_)
+                | ValidationLimboStatus::AwaitingAuthorKey => outcome.sys_retries += 1,
+                ValidationLimboStatus::SysValidated | ValidationLimboStatus::AwaitingAppDeps(_) => {
+                    outcome.app_retries += 1
+                }
+            }
+        }
+    }
+    Ok(outcome)
+}
+
+impl Workspace for SlowHealWorkspace {
+    fn flush_to_txn_ref(&mut self, writer: &mut Writer) -> WorkspaceResult<()> {
+        self.validation_limbo.0.flush_to_txn_ref(writer)?;
+        self.integration_limbo.flush_to_txn_ref(writer)?;
+        Ok(())
+    }
+}
+
+impl SlowHealWorkspace {
+    pub fn new(env: EnvironmentRead) -> WorkspaceResult<Self> {
+        let db = env.get_db(&*INTEGRATION_LIMBO)?;
+        let integration_limbo = KvBufFresh::new(env.clone(), db);
+        let validation_limbo = ValidationLimboStore::new(env)?;
+        Ok(Self {
+            validation_limbo,
+            integration_limbo,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use holochain_lmdb::test_utils::test_cell_env;
+
+    fn limbo_value(
+        status: ValidationLimboStatus,
+        time_added: Timestamp,
+        last_try: Option<Timestamp>,
+    ) -> ValidationLimboValue {
+        let basis: AnyDhtHash = fixt!(EntryHash).into();
+        ValidationLimboValue {
+            status,
+            op: DhtOpLight::RegisterAddLink(fixt!(HeaderHash), basis.clone()),
+            basis,
+            time_added,
+            last_try,
+            num_tries: 0,
+            from_agent: None,
+            send_receipt: false,
+        }
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn stale_ops_are_retried_and_old_ops_abandoned() {
+        observability::test_run().ok();
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let now = Timestamp(ABANDON_AFTER.as_secs() as i64 * 2, 0);
+        let recently = Timestamp(now.0 - 1, 0);
+        let a_while_ago = Timestamp(now.0 - RETRY_AFTER.as_secs() as i64 - 1, 0);
+        let long_ago = Timestamp(now.0 - ABANDON_AFTER.as_secs() as i64 - 1, 0);
+
+        let ops = vec![
+            // Tried recently
+            limbo_value(ValidationLimboStatus::Pending, a_while_ago, Some(recently)),
+            // Waiting on dependencies for a while
+            limbo_value(
+                ValidationLimboStatus::AwaitingSysDeps(fixt!(HeaderHash).into()),
+                a_while_ago,
+                None,
+            ),
+            limbo_value(
+                ValidationLimboStatus::AwaitingAppDeps(vec![fixt!(EntryHash).into()]),
+                a_while_ago,
+                Some(a_while_ago),
+            ),
+            // Never going to arrive
+            limbo_value(ValidationLimboStatus::Pending, long_ago, Some(recently)),
+            // Waiting on DPKI, which is never abandoned
+            limbo_value(
+                ValidationLimboStatus::AwaitingAuthorKey,
+                long_ago,
+                Some(a_while_ago),
+            ),
+        ];
+        let abandoned_hash = fixt!(DhtOpHash);
+        let mut workspace = SlowHealWorkspace::new(env.clone().into()).unwrap();
+        for (i, vlv) in ops.into_iter().enumerate() {
+            let op_hash = if i == 3 {
+                abandoned_hash.clone()
+            } else {
+                fixt!(DhtOpHash)
+            };
+            workspace.validation_limbo.put(op_hash, vlv).unwrap();
+        }
+
+        let outcome = slow_heal_workflow_inner(&mut workspace, now).unwrap();
+        assert_eq!(
+            outcome,
+            SlowHealOutcome {
+                sys_retries: 2,
+                app_retries: 1,
+                abandoned: 1,
+            }
+        );
+        assert!(!workspace
+            .validation_limbo
+            .contains(&abandoned_hash)
+            .unwrap());
+        assert_eq!(
+            workspace
+                .integration_limbo
+                .get(&abandoned_hash)
+                .unwrap()
+                .unwrap()
+                .validation_status,
+            ValidationStatus::Abandoned
+        );
+    }
+}
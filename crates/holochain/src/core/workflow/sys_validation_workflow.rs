@@ -96,7 +96,8 @@ async fn sys_validation_workflow_inner(
                     match vlv.status {
                         // We only want pending or awaiting sys dependency ops
                         ValidationLimboStatus::Pending
-                        | ValidationLimboStatus::AwaitingSysDeps(_) => Ok(true),
+                        | ValidationLimboStatus::AwaitingSysDeps(_)
+                        | ValidationLimboStatus::AwaitingAuthorKey => Ok(true),
                         ValidationLimboStatus::SysValidated
                         | ValidationLimboStatus::AwaitingAppDeps(_) => Ok(false),
                     }
@@ -174,7 +175,23 @@ async fn sys_validation_workflow_inner(
                 vlv.status = ValidationLimboStatus::Pending;
                 workspace.put_val_limbo(op_hash, vlv)?;
             }
-            Outcome::Rejected => {
+            Outcome::AwaitingAuthorKey => {
+                vlv.status = ValidationLimboStatus::AwaitingAuthorKey;
+                workspace.put_val_limbo(op_hash, vlv)?;
+            }
+            Outcome::Rejected(failure) => {
+                let proof = WarrantProof::InvalidOp {
+                    header: SignedHeader(op.header(), op.signature().clone()),
+                    failure,
+                };
+                workspace
+                    .warrants
+                    .issue(
+                        proof,
+                        conductor_api.cell_id().agent_pubkey().clone(),
+                        conductor_api.keystore(),
+                    )
+                    .await?;
                 let iv = IntegrationLimboValue {
                     op: vlv.op,
                     validation_status: ValidationStatus::Rejected,
@@ -223,34 +240,35 @@ async fn validate_op(
     }
 }
 
-/// For now errors result in an outcome but in the future
-/// we might find it useful to include the reason something
-/// was rejected etc.
-/// This is why the errors contain data but is currently unread.
+/// Errors result in an outcome. Rejections carry the reason
+/// so it can be given as evidence in a warrant.
 fn handle_failed(error: ValidationOutcome) -> Outcome {
     use Outcome::*;
+    let reason = error.to_string();
     match error {
+        ValidationOutcome::AuthorKey(_, _) => Rejected(reason),
+        ValidationOutcome::AuthorKeyUnavailable(_, _) => AwaitingAuthorKey,
         ValidationOutcome::Counterfeit(_, _) => {
             unreachable!("Counterfeit ops are dropped before sys validation")
         }
         ValidationOutcome::DepMissingFromDht(_) => MissingDhtDep,
-        ValidationOutcome::EntryDefId(_) => Rejected,
-        ValidationOutcome::EntryHash => Rejected,
-        ValidationOutcome::EntryTooLarge(_, _) => Rejected,
-        ValidationOutcome::EntryType => Rejected,
-        ValidationOutcome::EntryVisibility(_) => Rejected,
-        ValidationOutcome::TagTooLarge(_, _) => Rejected,
-        ValidationOutcome::NotCreateLink(_) => Rejected,
-        ValidationOutcome::NotNewEntry(_) => Rejected,
+        ValidationOutcome::EntryDefId(_) => Rejected(reason),
+        ValidationOutcome::EntryHash => Rejected(reason),
+        ValidationOutcome::EntryTooLarge(_, _) => Rejected(reason),
+        ValidationOutcome::EntryType => Rejected(reason),
+        ValidationOutcome::EntryVisibility(_) => Rejected(reason),
+        ValidationOutcome::TagTooLarge(_, _) => Rejected(reason),
+        ValidationOutcome::NotCreateLink(_) => Rejected(reason),
+        ValidationOutcome::NotNewEntry(_) => Rejected(reason),
         ValidationOutcome::NotHoldingDep(dep) => AwaitingOpDep(dep),
         ValidationOutcome::PrevHeaderError(PrevHeaderError::MissingMeta(dep)) => {
             AwaitingOpDep(dep.into())
         }
-        ValidationOutcome::PrevHeaderError(_) => Rejected,
-        ValidationOutcome::PrivateEntry => Rejected,
-        ValidationOutcome::UpdateTypeMismatch(_, _) => Rejected,
-        ValidationOutcome::VerifySignature(_, _) => Rejected,
-        ValidationOutcome::ZomeId(_) => Rejected,
+        ValidationOutcome::PrevHeaderError(_) => Rejected(reason),
+        ValidationOutcome::PrivateEntry => Rejected(reason),
+        ValidationOutcome::UpdateTypeMismatch(_, _) => Rejected(reason),
+        ValidationOutcome::VerifySignature(_, _) => Rejected(reason),
+        ValidationOutcome::ZomeId(_) => Rejected(reason),
     }
 }
 
@@ -261,6 +279,7 @@ async fn validate_op_inner(
     conductor_api: &impl CellConductorApiT,
     incoming_dht_ops_sender: Option<IncomingDhtOpSender>,
 ) -> SysValidationResult<()> {
+    check_author_key(&op.header(), conductor_api).await?;
     match op {
         DhtOp::StoreElement(_, header, entry) => {
             store_element(header, workspace, network.clone()).await?;
@@ -292,10 +311,26 @@ async fn validate_op_inner(
             store_element(&header, workspace, network).await?;
             Ok(())
         }
-        DhtOp::RegisterAgentActivity(_, header) => {
+        DhtOp::RegisterAgentActivity(signature, header) => {
             register_agent_activity(header, workspace, network.clone(), incoming_dht_ops_sender)
                 .await?;
             store_element(header, workspace, network).await?;
+            // A fork doesn't make this header invalid on its own
+            // but it is evidence against the author.
+            for other in check_chain_rollback(header, workspace).await? {
+                let proof = WarrantProof::chain_fork(
+                    other.into_inner().0,
+                    SignedHeader(header.clone(), signature.clone()),
+                );
+                workspace
+                    .warrants
+                    .issue(
+                        proof,
+                        conductor_api.cell_id().agent_pubkey().clone(),
+                        conductor_api.keystore(),
+                    )
+                    .await?;
+            }
             Ok(())
         }
         DhtOp::RegisterUpdatedContent(_, header, entry) => {
@@ -399,6 +434,7 @@ async fn sys_validate_element_inner(
     if !counterfeit_check(signature, header).await? {
         return Err(ValidationOutcome::Counterfeit(signature.clone(), header.clone()).into());
     }
+    check_author_key(header, conductor_api).await?;
     store_element(header, workspace, network.clone()).await?;
     if let Some((entry, EntryVisibility::Public)) =
         &entry.and_then(|e| header.entry_type().map(|et| (e, et.visibility())))
@@ -433,14 +469,14 @@ async fn sys_validate_element_inner(
     Ok(())
 }
 
-/// Check if the op has valid signature and author.
+/// Check if the op has a valid signature.
 /// Ops that fail this check should be dropped.
+/// The author key is checked with dpki during validation.
 pub async fn counterfeit_check(
     signature: &Signature,
     header: &Header,
 ) -> SysValidationResult<bool> {
-    Ok(verify_header_signature(&signature, &header).await?
-        && author_key_is_valid(header.author()).await?)
+    verify_header_signature(&signature, &header).await
 }
 
 async fn register_agent_activity(
@@ -465,7 +501,6 @@ async fn register_agent_activity(
         )
         .await?;
     }
-    check_chain_rollback(&header, &workspace).await?;
     Ok(())
 }
 
@@ -698,6 +733,8 @@ pub struct SysValidationWorkspace {
     /// Cached data
     pub element_cache: ElementBuf,
     pub meta_cache: MetadataBuf,
+    /// Warrants issued against the authors of rejected ops
+    pub warrants: WarrantStore,
     pub env: EnvironmentRead,
 }
 
@@ -718,6 +755,7 @@ impl<'a> SysValidationWorkspace {
             &mut self.meta_cache,
             network,
         )
+        .with_warrants(&mut self.warrants)
     }
 }
 
@@ -742,6 +780,8 @@ impl SysValidationWorkspace {
         let element_rejected = ElementBuf::rejected(env.clone())?;
         let meta_rejected = MetadataBuf::rejected(env.clone())?;
 
+        let warrants = WarrantStore::new(env.clone())?;
+
         Ok(Self {
             integration_limbo,
             validation_limbo,
@@ -755,6 +795,7 @@ impl SysValidationWorkspace {
             meta_authored,
             element_cache,
             meta_cache,
+            warrants,
             env,
         })
     }
@@ -787,6 +828,7 @@ impl SysValidationWorkspace {
         Cascade::empty()
             .with_network(network)
             .with_cache(cache_data)
+            .with_warrants(&mut self.warrants)
     }
 
     /// Create a cascade with local data only
@@ -817,6 +859,7 @@ impl SysValidationWorkspace {
             .with_pending(pending_data)
             .with_cache(cache_data)
             .with_rejected(rejected_data)
+            .with_warrants(&mut self.warrants)
     }
 
     /// Get a cascade over all local databases and the network
@@ -838,6 +881,7 @@ impl Workspace for SysValidationWorkspace {
 
         self.element_pending.flush_to_txn_ref(writer)?;
         self.meta_pending.flush_to_txn_ref(writer)?;
+        self.warrants.flush_to_txn_ref(writer)?;
         Ok(())
     }
 }
@@ -856,6 +900,9 @@ impl TryFrom<&CallZomeWorkspace> for SysValidationWorkspace {
             meta_rejected,
             element_cache,
             meta_cache,
+            scheduled_fns: _,
+            cap_grant_uses: _,
+            warrants: _,
         } = call_zome;
         let mut sys_val = Self::new(call_zome.env().clone())?;
         sys_val.element_authored = source_chain.elements().into();
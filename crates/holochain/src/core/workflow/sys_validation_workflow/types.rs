@@ -14,6 +14,10 @@ pub(super) enum Outcome {
     /// be found currently on the DHT.
     /// Note this is not proof it doesn't exist.
     MissingDhtDep,
+    /// Stays in limbo because DPKI could not be asked
+    /// whether the author key is valid
+    AwaitingAuthorKey,
     /// Moves to integration with status rejected
-    Rejected,
+    /// and a warrant is issued against the author
+    Rejected(String),
 }
@@ -12,6 +12,8 @@ use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentHostAccess
 use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentInvocation;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitHostAccess;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitInvocation;
+use crate::core::ribosome::guest_callback::schedule::ScheduleHostAccess;
+use crate::core::ribosome::guest_callback::schedule::ScheduleInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateHostAccess;
 use crate::core::ribosome::guest_callback::validate::ValidateInvocation;
 use crate::core::ribosome::guest_callback::validate_link::ValidateCreateLinkInvocation;
@@ -26,6 +28,7 @@ use crate::core::ribosome::FnComponents;
 use crate::core::ribosome::HostAccess;
 use crate::core::ribosome::ZomeCallHostAccess;
 use crate::core::ribosome::ZomeCallInvocation;
+use crate::core::ribosome::ZomeCallLimits;
 use crate::core::ribosome::ZomesToInvoke;
 use crate::core::workflow::CallZomeWorkspace;
 use crate::core::workflow::CallZomeWorkspaceLock;
@@ -302,7 +305,14 @@ fixturator!(
 
 fixturator!(
     ZomeCallHostAccess;
-    constructor fn new(CallZomeWorkspaceLock, KeystoreSender, HolochainP2pCell, SignalBroadcaster, CellConductorReadHandle, CellId);
+    constructor fn new(CallZomeWorkspaceLock, KeystoreSender, HolochainP2pCell, SignalBroadcaster, CellConductorReadHandle, CellId, ZomeCallLimits);
+);
+
+fixturator!(
+    ZomeCallLimits;
+    curve Empty ZomeCallLimits::new(&Default::default());
+    curve Unpredictable ZomeCallLimits::new(&Default::default());
+    curve Predictable ZomeCallLimits::new(&Default::default());
 );
 
 fixturator!(
@@ -345,6 +355,16 @@ fixturator!(
     constructor fn new(CallZomeWorkspaceLock, KeystoreSender, HolochainP2pCell);
 );
 
+fixturator!(
+    ScheduleInvocation;
+    constructor fn new(Zome, FunctionName, ExternIo);
+);
+
+fixturator!(
+    ScheduleHostAccess;
+    constructor fn new(CallZomeWorkspaceLock, KeystoreSender, HolochainP2pCell, SignalBroadcaster, CellConductorReadHandle, CellId, ZomeCallLimits);
+);
+
 fixturator!(
     ZomesToInvoke;
     constructor fn one(Zome);
@@ -430,12 +450,13 @@ fixturator!(
         MigrateAgent(MigrateAgentHostAccess)
         ValidationPackage(ValidationPackageHostAccess)
         PostCommit(PostCommitHostAccess)
+        Schedule(ScheduleHostAccess)
     ];
 );
 
 fixturator!(
     CallContext;
-    constructor fn new(Zome, HostAccess);
+    constructor fn new(Zome, FunctionName, HostAccess);
 );
 
 fixturator!(
@@ -12,6 +12,7 @@ use crate::core::ribosome::HostAccess;
 use crate::core::ribosome::RibosomeT;
 use crate::core::ribosome::ZomeCallHostAccess;
 use crate::core::ribosome::ZomeCallInvocation;
+use crate::core::ribosome::ZomeCallLimits;
 use crate::core::workflow::CallZomeWorkspace;
 use crate::core::workflow::CallZomeWorkspaceLock;
 use hdk::prelude::EntryError;
@@ -175,10 +176,16 @@ impl HostFnCaller {
             signal_tx,
             call_zome_handle,
             cell_id,
+            ZomeCallLimits::new(&Default::default()),
         );
         let ribosome = Arc::new(ribosome);
         let zome = ribosome.dna_def().get_zome(&zome_name).unwrap();
-        let call_context = Arc::new(CallContext::new(zome, host_access.into()));
+        // Host functions are called directly, not from a zome function
+        let call_context = Arc::new(CallContext::new(
+            zome,
+            "host_fn_caller".into(),
+            host_access.into(),
+        ));
         (env, ribosome, call_context, workspace_lock)
     }
 }
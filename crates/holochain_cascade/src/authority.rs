@@ -9,6 +9,7 @@ use holochain_lmdb::env::EnvironmentRead;
 use holochain_lmdb::env::EnvironmentWrite;
 use holochain_lmdb::env::ReadManager;
 use holochain_lmdb::error::DatabaseError;
+use holochain_lmdb::error::DatabaseResult;
 use holochain_lmdb::fresh_reader;
 use holochain_lmdb::prelude::PrefixType;
 use holochain_lmdb::prelude::Readable;
@@ -17,6 +18,7 @@ use holochain_state::metadata::ChainItemKey;
 use holochain_state::metadata::LinkMetaKey;
 use holochain_state::metadata::MetadataBuf;
 use holochain_state::metadata::MetadataBufT;
+use holochain_state::warrant_db::WarrantStore;
 use holochain_types::prelude::*;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
@@ -257,13 +259,35 @@ pub fn handle_get_agent_activity(
     let element_integrated = ElementBuf::vault(env.clone(), false)?;
     let meta_integrated = MetadataBuf::vault(env.clone())?;
     let element_rejected = ElementBuf::rejected(env.clone())?;
+    let warrant_store = WarrantStore::new(env.clone())?;
 
     // Status
     let status = meta_integrated
         .get_activity_status(&agent)?
         .unwrap_or(ChainStatus::Empty);
+    // Check the activity itself for forks in case the stored
+    // status has not caught up with the headers we hold.
+    let fork = fresh_reader!(env, |r| find_chain_fork(&meta_integrated, &agent, &r))?;
+    let status = match (status, fork) {
+        (ChainStatus::Empty, Some(fork)) | (ChainStatus::Valid(_), Some(fork)) => {
+            ChainStatus::Forked(fork)
+        }
+        (ChainStatus::Invalid(head), Some(fork)) if fork.fork_seq < head.header_seq => {
+            ChainStatus::Forked(fork)
+        }
+        (ChainStatus::Forked(prev), Some(fork)) if fork.fork_seq < prev.fork_seq => {
+            ChainStatus::Forked(fork)
+        }
+        (status, _) => status,
+    };
+    if let ChainStatus::Forked(fork) = &status {
+        warn!(msg = "Serving a forked chain", ?agent, ?fork);
+    }
     let highest_observed = meta_integrated.get_activity_observed(&agent)?;
 
+    // Any evidence this authority has against the agent is always sent
+    let warrants = warrant_store.warrants(&agent)?;
+
     // Valid headers
     let valid_activity = if options.include_valid_activity {
         fresh_reader!(env, |r| {
@@ -302,16 +326,50 @@ pub fn handle_get_agent_activity(
         agent,
         status,
         highest_observed,
+        warrants,
     })
 }
 
+/// Find the earliest position in the valid activity
+/// where the agent has two headers.
+fn find_chain_fork<R: Readable>(
+    meta: &MetadataBuf,
+    agent: &AgentPubKey,
+    reader: &R,
+) -> DatabaseResult<Option<ChainFork>> {
+    let mut activity = meta.get_activity_sequence(
+        reader,
+        ChainItemKey::AgentStatus(agent.clone(), ValidationStatus::Valid),
+    )?;
+    let mut last: Option<(u32, HeaderHash)> = None;
+    while let Some((seq, hash)) = activity.next()? {
+        if let Some((last_seq, last_hash)) = last {
+            if last_seq == seq {
+                return Ok(Some(ChainFork {
+                    fork_seq: seq,
+                    first_header: last_hash,
+                    second_header: hash,
+                }));
+            }
+        }
+        last = Some((seq, hash));
+    }
+    Ok(None)
+}
+
 fn get_full_headers<'a, P: PrefixType + 'a, R: Readable>(
     hashes: impl FallibleIterator<Item = (u32, HeaderHash), Error = DatabaseError> + 'a,
     query: ChainQueryFilter,
     database: ElementBuf<P>,
     reader: &'a R,
 ) -> impl FallibleIterator<Item = (u32, SignedHeaderHashed), Error = DatabaseError> + 'a {
+    // The activity is in sequence order so a page of a long chain
+    // only needs to read the headers in its range
+    let start = query.sequence_range.as_ref().map(|r| r.start);
+    let end = query.sequence_range.as_ref().map(|r| r.end);
     hashes
+        .skip_while(move |(s, _)| Ok(start.map_or(false, |start| *s < start)))
+        .take_while(move |(s, _)| Ok(end.map_or(true, |end| *s < end)))
         .filter_map(move |(s, h)| {
             Ok(database
                 .get_header_with_reader(reader, &h)?
@@ -26,6 +26,8 @@ use holochain_state::prelude::*;
 use holochain_types::prelude::*;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use tracing::*;
 use tracing_futures::Instrument;
 
@@ -132,6 +134,8 @@ pub struct Cascade<
     pending_data: Option<DbPair<'a, MetaPending, PendingPrefix>>,
     rejected_data: Option<DbPair<'a, MetaRejected, RejectedPrefix>>,
     cache_data: Option<DbPairMut<'a, MetaCache>>,
+    /// Where verified warrants fetched from the network are held
+    warrants: Option<&'a mut WarrantStore>,
     env: Option<EnvironmentRead>,
     network: Option<Network>,
 }
@@ -151,6 +155,18 @@ enum Search {
     NotInCascade,
 }
 
+#[derive(Default)]
+/// What the agent activity authorities sent
+/// that isn't kept in the cache
+struct FetchedActivity {
+    /// Verified warrants against the agent
+    warrants: Vec<SignedWarrant>,
+    /// Full valid headers, which may repeat across authorities
+    valid_headers: Vec<SignedHeaderHashed>,
+    /// Full rejected headers, which may repeat across authorities
+    rejected_headers: Vec<SignedHeaderHashed>,
+}
+
 impl<'a, Network, MetaVault, MetaAuthored, MetaCache, MetaPending, MetaRejected>
     Cascade<'a, Network, MetaVault, MetaAuthored, MetaCache, MetaPending, MetaRejected>
 where
@@ -202,6 +218,7 @@ where
             integrated_data,
             authored_data,
             cache_data,
+            warrants: None,
         }
     }
 }
@@ -215,6 +232,7 @@ impl<'a> Cascade<'a> {
             pending_data: None,
             rejected_data: None,
             cache_data: None,
+            warrants: None,
             env: None,
             network: None,
         }
@@ -275,6 +293,12 @@ where
         self
     }
 
+    /// Hold the verified warrants fetched from the network in this [WarrantStore]
+    pub fn with_warrants(mut self, warrants: &'a mut WarrantStore) -> Self {
+        self.warrants = Some(warrants);
+        self
+    }
+
     /// Add the integrated [ElementBuf] and [MetadataBuf] to the cascade
     pub fn with_network<N: HolochainP2pCellT + Clone>(
         self,
@@ -286,6 +310,7 @@ where
             pending_data: self.pending_data,
             rejected_data: self.rejected_data,
             cache_data: self.cache_data,
+            warrants: self.warrants,
             env: self.env,
             network: Some(network),
         }
@@ -1636,18 +1661,54 @@ where
             .collect()
     }
 
+    /// Fetch the activity from the network and cache it.
+    /// Returns the warrants from all the responses which pass verification
+    /// along with any full headers the authorities sent.
+    /// The verified warrants are also held in the warrant store if there is one.
     async fn fetch_agent_activity(
         &mut self,
         agent: AgentPubKey,
         query: ChainQueryFilter,
         options: GetActivityOptions,
-    ) -> CascadeResult<()> {
-        let network = ok_or_return!(self.network.as_mut());
-        let all_agent_activity = network.get_agent_activity(agent, query, options).await?;
-        for agent_activity in all_agent_activity {
+    ) -> CascadeResult<FetchedActivity> {
+        let network = ok_or_return!(self.network.as_mut(), FetchedActivity::default());
+        let all_agent_activity = network
+            .get_agent_activity(agent.clone(), query, options)
+            .await?;
+        let mut fetched = FetchedActivity::default();
+        for mut agent_activity in all_agent_activity {
+            for warrant in agent_activity.warrants.drain(..) {
+                // Only keep evidence against the agent we asked about
+                // that we can check for ourselves.
+                if warrant.warrant.proof.author() != &agent
+                    || fetched
+                        .warrants
+                        .iter()
+                        .any(|w| w.warrant.proof == warrant.warrant.proof)
+                {
+                    continue;
+                }
+                if verify_warrant(&warrant).await? {
+                    if let Some(store) = self.warrants.as_mut() {
+                        store.add(warrant.clone())?;
+                    }
+                    fetched.warrants.push(warrant);
+                } else {
+                    warn!(
+                        msg = "Dropping a warrant that failed verification",
+                        ?warrant
+                    );
+                }
+            }
+            if let ChainItems::Full(headers) = &agent_activity.valid_activity {
+                fetched.valid_headers.extend(headers.iter().cloned());
+            }
+            if let ChainItems::Full(headers) = &agent_activity.rejected_activity {
+                fetched.rejected_headers.extend(headers.iter().cloned());
+            }
             self.update_agent_activity_stores(agent_activity).await?;
         }
-        Ok(())
+        Ok(fetched)
     }
 
     async fn fetch_agent_activity_status(
@@ -1655,71 +1716,42 @@ where
         agent: AgentPubKey,
         query: ChainQueryFilter,
         mut options: GetActivityOptions,
-    ) -> CascadeResult<()> {
+    ) -> CascadeResult<Vec<SignedWarrant>> {
         options.include_valid_activity = false;
         options.include_rejected_activity = false;
         options.include_full_headers = false;
         // TODO: Maybe this could short circuit in full sharding? But I'm not sure.
         // Skipping this for now.
-        self.fetch_agent_activity(agent.clone(), query.clone(), options)
-            .await?;
-        Ok(())
+        Ok(self
+            .fetch_agent_activity(agent.clone(), query.clone(), options)
+            .await?
+            .warrants)
     }
 
+    /// Get the cached activity hashes with this validation status
+    /// which are in the range, in order of header sequence.
+    /// Note if the chain is forked there could be multiple headers at each sequence number.
     fn get_agent_activity_from_cache(
         agent: AgentPubKey,
         range: &Option<std::ops::Range<u32>>,
+        status: ValidationStatus,
         cache_data: &DbPairMut<'a, MetaCache>,
         env: &EnvironmentRead,
     ) -> CascadeResult<Vec<(u32, HeaderHash)>> {
-        match range {
-            Some(range) => {
-                // One less than the end of an exclusive range is actually
-                // the last header we want in the chain.
-                fresh_reader!(env, |r| {
-                    // Check if we have up to that header in the metadata store.
-                    if cache_data
-                        .meta
-                        .get_activity(
-                            &r,
-                            ChainItemKey::AgentStatusSequence(
-                                agent.clone(),
-                                ValidationStatus::Valid,
-                                range.end - 1,
-                            ),
-                        )?
-                        .next()?
-                        .is_some()
-                    {
-                        // We have the chain so collect the hashes in order of header sequence.
-                        // Note if the chain is forked there could be multiple headers at each sequence number.
-                        Ok(cache_data
-                            .meta
-                            .get_activity_sequence(
-                                &r,
-                                ChainItemKey::AgentStatus(agent, ValidationStatus::Valid),
-                            )?
-                            // TODO: PERF: Use an iter from to start from the correct sequence
-                            .skip_while(|(s, _)| Ok(*s < range.start))
-                            .take_while(|(s, _)| Ok(*s < range.end))
-                            .collect()?)
-                    } else {
-                        // The requested chain is not in our cache.
-                        Ok(vec![])
-                    }
-                })
+        fresh_reader!(env, |r| {
+            let activity = cache_data
+                .meta
+                .get_activity_sequence(&r, ChainItemKey::AgentStatus(agent, status))?;
+            match range {
+                Some(range) => Ok(activity
+                    // TODO: PERF: Use an iter from to start from the correct sequence
+                    .skip_while(|(s, _)| Ok(*s < range.start))
+                    .take_while(|(s, _)| Ok(*s < range.end))
+                    .collect()?),
+                // Requesting full chain so return all everything we have
+                None => Ok(activity.collect()?),
             }
-            // Requesting full chain so return all everything we have
-            None => fresh_reader!(env, |r| {
-                Ok(cache_data
-                    .meta
-                    .get_activity_sequence(
-                        &r,
-                        ChainItemKey::AgentStatus(agent, ValidationStatus::Valid),
-                    )?
-                    .collect()?)
-            }),
-        }
+        })
     }
 
     /// Check if we have a cache hit on a valid chain
@@ -1734,30 +1766,37 @@ where
 
         // Check if the range contains any values.
         // This also makes it safe to do `range.end - 1`
-        match sequence_range {
+        let range = match sequence_range {
             // The range is empty so there's not hashes to get
-            Some(r) if r.end == 0 => return Ok(Some(vec![])),
+            Some(r) if r.start >= r.end => return Ok(Some(vec![])),
+            Some(r) => r,
             // It only makes sense to check the cache first if
             // a range has been requested otherwise
             // we must go to the network because we don't
             // know how long the chain is.
             None => return Ok(None),
-            _ => {}
-        }
+        };
         // Try getting the activity from the cache.
-        let chain_hashes =
-            Self::get_agent_activity_from_cache(agent.clone(), sequence_range, cache_data, env)?;
+        let chain_hashes = Self::get_agent_activity_from_cache(
+            agent.clone(),
+            sequence_range,
+            ValidationStatus::Valid,
+            cache_data,
+            env,
+        )?;
 
         // Get the current status
         let cached_status = cache_data.meta.get_activity_status(&agent)?;
 
-        // If the chain is valid and the header we need is equal or below
-        // and the hashes length is equal to one more then the last header sequence number
-        // then we have a cache valid hit
-        match (chain_hashes.last(), &cached_status) {
-            (Some((chain_head_seq, _)), Some(ChainStatus::Valid(valid_status)))
-                if *chain_head_seq <= valid_status.header_seq
-                    && chain_hashes.len() as u32 == chain_head_seq + 1 =>
+        // If the chain is valid and we have every header from the start of the
+        // range up to the end of the range, or the chain head if that is sooner,
+        // then we have a cache valid hit.
+        // This way every page of a chain can be served from the cache.
+        match (chain_hashes.first(), chain_hashes.last(), &cached_status) {
+            (Some((first_seq, _)), Some((last_seq, _)), Some(ChainStatus::Valid(valid_status)))
+                if *first_seq == range.start
+                    && *last_seq == std::cmp::min(range.end - 1, valid_status.header_seq)
+                    && chain_hashes.len() as u32 == last_seq - first_seq + 1 =>
             {
                 Ok(Some(chain_hashes))
             }
@@ -1765,6 +1804,27 @@ where
         }
     }
 
+    /// Get the cached rejected hashes if they were requested
+    fn rejected_activity_from_cache(
+        &self,
+        agent: AgentPubKey,
+        query: &ChainQueryFilter,
+        options: &GetActivityOptions,
+    ) -> CascadeResult<ChainItems<Element>> {
+        if !options.include_rejected_activity {
+            return Ok(ChainItems::NotRequested);
+        }
+        let cache_data = ok_or_return!(self.cache_data.as_ref(), ChainItems::NotRequested);
+        let env = ok_or_return!(self.env.as_ref(), ChainItems::NotRequested);
+        Ok(ChainItems::Hashes(Self::get_agent_activity_from_cache(
+            agent,
+            &query.sequence_range,
+            ValidationStatus::Rejected,
+            cache_data,
+            env,
+        )?))
+    }
+
     /// Do a full fetch of hashes and return the activity
     async fn fetch_and_create_activity(
         &mut self,
@@ -1775,50 +1835,103 @@ where
         // Fetch the activity from the network
         // TODO: Maybe this could short circuit in full sharding? But I'm not sure.
         // Skipping this for now.
-        self.fetch_agent_activity(agent.clone(), query.clone(), options)
-            .await?;
-
-        let cache_data = ok_or_return!(
-            self.cache_data.as_ref(),
-            AgentActivityResponse::empty(&agent)
-        );
-        let env = ok_or_return!(self.env.as_ref(), AgentActivityResponse::empty(&agent));
+        let warrants = self
+            .fetch_agent_activity(agent.clone(), query.clone(), options.clone())
+            .await?
+            .warrants;
+
+        let (cache_data, env) = match (self.cache_data.as_ref(), self.env.as_ref()) {
+            (Some(cache_data), Some(env)) => (cache_data, env),
+            _ => {
+                return Ok(AgentActivityResponse {
+                    warrants,
+                    ..AgentActivityResponse::empty(&agent)
+                })
+            }
+        };
         // Now try getting the latest activity from cache
         let hashes = Self::get_agent_activity_from_cache(
             agent.clone(),
             &query.sequence_range,
+            ValidationStatus::Valid,
             cache_data,
             env,
         )?;
-        self.create_activity(agent, hashes)
+        let rejected_activity =
+            self.rejected_activity_from_cache(agent.clone(), &query, &options)?;
+        self.create_activity(
+            agent,
+            ChainItems::Hashes(hashes),
+            rejected_activity,
+            warrants,
+        )
+    }
+
+    /// Fetch the full headers from the authorities and return
+    /// the activity with them, so there is no need to get each
+    /// header by its hash afterwards.
+    async fn fetch_and_create_full_activity(
+        &mut self,
+        agent: AgentPubKey,
+        query: ChainQueryFilter,
+        options: GetActivityOptions,
+    ) -> CascadeResult<AgentActivityResponse<Element>> {
+        let FetchedActivity {
+            warrants,
+            valid_headers,
+            rejected_headers,
+        } = self
+            .fetch_agent_activity(agent.clone(), query.clone(), options.clone())
+            .await?;
+        let valid_activity = if options.include_valid_activity {
+            let headers = check_activity_headers(&agent, &query, valid_headers).await;
+            ChainItems::Full(
+                self.retrieve_activity_entries(headers, &query, options.retry_gets)
+                    .await?,
+            )
+        } else {
+            ChainItems::NotRequested
+        };
+        let rejected_activity = if options.include_rejected_activity {
+            let headers = check_activity_headers(&agent, &query, rejected_headers).await;
+            ChainItems::Full(
+                self.retrieve_activity_entries(headers, &query, options.retry_gets)
+                    .await?,
+            )
+        } else {
+            ChainItems::NotRequested
+        };
+        self.create_activity(agent, valid_activity, rejected_activity, warrants)
     }
 
-    /// Turn the hashes into agent activity with status and highest_observed
-    // TODO: There are several parts missing to this function because we
-    // are currently constraining the behavior to only serve getting validation
-    // packages.
-    // - [ ] Return the rejected activity (with or without caching it)
-    // - [ ] Be able to handle full headers as well as hashes (with or without caching)
-    // - [ ] Maybe Empty chains should not be set to NotRequested and set to the
+    /// Add the status and highest_observed to the activity
+    /// along with any verified warrants against the agent
+    // TODO: Maybe Empty chains should not be set to NotRequested and set to the
     // value that reflects the requester
     fn create_activity(
         &self,
         agent: AgentPubKey,
-        hashes: Vec<(u32, HeaderHash)>,
+        valid_activity: ChainItems<Element>,
+        rejected_activity: ChainItems<Element>,
+        warrants: Vec<SignedWarrant>,
     ) -> CascadeResult<AgentActivityResponse<Element>> {
         let cache_data = ok_or_return!(
             self.cache_data.as_ref(),
-            AgentActivityResponse::empty(&agent)
+            AgentActivityResponse {
+                warrants,
+                ..AgentActivityResponse::empty(&agent)
+            }
         );
         // Now try getting the latest activity from cache
         let highest_observed = cache_data.meta.get_activity_observed(&agent)?;
         match cache_data.meta.get_activity_status(&agent)? {
             Some(status) => Ok(AgentActivityResponse {
                 agent,
-                valid_activity: ChainItems::Hashes(hashes),
-                rejected_activity: ChainItems::NotRequested,
+                valid_activity,
+                rejected_activity,
                 status,
                 highest_observed,
+                warrants,
             }),
             // If we don't have any status then we must return an empty chain
             None => Ok(AgentActivityResponse {
@@ -1827,6 +1940,7 @@ where
                 rejected_activity: ChainItems::NotRequested,
                 status: ChainStatus::Empty,
                 highest_observed,
+                warrants,
             }),
         }
     }
@@ -1838,12 +1952,12 @@ where
     /// Get agent activity from agent activity authorities.
     /// Hashes are requested from the authority and cache for valid chains.
     /// Options:
-    /// - include_valid_activity will include the valid chain hashes.     
-    /// - include_rejected_activity will include the valid chain hashes. (unimplemented)
-    /// - include_full_headers will fetch the valid headers in parallel (requires include_valid_activity)
+    /// - include_valid_activity will include the valid chain hashes.
+    /// - include_rejected_activity will include the rejected chain hashes.
+    /// - include_full_headers will get the signed headers from the authorities instead of the hashes
     /// Query:
-    /// - include_entries will also fetch the entries in parallel (requires include_full_headers)
-    /// - sequence_range will get all the activity in the exclusive range
+    /// - include_entries will also fetch the public entries in parallel (requires include_full_headers)
+    /// - sequence_range will get all the activity in the exclusive range, so a long chain can be fetched a page at a time
     /// - header_type and entry_type will filter the activity (requires include_full_headers)
     pub async fn get_agent_activity(
         &mut self,
@@ -1852,10 +1966,6 @@ where
         mut options: GetActivityOptions,
     ) -> CascadeResult<AgentActivityResponse<Element>> {
         const DEFAULT_ACTIVITY_TIMEOUT_MS: u64 = 1000;
-        // Get the request options
-        let requester_options = options.clone();
-        // For now only fetching hashes until caching is worked out.
-        options.include_full_headers = false;
         // Get agent activity takes longer then other calls so we
         // will give it a larger default timeout
         options.timeout_ms = options
@@ -1863,8 +1973,16 @@ where
             .clone()
             .or(Some(DEFAULT_ACTIVITY_TIMEOUT_MS));
 
+        // Only the hashes are cached so full headers always
+        // come from the authorities.
+        if options.include_full_headers {
+            return self
+                .fetch_and_create_full_activity(agent, query, options)
+                .await;
+        }
+
         // See if we have a cache hit
-        let chain_hashes = match &query.sequence_range {
+        let (chain_hashes, warrants) = match &query.sequence_range {
             Some(_) => {
                 // If we have some cached agent activity then don't fetch the activity.
                 // Instead fetch just the status and see if the chain is still valid
@@ -1874,130 +1992,86 @@ where
                 // Fetch status without activity
 
                 // Fetch just the status
-                self.fetch_agent_activity_status(agent.clone(), query.clone(), options.clone())
+                let warrants = self
+                    .fetch_agent_activity_status(agent.clone(), query.clone(), options.clone())
                     .await?;
 
                 // See if our cache is still valid
-                self.find_valid_activity_cache_hit(agent.clone(), &query.sequence_range)?
+                (
+                    self.find_valid_activity_cache_hit(agent.clone(), &query.sequence_range)?,
+                    warrants,
+                )
             }
-            None => None,
+            None => (None, Vec::new()),
         };
 
         // Create the activity
-        let mut activity = match chain_hashes {
+        match chain_hashes {
             // If there was no activity in the cache then try fetching it
-            None => {
-                self.fetch_and_create_activity(agent.clone(), query.clone(), options.clone())
-                    .await?
-            }
+            None => self.fetch_and_create_activity(agent, query, options).await,
             // Create the activity from the hashes
-            Some(chain_hashes) => self.create_activity(agent.clone(), chain_hashes)?,
-        };
-
-        // Check if we are done
-        match &activity {
-            // ChainItems is empty so nothing else to do.
-            AgentActivityResponse {
-                status: ChainStatus::Empty,
-                ..
-            } => return Ok(activity),
-            // ChainItems has a status but there are no hashes
-            // so nothing else to do.
-            AgentActivityResponse {
-                valid_activity: ChainItems::Hashes(h),
-                ..
-            } if h.is_empty() => {
-                if requester_options.include_full_headers {
-                    activity.valid_activity = ChainItems::Full(Vec::new());
-                }
-                return Ok(activity);
-            }
-            _ => {}
-        }
-
-        match &activity.valid_activity {
-            ChainItems::Full(_) => todo!(),
-            ChainItems::Hashes(hashes) => {
-                // If full headers and include entries is requested
-                // retrieve them in parallel
-                if query.include_entries && requester_options.include_full_headers {
-                    let hashes = hashes.iter().map(|(_, h)| h.clone());
-                    let mut elements = self
-                        .retrieve_activity_elements(hashes.clone(), &query)
-                        .await?;
-                    let mut retry_gets = requester_options.retry_gets;
-                    while elements.is_none() && retry_gets > 0 {
-                        retry_gets -= 1;
-                        elements = self
-                            .retrieve_activity_elements(hashes.clone(), &query)
-                            .await?;
-                    }
-                    let elements = elements.unwrap_or_else(Vec::new);
-                    Ok(AgentActivityResponse {
-                        valid_activity: ChainItems::Full(elements),
-                        ..activity
-                    })
-                // If only full headers is requested
-                // retrieve just the headers in parallel
-                } else if requester_options.include_full_headers {
-                    let hashes = hashes.iter().map(|(_, h)| h.clone());
-                    let mut elements = self
-                        .retrieve_activity_headers(hashes.clone(), &query)
-                        .await?;
-                    let mut retry_gets = requester_options.retry_gets;
-                    while elements.is_none() && retry_gets > 0 {
-                        retry_gets -= 1;
-                        elements = self
-                            .retrieve_activity_headers(hashes.clone(), &query)
-                            .await?;
-                    }
-                    let elements = elements.unwrap_or_else(Vec::new);
-                    Ok(AgentActivityResponse {
-                        valid_activity: ChainItems::Full(elements),
-                        ..activity
-                    })
-                } else {
-                    // Otherwise return just the hashes
-                    Ok(activity)
-                }
+            Some(chain_hashes) => {
+                let rejected_activity =
+                    self.rejected_activity_from_cache(agent.clone(), &query, &options)?;
+                self.create_activity(
+                    agent,
+                    ChainItems::Hashes(chain_hashes),
+                    rejected_activity,
+                    warrants,
+                )
             }
-            ChainItems::NotRequested => Ok(activity),
         }
     }
 
-    async fn retrieve_activity_elements(
+    /// Turn the activity headers into elements.
+    /// If the query includes entries then the public entries
+    /// are fetched in parallel. Private entries are hidden.
+    async fn retrieve_activity_entries(
         &mut self,
-        hashes: impl IntoIterator<Item = HeaderHash>,
+        headers: Vec<SignedHeaderHashed>,
         query: &ChainQueryFilter,
-    ) -> CascadeResult<Option<Vec<Element>>> {
-        Ok(self
-            .retrieve_parallel(hashes, Default::default())
-            .await?
+        mut retry_gets: u8,
+    ) -> CascadeResult<Vec<Element>> {
+        if !query.include_entries {
+            return Ok(headers
+                .into_iter()
+                .map(|shh| Element::new(shh, None))
+                .collect());
+        }
+        let public_entry = |shh: &SignedHeaderHashed| match shh.header().entry_data() {
+            Some((entry_hash, entry_type))
+                if *entry_type.visibility() == EntryVisibility::Public =>
+            {
+                Some(entry_hash.clone())
+            }
+            _ => None,
+        };
+        let entry_hashes: Vec<_> = headers.iter().filter_map(public_entry).collect();
+        let mut entries = self
+            .retrieve_entries_parallel(entry_hashes.clone(), Default::default())
+            .await?;
+        while entries.iter().any(Option::is_none) && retry_gets > 0 {
+            retry_gets -= 1;
+            entries = self
+                .retrieve_entries_parallel(entry_hashes.clone(), Default::default())
+                .await?;
+        }
+        let entries: HashMap<EntryHash, Entry> = entries
             .into_iter()
-            // Filter the headers by the query
-            .filter(|o| match o {
-                Some(el) => query.check(el.header()),
-                None => true,
+            .flatten()
+            .map(|entry| {
+                let (entry, entry_hash) = entry.into_inner();
+                (entry_hash, entry)
             })
-            .collect::<Option<Vec<_>>>())
-    }
-
-    async fn retrieve_activity_headers(
-        &mut self,
-        hashes: impl IntoIterator<Item = HeaderHash>,
-        query: &ChainQueryFilter,
-    ) -> CascadeResult<Option<Vec<Element>>> {
-        Ok(self
-            .retrieve_headers_parallel(hashes, Default::default())
-            .await?
+            .collect();
+        // Any public entry that still couldn't be found is left as not stored
+        Ok(headers
             .into_iter()
-            // Filter the headers by the query
-            .filter(|o| match o {
-                Some(el) => query.check(el.header()),
-                None => true,
+            .map(|shh| {
+                let entry = public_entry(&shh).and_then(|hash| entries.get(&hash).cloned());
+                Element::new(shh, entry)
             })
-            .map(|shh| shh.map(|s| Element::new(s, None)))
-            .collect::<Option<Vec<_>>>())
+            .collect())
     }
 
     /// Get the validation package if it is cached without going to the network
@@ -2157,6 +2231,34 @@ where
     Ok(())
 }
 
+/// Only keep the activity headers which are by this agent, match the query
+/// and are signed by the agent, once each and in chain order.
+async fn check_activity_headers(
+    agent: &AgentPubKey,
+    query: &ChainQueryFilter,
+    headers: Vec<SignedHeaderHashed>,
+) -> Vec<SignedHeaderHashed> {
+    let mut seen = HashSet::new();
+    let mut checked = Vec::with_capacity(headers.len());
+    for shh in headers {
+        if shh.header().author() != agent
+            || !query.check(shh.header())
+            || !seen.insert(shh.header_address().clone())
+        {
+            continue;
+        }
+        match shh.validate().await {
+            Ok(()) => checked.push(shh),
+            Err(error) => warn!(
+                msg = "Dropping an activity header that failed verification",
+                ?error
+            ),
+        }
+    }
+    checked.sort_by_key(|shh| shh.header().header_seq());
+    checked
+}
+
 pub fn get_header<P: PrefixType>(
     hash: HeaderHash,
     element_store: &ElementBuf<P>,
@@ -1,3 +1,6 @@
+use crate::AppCryptoPermissions;
+use crate::InstalledAppInfo;
+use crate::InstalledAppStatus;
 use holo_hash::*;
 use holochain_types::prelude::*;
 use holochain_zome_types::cell::CellId;
@@ -26,7 +29,7 @@ pub enum AdminRequest {
     ///
     /// [`AdminInterfaceConfig`]: ../config/struct.AdminInterfaceConfig.html
     /// [`AdminResponse::AdminInterfacesAdded`]: enum.AdminResponse.html#variant.AdminInterfacesAdded
-    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
     AddAdminInterfaces(Vec<crate::config::AdminInterfaceConfig>),
 
     /// Register a DNA for later use in InstallApp
@@ -50,6 +53,91 @@ pub enum AdminRequest {
     /// [`CreateCloneCellPayload`]: ../../../holochain_types/app/struct.CreateCloneCellPayload.html
     /// [`AdminResponse::DnaCloned`]: enum.AdminResponse.html#variant.DnaCloned
     CreateCloneCell(Box<CreateCloneCellPayload>),
+    /// Destroy a clone cell, archived or not, removing it from its app and
+    /// deleting all of its data.
+    ///
+    /// Will be responded to with an [`AdminResponse::CloneCellDestroyed`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::CloneCellDestroyed`]: enum.AdminResponse.html#variant.CloneCellDestroyed
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
+    DestroyCloneCell {
+        /// The clone cell to destroy
+        cell_id: CellId,
+    },
+    /// Archive a clone cell of an app slot.
+    /// The cell stops running and no longer counts towards the clone limit of
+    /// the slot, but its data is kept so that it can be restored.
+    ///
+    /// Will be responded to with an [`AdminResponse::CloneCellArchived`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::CloneCellArchived`]: enum.AdminResponse.html#variant.CloneCellArchived
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
+    ArchiveCloneCell {
+        /// The app the clone belongs to
+        installed_app_id: InstalledAppId,
+        /// The slot the clone belongs to
+        slot_id: SlotId,
+        /// The clone cell to archive
+        cell_id: CellId,
+    },
+    /// Restore an archived clone cell of an app slot, running it again if the
+    /// app is active.
+    ///
+    /// Will be responded to with an [`AdminResponse::CloneCellRestored`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::CloneCellRestored`]: enum.AdminResponse.html#variant.CloneCellRestored
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
+    RestoreCloneCell {
+        /// The app the clone belongs to
+        installed_app_id: InstalledAppId,
+        /// The slot the clone belongs to
+        slot_id: SlotId,
+        /// The archived clone cell to restore
+        cell_id: CellId,
+    },
+    /// List the clone cells of an app slot, both running and archived.
+    ///
+    /// Will be responded to with an [`AdminResponse::CloneCellsListed`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::CloneCellsListed`]: enum.AdminResponse.html#variant.CloneCellsListed
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
+    ListCloneCells {
+        /// The app to list the clones of
+        installed_app_id: InstalledAppId,
+        /// The slot to list the clones of
+        slot_id: SlotId,
+    },
+    /// Check how well the ops a running cell has authored are held on the DHT,
+    /// by counting their validation receipts.
+    ///
+    /// Will be responded to with an [`AdminResponse::CellHealth`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::CellHealth`]: enum.AdminResponse.html#variant.CellHealth
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
+    GetCellHealth {
+        /// The cell to check
+        cell_id: CellId,
+    },
+    /// Set which [`CryptoRequest`]s an installed app may make over app interfaces.
+    /// Replaces any permissions previously set for the app.
+    ///
+    /// Will be responded to with an [`AdminResponse::AppCryptoPermissionsSet`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`CryptoRequest`]: enum.CryptoRequest.html
+    /// [`AdminResponse::AppCryptoPermissionsSet`]: enum.AdminResponse.html#variant.AppCryptoPermissionsSet
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
+    SetAppCryptoPermissions {
+        /// The app to set the permissions of
+        installed_app_id: InstalledAppId,
+        /// The crypto operations the app may perform
+        permissions: AppCryptoPermissions,
+    },
 
     /// Install an app from a list of `Dna` paths.
     /// Triggers genesis to be run on all `Cell`s and to be stored.
@@ -67,7 +155,7 @@ pub enum AdminRequest {
     /// [`InstallAppPayload`]: ../../../holochain_types/app/struct.InstallAppPayload.html
     /// [`AdminRequest::ActivateApp`]: enum.AdminRequest.html#variant.ActivateApp
     /// [`AdminResponse::AppInstalled`]: enum.AdminResponse.html#variant.AppInstalled
-    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
     InstallApp(Box<InstallAppPayload>),
 
     /// Install an app using an [`AppBundle`].
@@ -87,7 +175,7 @@ pub enum AdminRequest {
     /// [`InstallAppBundlePayload`]: ../../../holochain_types/app/struct.InstallAppBundlePayload.html
     /// [`AdminRequest::ActivateApp`]: enum.AdminRequest.html#variant.ActivateApp
     /// [`AdminResponse::AppInstalled`]: enum.AdminResponse.html#variant.AppInstalled
-    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
     InstallAppBundle(Box<InstallAppBundlePayload>),
 
     /// List the hashes of all installed `Dna`s.
@@ -97,7 +185,7 @@ pub enum AdminRequest {
     /// or an [`AdminResponse::Error`]
     ///
     /// [`AdminResponse::DnasListed`]: enum.AdminResponse.html#variant.DnasListed
-    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
     ListDnas,
 
     /// Generate a new AgentPubKey.
@@ -107,7 +195,7 @@ pub enum AdminRequest {
     /// or an [`AdminResponse::Error`]
     ///
     /// [`AdminResponse::AgentPubKeyGenerated`]: enum.AdminResponse.html#variant.AgentPubKeyGenerated
-    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
     GenerateAgentPubKey,
 
     /// List all the cell ids in the conductor.
@@ -117,7 +205,7 @@ pub enum AdminRequest {
     /// or an [`AdminResponse::Error`]
     ///
     /// [`AdminResponse::CellIdsListed`]: enum.AdminResponse.html#variant.CellIdsListed
-    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
     ListCellIds,
     /// List the ids of all the active (activated) Apps in the conductor.
     /// Takes no arguments.
@@ -126,8 +214,20 @@ pub enum AdminRequest {
     /// or an [`AdminResponse::Error`]
     ///
     /// [`AdminResponse::ActiveAppsListed`]: enum.AdminResponse.html#variant.ActiveAppsListed
-    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
     ListActiveApps,
+    /// List the installed `App`s, active and inactive, with their cells and status.
+    /// If `status_filter` is given then only the `App`s with that status are listed.
+    ///
+    /// Will be responded to with an [`AdminResponse::AppsListed`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::AppsListed`]: enum.AdminResponse.html#variant.AppsListed
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
+    ListApps {
+        /// Only list the apps with this status
+        status_filter: Option<AppStatusFilter>,
+    },
     /// Changes the `App` specified by argument `installed_app_id` from an inactive state to an active state in the conductor,
     /// meaning that Zome calls can now be made and the `App` will be loaded on a reboot of the conductor.
     /// It is likely to want to call this after calling [`AdminRequest::InstallApp`], since a freshly
@@ -138,7 +238,7 @@ pub enum AdminRequest {
     ///
     /// [`AdminRequest::InstallApp`]: enum.AdminRequest.html#variant.InstallApp
     /// [`AdminResponse::AppActivated`]: enum.AdminResponse.html#variant.AppActivated
-    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
     ActivateApp {
         /// The InstalledAppId to activate
         installed_app_id: InstalledAppId,
@@ -151,11 +251,25 @@ pub enum AdminRequest {
     /// or an [`AdminResponse::Error`]
     ///
     /// [`AdminResponse::AppDeactivated`]: enum.AdminResponse.html#variant.AppDeactivated
-    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
     DeactivateApp {
         /// The InstalledAppId to deactivate
         installed_app_id: InstalledAppId,
     },
+    /// Removes the `App` specified by argument `installed_app_id` from the conductor, whether it is
+    /// active or inactive.
+    /// The cells of the `App` are shut down, their agents leave the network and all of their data
+    /// is deleted, unless another installed `App` still uses the cell.
+    ///
+    /// Will be responded to with an [`AdminResponse::AppUninstalled`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::AppUninstalled`]: enum.AdminResponse.html#variant.AppUninstalled
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
+    UninstallApp {
+        /// The InstalledAppId to uninstall
+        installed_app_id: InstalledAppId,
+    },
     /// Open up a new websocket interface at the networking port
     /// (optionally) specified by argument `port` (or using any free port if argument `port` is `None`)
     /// over which you can then use the [`AppRequest`] API.
@@ -167,11 +281,17 @@ pub enum AdminRequest {
     /// or an [`AdminResponse::Error`]
     ///
     /// [`AdminResponse::AppInterfaceAttached`]: enum.AdminResponse.html#variant.AppInterfaceAttached
-    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
     AttachAppInterface {
         /// Optional port, use None to let the
         /// OS choose a free port
         port: Option<u16>,
+        /// The app which the interface makes [`AppRequest::Crypto`] requests
+        /// for. Without an app the interface refuses crypto requests.
+        ///
+        /// [`AppRequest::Crypto`]: enum.AppRequest.html#variant.Crypto
+        #[serde(default)]
+        installed_app_id: Option<InstalledAppId>,
     },
     /// Dump the full state of the `Cell` specified by argument `cell_id`,
     /// including its chain, as a string containing JSON.
@@ -179,7 +299,7 @@ pub enum AdminRequest {
     /// Will be responded to with an [`AdminResponse::StateDumped`]
     /// or an [`AdminResponse::Error`]
     ///
-    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::Error`]: enum.AdminResponse.html#variant.Error
     /// [`AdminResponse::StateDumped`]: enum.AdminResponse.html#variant.StateDumped
     DumpState {
         /// The `CellId` for which to dump state
@@ -271,6 +391,41 @@ pub enum AdminResponse {
     /// [`CellId`]: ../../../holochain_types/cell/struct.CellId.html
     CloneCellCreated(CellId),
 
+    /// The successful response to an [`AdminRequest::DestroyCloneCell`].
+    ///
+    /// [`AdminRequest::DestroyCloneCell`]: enum.AdminRequest.html#variant.DestroyCloneCell
+    CloneCellDestroyed,
+
+    /// The successful response to an [`AdminRequest::ArchiveCloneCell`].
+    ///
+    /// [`AdminRequest::ArchiveCloneCell`]: enum.AdminRequest.html#variant.ArchiveCloneCell
+    CloneCellArchived,
+
+    /// The successful response to an [`AdminRequest::RestoreCloneCell`].
+    ///
+    /// [`AdminRequest::RestoreCloneCell`]: enum.AdminRequest.html#variant.RestoreCloneCell
+    CloneCellRestored,
+
+    /// The successful response to an [`AdminRequest::ListCloneCells`].
+    ///
+    /// [`AdminRequest::ListCloneCells`]: enum.AdminRequest.html#variant.ListCloneCells
+    CloneCellsListed {
+        /// The clones that are running when the app is active
+        clones: Vec<CellId>,
+        /// The clones that have been archived
+        archived: Vec<CellId>,
+    },
+
+    /// The successful response to an [`AdminRequest::GetCellHealth`].
+    ///
+    /// [`AdminRequest::GetCellHealth`]: enum.AdminRequest.html#variant.GetCellHealth
+    CellHealth(CellHealth),
+
+    /// The successful response to an [`AdminRequest::SetAppCryptoPermissions`].
+    ///
+    /// [`AdminRequest::SetAppCryptoPermissions`]: enum.AdminRequest.html#variant.SetAppCryptoPermissions
+    AppCryptoPermissionsSet,
+
     /// The succesful response to an [`AdminRequest::AddAdminInterfaces`].
     ///
     /// It means the `AdminInterface`s have successfully been added
@@ -306,6 +461,13 @@ pub enum AdminResponse {
     /// [`AdminRequest::ListActiveApps`]: enum.AdminRequest.html#variant.ListActiveApps
     ActiveAppsListed(Vec<InstalledAppId>),
 
+    /// The succesful response to an [`AdminRequest::ListApps`].
+    ///
+    /// Contains the info of every installed `App` that matches the status filter
+    ///
+    /// [`AdminRequest::ListApps`]: enum.AdminRequest.html#variant.ListApps
+    AppsListed(Vec<InstalledAppInfo>),
+
     /// The succesful response to an [`AdminRequest::AttachAppInterface`].
     ///
     /// `AppInterfaceApi` successfully attached.
@@ -332,6 +494,13 @@ pub enum AdminResponse {
     /// [`AdminRequest::DeactivateApp`]: enum.AdminRequest.html#variant.DeactivateApp
     AppDeactivated,
 
+    /// The succesful response to an [`AdminRequest::UninstallApp`].
+    ///
+    /// It means the `App` was uninstalled successfully.
+    ///
+    /// [`AdminRequest::UninstallApp`]: enum.AdminRequest.html#variant.UninstallApp
+    AppUninstalled,
+
     /// The succesful response to an [`AdminRequest::DumpState`].
     ///
     /// The result contains a string of serialized JSON data which can be deserialized to access the
@@ -355,6 +524,28 @@ pub enum AdminResponse {
     AgentInfoRequested(Vec<AgentInfoSigned>),
 }
 
+/// The status of the apps to list with [`AdminRequest::ListApps`]
+///
+/// [`AdminRequest::ListApps`]: enum.AdminRequest.html#variant.ListApps
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppStatusFilter {
+    /// Only list active apps
+    Active,
+    /// Only list inactive apps
+    Inactive,
+}
+
+impl AppStatusFilter {
+    /// Does the status pass this filter?
+    pub fn matches(&self, status: &InstalledAppStatus) -> bool {
+        match self {
+            Self::Active => status.is_active(),
+            Self::Inactive => !status.is_active(),
+        }
+    }
+}
+
 /// Error type that goes over the websocket wire.
 /// This intends to be application developer facing
 /// so it should be readable and relevant
@@ -376,6 +567,8 @@ pub enum ExternalApiWireError {
     ActivateApp(String),
     /// The zome call is unauthorized
     ZomeCallUnauthorized(String),
+    /// The app is not allowed to make the crypto request
+    CryptoUnauthorized(String),
 }
 
 impl ExternalApiWireError {
@@ -20,10 +20,23 @@ pub enum AppRequest {
         /// The InstalledAppId for which to get information
         installed_app_id: InstalledAppId,
     },
-    /// Asks the conductor to do some crypto.
+    /// Asks the conductor to sign, encrypt or decrypt some data on behalf of
+    /// the app the interface was attached for with
+    /// [`AdminRequest::AttachAppInterface`]. Interfaces attached without an
+    /// app refuse these requests. See the inner [`CryptoRequest`] for the
+    /// operations.
     ///
-    /// Is currently unimplemented and will return
-    /// an [`AppResponse::Unimplemented`](enum.AppResponse.html#variant.Unimplemented)
+    /// Each operation must first be allowed for the app with
+    /// [`AdminRequest::SetAppCryptoPermissions`].
+    ///
+    /// Will be responded to with an [`AppResponse::Crypto`]
+    /// or an [`AppResponse::Error`]
+    ///
+    /// [`CryptoRequest`]: enum.CryptoRequest.html
+    /// [`AdminRequest::AttachAppInterface`]: enum.AdminRequest.html#variant.AttachAppInterface
+    /// [`AdminRequest::SetAppCryptoPermissions`]: enum.AdminRequest.html#variant.SetAppCryptoPermissions
+    /// [`AppResponse::Crypto`]: enum.AppResponse.html#variant.Crypto
+    /// [`AppResponse::Error`]: enum.AppResponse.html#variant.Error
     Crypto(Box<CryptoRequest>),
     /// Call a zome function. See the inner [`ZomeCall`]
     /// struct to understand the data that must be provided.
@@ -39,10 +52,17 @@ pub enum AppRequest {
     /// DEPRECATED. Use `ZomeCall`.
     ZomeCallInvocation(Box<ZomeCall>),
 
-    /// Update signal subscriptions.
+    /// Update the signal subscription of this connection for an app.
+    ///
+    /// Once a connection has subscribed for any app it only receives the
+    /// signals of the cells of its subscribed apps which pass their filters.
+    /// A connection without subscriptions receives every signal.
     ///
-    /// Is currently unimplemented and will return
-    /// an [`AppResponse::Unimplemented`](enum.AppResponse.html#variant.Unimplemented)
+    /// Will be responded to with an [`AppResponse::SignalSubscriptionUpdated`]
+    /// or an [`AppResponse::Error`]
+    ///
+    /// [`AppResponse::SignalSubscriptionUpdated`]: enum.AppResponse.html#variant.SignalSubscriptionUpdated
+    /// [`AppResponse::Error`]: enum.AppResponse.html#variant.Error
     SignalSubscription(SignalSubscription),
 }
 
@@ -83,6 +103,16 @@ pub enum AppResponse {
 
     /// DEPRECATED. See `ZomeCall`.
     ZomeCallInvocation(Box<ExternIO>),
+
+    /// The successful response to an [`AppRequest::SignalSubscription`].
+    ///
+    /// [`AppRequest::SignalSubscription`]: enum.AppRequest.html#variant.SignalSubscription
+    SignalSubscriptionUpdated,
+
+    /// The successful response to an [`AppRequest::Crypto`].
+    ///
+    /// [`AppRequest::Crypto`]: enum.AppRequest.html#variant.Crypto
+    Crypto(Box<CryptoResponse>),
 }
 
 /// The data provided across an App interface in order to make a zome call
@@ -109,13 +139,86 @@ pub struct ZomeCall {
     pub provenance: AgentPubKey,
 }
 
-#[allow(missing_docs)]
+/// The crypto operations an app can ask the conductor to perform
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case", tag = "type", content = "data")]
 pub enum CryptoRequest {
-    Sign(String),
-    Decrypt(String),
-    Encrypt(String),
+    /// Sign some bytes with the agent key of the app.
+    ///
+    /// Responded to with a [`CryptoResponse::Signed`].
+    ///
+    /// [`CryptoResponse::Signed`]: enum.CryptoResponse.html#variant.Signed
+    Sign {
+        /// The bytes to sign
+        data: Bytes,
+    },
+    /// Encrypt some data from a sender x25519 key in the keystore
+    /// to a recipient x25519 key.
+    /// The sender must be one of the app's registered x25519 keys.
+    ///
+    /// Responded to with a [`CryptoResponse::Encrypted`].
+    ///
+    /// [`CryptoResponse::Encrypted`]: enum.CryptoResponse.html#variant.Encrypted
+    Encrypt {
+        /// The sender and recipient keys and the data to encrypt
+        input: X25519XSalsa20Poly1305Encrypt,
+    },
+    /// Decrypt some data sent by a sender x25519 key to a recipient
+    /// x25519 key in the keystore.
+    /// The recipient must be one of the app's registered x25519 keys.
+    ///
+    /// Responded to with a [`CryptoResponse::Decrypted`].
+    ///
+    /// [`CryptoResponse::Decrypted`]: enum.CryptoResponse.html#variant.Decrypted
+    Decrypt {
+        /// The sender and recipient keys and the data to decrypt
+        input: X25519XSalsa20Poly1305Decrypt,
+    },
+}
+
+impl CryptoRequest {
+    /// Do these permissions allow this request?
+    pub fn is_allowed_by(&self, permissions: &AppCryptoPermissions) -> bool {
+        match self {
+            Self::Sign { .. } => permissions.sign,
+            Self::Encrypt { input } => {
+                permissions.encrypt && permissions.x25519_keys.contains(input.as_sender_ref())
+            }
+            Self::Decrypt { input } => {
+                permissions.decrypt && permissions.x25519_keys.contains(input.as_recipient_ref())
+            }
+        }
+    }
+}
+
+/// The results of the crypto operations of a [`CryptoRequest`]
+///
+/// [`CryptoRequest`]: enum.CryptoRequest.html
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+pub enum CryptoResponse {
+    /// The signature of the data by the agent key of the app
+    Signed(Signature),
+    /// The encrypted data
+    Encrypted(XSalsa20Poly1305EncryptedData),
+    /// The decrypted data, or `None` if it could not be decrypted
+    Decrypted(Option<XSalsa20Poly1305Data>),
+}
+
+/// Which crypto operations an app may ask the conductor to perform
+/// over an app interface. Nothing is allowed by default.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AppCryptoPermissions {
+    /// The app may sign data with its agent key
+    pub sign: bool,
+    /// The app may encrypt data with keystore x25519 keys
+    pub encrypt: bool,
+    /// The app may decrypt data with keystore x25519 keys
+    pub decrypt: bool,
+    /// The keystore x25519 keys registered to the app's agent.
+    /// The app may only encrypt from and decrypt to these keys.
+    #[serde(default)]
+    pub x25519_keys: Vec<X25519PubKey>,
 }
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
@@ -125,12 +228,15 @@ pub struct InstalledAppInfo {
     pub installed_app_id: InstalledAppId,
     /// Info about the Cells installed in this app
     pub cell_data: Vec<InstalledCell>,
-    /// Is this app currently active?
-    pub active: bool,
+    /// The agent key used to install this app
+    pub agent_key: AgentPubKey,
+    /// Is this app currently active, and if not then why not?
+    pub status: InstalledAppStatus,
 }
 
 impl InstalledAppInfo {
-    pub fn from_installed_app(app: &InstalledApp, active: bool) -> Self {
+    /// Collect the info about an installed app with the given status
+    pub fn from_installed_app(app: &InstalledApp, status: InstalledAppStatus) -> Self {
         let installed_app_id = app.installed_app_id().clone();
         let cell_data = app
             .provisioned_cells()
@@ -139,7 +245,45 @@ impl InstalledAppInfo {
         Self {
             installed_app_id,
             cell_data,
-            active,
+            agent_key: app.agent_key().clone(),
+            status,
         }
     }
 }
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+/// Whether an installed app is active
+pub enum InstalledAppStatus {
+    /// The app is active and its cells can be called
+    Active,
+    /// The app is inactive
+    Inactive {
+        /// Why the app is inactive
+        reason: DeactivationReason,
+    },
+}
+
+impl InstalledAppStatus {
+    /// Is the app active?
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Active)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+/// The reason an installed app is inactive
+pub enum DeactivationReason {
+    /// The app was installed but has never been activated
+    NeverActivated,
+    /// The app was deactivated with [`AdminRequest::DeactivateApp`]
+    ///
+    /// [`AdminRequest::DeactivateApp`]: enum.AdminRequest.html#variant.DeactivateApp
+    Normal,
+    /// The app was deactivated because its cells can never be set up
+    Quarantined {
+        /// The error that occurred while setting up the cells
+        error: String,
+    },
+}
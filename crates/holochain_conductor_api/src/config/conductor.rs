@@ -11,6 +11,7 @@ mod dpki_config;
 mod error;
 mod passphrase_service_config;
 pub mod paths;
+mod zome_call_config;
 //mod logger_config;
 //mod signal_config;
 pub use paths::EnvironmentRootPath;
@@ -20,6 +21,7 @@ pub use dpki_config::DpkiConfig;
 //pub use logger_config::LoggerConfig;
 pub use error::*;
 pub use passphrase_service_config::PassphraseServiceConfig;
+pub use zome_call_config::ZomeCallConfig;
 //pub use signal_config::SignalConfig;
 use std::path::Path;
 use std::path::PathBuf;
@@ -56,8 +58,8 @@ pub struct ConductorConfig {
     /// over this websocket.
     pub decryption_service_uri: Option<String>,
 
-    /// Optional DPKI configuration if conductor is using a DPKI app to initalize and manage
-    /// keys for new instances
+    /// Optional DPKI configuration for checking that agent keys are valid.
+    /// If omitted, every key is treated as valid.
     pub dpki: Option<DpkiConfig>,
 
     /// Optional path for keystore directory.  If not specified will use the default provided
@@ -75,6 +77,11 @@ pub struct ConductorConfig {
 
     /// Config options for the network module. Optional.
     pub network: Option<holochain_p2p::kitsune_p2p::KitsuneP2pConfig>,
+
+    /// Limits on zome call duration, including how long zomes may `sleep` for.
+    /// Uses the defaults if omitted.
+    #[serde(default)]
+    pub zome_call: ZomeCallConfig,
     //
     //
     // /// Which signals to emit
@@ -150,6 +157,7 @@ pub mod tests {
                 keystore_path: None,
                 admin_interfaces: None,
                 use_dangerous_test_keystore: false,
+                zome_call: ZomeCallConfig::default(),
             }
         );
     }
@@ -169,8 +177,10 @@ pub mod tests {
       type: cmd
 
     dpki:
-      instance_id: some_id
-      init_params: some_params
+      type: cell
+      installed_app_id: dpki
+      zome_name: keys
+      fn_name: key_state
 
     admin_interfaces:
       - driver:
@@ -189,12 +199,19 @@ pub mod tests {
             proxy_accept_config: reject_all
       tuning_params:
         gossip_loop_iteration_delay_ms: 42
+        gossip_time_window_s: 42
         default_notify_remote_agent_count: 42
         default_notify_timeout_ms: 42
         default_rpc_single_timeout_ms: 42
         default_rpc_multi_remote_agent_count: 42
         default_rpc_multi_timeout_ms: 42
         agent_info_expires_after_ms: 42
+        arc_target_redundancy: 42
+        arc_storage_limit_ops: 42
+
+    zome_call:
+      timeout_ms: 1000
+      max_sleep_ms: 100
 
     "#;
         let result: ConductorConfigResult<ConductorConfig> = config_from_yaml(yaml);
@@ -214,12 +231,15 @@ pub mod tests {
         {
             let mut tuning_params = &mut network_config.tuning_params;
             tuning_params.gossip_loop_iteration_delay_ms = 42;
+            tuning_params.gossip_time_window_s = 42;
             tuning_params.default_notify_remote_agent_count = 42;
             tuning_params.default_notify_timeout_ms = 42;
             tuning_params.default_rpc_single_timeout_ms = 42;
             tuning_params.default_rpc_multi_remote_agent_count = 42;
             tuning_params.default_rpc_multi_timeout_ms = 42;
             tuning_params.agent_info_expires_after_ms = 42;
+            tuning_params.arc_target_redundancy = 42;
+            tuning_params.arc_storage_limit_ops = 42;
         }
         assert_eq!(
             result.unwrap(),
@@ -229,9 +249,10 @@ pub mod tests {
                 signing_service_uri: Some("ws://localhost:9001".into()),
                 encryption_service_uri: Some("ws://localhost:9002".into()),
                 decryption_service_uri: Some("ws://localhost:9003".into()),
-                dpki: Some(DpkiConfig {
-                    instance_id: "some_id".into(),
-                    init_params: "some_params".into()
+                dpki: Some(DpkiConfig::Cell {
+                    installed_app_id: "dpki".into(),
+                    zome_name: "keys".into(),
+                    fn_name: "key_state".into(),
                 }),
                 passphrase_service: Some(PassphraseServiceConfig::Cmd),
                 keystore_path: None,
@@ -239,6 +260,10 @@ pub mod tests {
                     driver: InterfaceDriver::Websocket { port: 1234 }
                 }]),
                 network: Some(network_config),
+                zome_call: ZomeCallConfig {
+                    timeout_ms: 1000,
+                    max_sleep_ms: 100,
+                },
             }
         );
     }
@@ -270,6 +295,7 @@ pub mod tests {
                 keystore_path: Some(PathBuf::from("/path/to/keystore").into()),
                 admin_interfaces: None,
                 use_dangerous_test_keystore: true,
+                zome_call: ZomeCallConfig::default(),
             }
         );
     }
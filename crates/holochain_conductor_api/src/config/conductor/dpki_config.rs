@@ -1,13 +1,31 @@
-// Legacy config that will probably change
-#![allow(missing_docs)]
-
 use serde::Deserialize;
 use serde::Serialize;
+use std::path::PathBuf;
 
-/// Configure which app instance id to treat as the DPKI application handler
-/// as well as what parameters to pass it on its initialization
+/// Where the conductor looks up whether an agent key is valid.
+///
+/// Ops signed by keys which DPKI reports as unknown, or made after the key
+/// was revoked, are rejected by sys validation, and agents with such keys
+/// can't install apps.
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
-pub struct DpkiConfig {
-    pub instance_id: String,
-    pub init_params: String,
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DpkiConfig {
+    /// Ask a DPKI DNA installed as an app on this conductor
+    Cell {
+        /// The app which runs the DPKI DNA in its first cell
+        installed_app_id: String,
+        /// The zome with the key state function
+        zome_name: String,
+        /// The function which takes an `AgentPubKey`
+        /// and returns its `KeyState`
+        fn_name: String,
+    },
+    /// Read the key states from a YAML file with lists of `valid`
+    /// and `revoked` keys. Keys in neither list are unknown.
+    /// A revoked key may be given as a `key` with the RFC 3339 time it was
+    /// `revoked_at`, otherwise all of its ops are rejected.
+    AllowList {
+        /// Path to the file
+        path: PathBuf,
+    },
 }
@@ -0,0 +1,35 @@
+use serde::Deserialize;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Limits on how long zome calls may run for
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct ZomeCallConfig {
+    /// The longest a zome call may take before the conductor gives up on it,
+    /// including any time the zome spends in `sleep`.
+    pub timeout_ms: u64,
+    /// The longest a single call to the `sleep` host function may wait for.
+    pub max_sleep_ms: u64,
+}
+
+impl ZomeCallConfig {
+    /// The zome call timeout as a [Duration]
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+
+    /// The maximum sleep as a [Duration]
+    pub fn max_sleep(&self) -> Duration {
+        Duration::from_millis(self.max_sleep_ms)
+    }
+}
+
+impl Default for ZomeCallConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 60_000,
+            max_sleep_ms: 10_000,
+        }
+    }
+}
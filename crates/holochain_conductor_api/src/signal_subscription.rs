@@ -1,7 +1,11 @@
+use holo_hash::AnyDhtHash;
 use holochain_serialized_bytes::prelude::*;
 use holochain_types::app::InstalledAppId;
+use holochain_types::signal::Signal;
+use holochain_types::signal::SignalKind;
 use holochain_zome_types::cell::CellId;
 use std::collections::HashMap;
+use std::collections::HashSet;
 
 /// Declares updated Signal subscription settings for an App.
 /// This message is part of the AppInterfaceApi
@@ -13,6 +17,26 @@ pub struct SignalSubscription {
     filters: SignalFilterSet,
 }
 
+impl SignalSubscription {
+    /// Constructor
+    pub fn new(installed_app_id: InstalledAppId, filters: SignalFilterSet) -> Self {
+        Self {
+            installed_app_id,
+            filters,
+        }
+    }
+
+    /// Accessor
+    pub fn installed_app_id(&self) -> &InstalledAppId {
+        &self.installed_app_id
+    }
+
+    /// Accessor
+    pub fn filters(&self) -> &SignalFilterSet {
+        &self.filters
+    }
+}
+
 /// Associate a SignalFilter with each Cell in an App.
 /// The filtering can be interpreted as inclusive or exclusive,
 /// depending on the use case.
@@ -46,22 +70,90 @@ impl SignalFilterSet {
     pub fn block_all() -> Self {
         SignalFilterSet::Include(HashMap::new())
     }
-}
 
-/// Specifies fine-grained filter controls for the signals
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
-pub struct SignalFilter;
+    /// The bases this filter set subscribes to integrated op signals about.
+    /// Integrated ops are only signalled at bases which are subscribed to,
+    /// so only an Include set with filters listing their bases subscribes.
+    pub fn integrated_op_bases(&self) -> Vec<AnyDhtHash> {
+        match self {
+            Self::Include(filters) => filters
+                .values()
+                .filter(|filter| {
+                    filter
+                        .kinds
+                        .as_ref()
+                        .map(|kinds| kinds.contains(&SignalKind::OpIntegrated))
+                        .unwrap_or(true)
+                })
+                .filter_map(|filter| filter.bases.as_ref())
+                .flatten()
+                .cloned()
+                .collect(),
+            Self::Exclude(_) => Vec::new(),
+        }
+    }
 
-impl Default for SignalFilter {
-    fn default() -> Self {
-        // The default is no filter
-        Self::empty()
+    /// Does this filter set let the signal through?
+    pub fn allows(&self, signal: &Signal) -> bool {
+        let matches = |filters: &HashMap<CellId, SignalFilter>| {
+            filters
+                .iter()
+                .any(|(cell_id, filter)| signal.is_about_cell(cell_id) && filter.matches(signal))
+        };
+        match self {
+            Self::Include(filters) => matches(filters),
+            Self::Exclude(filters) => !matches(filters),
+        }
     }
 }
 
+/// Specifies fine-grained filter controls for the signals
+#[derive(
+    Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+pub struct SignalFilter {
+    /// Only match signals of these kinds.
+    /// If not set, signals of every kind are matched.
+    #[serde(default)]
+    pub kinds: Option<HashSet<SignalKind>>,
+    /// Only match signals about DHT data at these bases.
+    /// If not set, signals about any basis are matched.
+    /// Signals which are not about DHT data are unaffected.
+    #[serde(default)]
+    pub bases: Option<HashSet<AnyDhtHash>>,
+}
+
 impl SignalFilter {
     /// A passthrough filter which filters nothing
     pub fn empty() -> Self {
-        SignalFilter
+        Self::default()
+    }
+
+    /// A filter which matches signals of the given kinds
+    pub fn kinds<I: IntoIterator<Item = SignalKind>>(kinds: I) -> Self {
+        Self {
+            kinds: Some(kinds.into_iter().collect()),
+            bases: None,
+        }
+    }
+
+    /// Only match signals about DHT data at the given bases
+    pub fn with_bases<I: IntoIterator<Item = AnyDhtHash>>(mut self, bases: I) -> Self {
+        self.bases = Some(bases.into_iter().collect());
+        self
+    }
+
+    /// Does this filter match the signal?
+    pub fn matches(&self, signal: &Signal) -> bool {
+        let kind_matches = self
+            .kinds
+            .as_ref()
+            .map(|kinds| kinds.contains(&signal.kind()))
+            .unwrap_or(true);
+        let basis_matches = match (&self.bases, signal.basis()) {
+            (Some(bases), Some(basis)) => bases.contains(&basis),
+            _ => true,
+        };
+        kind_matches && basis_matches
     }
 }
@@ -68,6 +68,12 @@ pub enum DbName {
     ValidationLimbo,
     /// KVV store to accumulate validation receipts for a published EntryHash
     ValidationReceipts,
+    /// KV store of zome functions scheduled to run later, keyed by the time they are due
+    ScheduledFns,
+    /// KV store of the number of times each usage-limited cap grant has been used
+    CapGrantUses,
+    /// KV store of the warrants held against each agent
+    Warrants,
     /// Single store for all known agents on the network
     Agent,
 }
@@ -99,6 +105,9 @@ impl DbName {
             IntegrationLimbo => Single,
             ValidationLimbo => Single,
             ValidationReceipts => Multi,
+            ScheduledFns => Single,
+            CapGrantUses => Single,
+            Warrants => Single,
             Agent => Single,
         }
     }
@@ -170,6 +179,12 @@ lazy_static! {
     pub static ref VALIDATION_LIMBO: DbKey<SingleStore> = DbKey::new(DbName::ValidationLimbo);
     /// The key to access the ValidationReceipts database
     pub static ref VALIDATION_RECEIPTS: DbKey<MultiStore> = DbKey::new(DbName::ValidationReceipts);
+    /// The key to access the ScheduledFns database
+    pub static ref SCHEDULED_FNS: DbKey<SingleStore> = DbKey::new(DbName::ScheduledFns);
+    /// The key to access the CapGrantUses database
+    pub static ref CAP_GRANT_USES: DbKey<SingleStore> = DbKey::new(DbName::CapGrantUses);
+    /// The key to access the Warrants database
+    pub static ref WARRANTS: DbKey<SingleStore> = DbKey::new(DbName::Warrants);
     /// The key to access the Agent database
     pub static ref AGENT: DbKey<SingleStore> = DbKey::new(DbName::Agent);
 }
@@ -230,6 +245,9 @@ fn register_databases(env: &Rkv, kind: &EnvironmentKind, um: &mut DbMap) -> Data
             register_db(env, um, &*INTEGRATION_LIMBO)?;
             register_db(env, um, &*VALIDATION_LIMBO)?;
             register_db(env, um, &*VALIDATION_RECEIPTS)?;
+            register_db(env, um, &*SCHEDULED_FNS)?;
+            register_db(env, um, &*CAP_GRANT_USES)?;
+            register_db(env, um, &*WARRANTS)?;
         }
         EnvironmentKind::Conductor => {
             register_db(env, um, &*CONDUCTOR_STATE)?;
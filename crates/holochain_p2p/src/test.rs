@@ -485,4 +485,72 @@ mod tests {
         p2p.ghost_actor_shutdown().await.unwrap();
         r_task.await.unwrap();
     }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_holochain_agent_info_verifies() {
+        use holochain_keystore::AgentPubKeyExt;
+        observability::test_run().ok();
+
+        let keystore = holochain_keystore::test_keystore::spawn_test_keystore()
+            .await
+            .unwrap();
+        let dna = newhash!(DnaHash, 's');
+        let agent = holo_hash::AgentPubKey::new_from_pure_entropy(&keystore)
+            .await
+            .unwrap();
+
+        let (p2p, mut evt) = spawn_holochain_p2p(
+            KitsuneP2pConfig::default(),
+            TlsConfig::new_ephemeral().await.unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let (put_send, put_recv) = futures::channel::oneshot::channel();
+        let r_task = tokio::task::spawn(async move {
+            use tokio::stream::StreamExt;
+            let mut put_send = Some(put_send);
+            while let Some(evt) = evt.next().await {
+                use crate::types::event::HolochainP2pEvent::*;
+                match evt {
+                    // sign the way the conductor does
+                    SignNetworkData {
+                        respond,
+                        to_agent,
+                        data,
+                        ..
+                    } => {
+                        let sig = to_agent
+                            .sign_raw(&keystore, &data)
+                            .await
+                            .map_err(HolochainP2pError::other);
+                        respond.r(Ok(async move { sig }.boxed().into()));
+                    }
+                    PutAgentInfoSigned {
+                        respond,
+                        agent_info_signed,
+                        ..
+                    } => {
+                        if let Some(put_send) = put_send.take() {
+                            let _ = put_send.send(agent_info_signed);
+                        }
+                        respond.r(Ok(async move { Ok(()) }.boxed().into()));
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        p2p.join(dna.clone(), agent.clone()).await.unwrap();
+
+        // kitsune only stores our agent info once it verifies
+        let agent_info_signed = put_recv.await.unwrap();
+        assert_eq!(36, agent_info_signed.as_agent_ref().0.len());
+        assert_eq!(agent.get_raw_36(), &agent_info_signed.as_agent_ref().0[..]);
+        let agent_info = agent_info_signed.verify().await.unwrap();
+        assert_eq!(agent_info.as_agent_ref(), agent_info_signed.as_agent_ref());
+
+        p2p.ghost_actor_shutdown().await.unwrap();
+        r_task.await.unwrap();
+    }
 }
@@ -101,6 +101,16 @@ pub fn space_holo_to_kit(d: holo_hash::DnaHash) -> kitsune_p2p::KitsuneSpace {
     d.into_kitsune_raw()
 }
 
+/// Turn a [KitsuneAgent] into an [AgentKey]
+pub fn agent_kit_to_holo(a: kitsune_p2p::KitsuneAgent) -> holo_hash::AgentPubKey {
+    holo_hash::AgentPubKey::from_raw_36(a.into())
+}
+
+/// Turn a [KitsuneSpace] into a [DnaHash]
+pub fn space_kit_to_holo(s: kitsune_p2p::KitsuneSpace) -> holo_hash::DnaHash {
+    holo_hash::DnaHash::from_raw_36(s.into())
+}
+
 pub mod actor;
 pub mod event;
 
@@ -0,0 +1,148 @@
+//! # Capability Grant Use Database Types
+
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::buffer::KvStoreT;
+use holochain_lmdb::db::CAP_GRANT_USES;
+use holochain_lmdb::error::DatabaseResult;
+use holochain_lmdb::prelude::EnvironmentRead;
+use holochain_lmdb::prelude::GetDb;
+use holochain_lmdb::transaction::Writer;
+use holochain_types::prelude::*;
+use shrinkwraprs::Shrinkwrap;
+
+#[derive(Shrinkwrap)]
+#[shrinkwrap(mutable)]
+/// The database of how many times each usage-limited cap grant of a cell has
+/// authorized a zome call.
+///
+/// Grants are keyed by the hash of their entry so a grant that is deleted
+/// and committed again with the same content keeps its count.
+pub struct CapGrantUseStore(pub KvBufFresh<EntryHash, u32>);
+
+impl CapGrantUseStore {
+    /// Create a new cap grant uses db
+    pub fn new(env: EnvironmentRead) -> DatabaseResult<Self> {
+        let db = env.get_db(&*CAP_GRANT_USES)?;
+        Ok(Self(KvBufFresh::new(env, db)))
+    }
+
+    /// The number of times the grant with this entry hash has been used
+    pub fn uses(&self, grant_hash: &EntryHash) -> DatabaseResult<u32> {
+        Ok(self.0.get(grant_hash)?.unwrap_or(0))
+    }
+
+    /// Does the grant with this entry hash still authorize calls at `now`?
+    pub fn allows(
+        &self,
+        grant_hash: &EntryHash,
+        grant: &ZomeCallCapGrant,
+        now: Timestamp,
+    ) -> DatabaseResult<bool> {
+        let uses = match grant.limits.max_uses {
+            // Only grants with a maximum number of uses are counted
+            Some(_) => self.uses(grant_hash)?,
+            None => 0,
+        };
+        Ok(grant.limits.allows(now, uses))
+    }
+
+    /// Count a call authorized by this grant if it has a use left, returning
+    /// whether it had.
+    ///
+    /// The count is checked and written in the given transaction, bypassing
+    /// the scratch space, so calls running at the same time can't use a grant
+    /// more often than it allows. Grants without a maximum number of uses
+    /// are not counted.
+    pub fn try_use(&self, writer: &mut Writer, grant: &ZomeCallCapGrant) -> DatabaseResult<bool> {
+        let max_uses = match grant.limits.max_uses {
+            Some(max_uses) => max_uses,
+            None => return Ok(true),
+        };
+        let grant_hash = EntryHashed::from_content_sync(Entry::CapGrant(grant.clone()))
+            .into_inner()
+            .1;
+        let uses = self.0.store().get(writer, &grant_hash)?.unwrap_or(0);
+        if uses >= max_uses {
+            return Ok(false);
+        }
+        self.0.store().put(writer, &grant_hash, &(uses + 1))?;
+        Ok(true)
+    }
+
+    /// Give back a use of this grant by a call which failed.
+    ///
+    /// Like [CapGrantUseStore::try_use] the count is written in the given
+    /// transaction. Grants without a maximum number of uses are not counted.
+    pub fn refund(&self, writer: &mut Writer, grant: &ZomeCallCapGrant) -> DatabaseResult<()> {
+        if grant.limits.max_uses.is_none() {
+            return Ok(());
+        }
+        let grant_hash = EntryHashed::from_content_sync(Entry::CapGrant(grant.clone()))
+            .into_inner()
+            .1;
+        let uses = self.0.store().get(writer, &grant_hash)?.unwrap_or(0);
+        self.0
+            .store()
+            .put(writer, &grant_hash, &uses.saturating_sub(1))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_lmdb::env::WriteManager;
+    use holochain_lmdb::test_utils::test_cell_env;
+    use std::collections::HashSet;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn uses_are_counted_up_to_the_limit() {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+
+        let grant = ZomeCallCapGrant::new(
+            "tag".into(),
+            CapAccess::Unrestricted,
+            HashSet::new(),
+            CurryPayloads::default(),
+            CapGrantLimits::new(Some(Timestamp(100, 0)), Some(2)),
+        );
+        let grant_hash = EntryHashed::from_content_sync(Entry::CapGrant(grant.clone()))
+            .into_inner()
+            .1;
+
+        let store = CapGrantUseStore::new(env.clone().into()).unwrap();
+        let try_use = || {
+            env.guard()
+                .with_commit(|writer| store.try_use(writer, &grant))
+                .unwrap()
+        };
+        assert!(store.allows(&grant_hash, &grant, Timestamp(10, 0)).unwrap());
+        assert!(try_use());
+        assert_eq!(store.uses(&grant_hash).unwrap(), 1);
+        assert!(store.allows(&grant_hash, &grant, Timestamp(10, 0)).unwrap());
+        assert!(try_use());
+        assert_eq!(store.uses(&grant_hash).unwrap(), 2);
+        assert!(!store.allows(&grant_hash, &grant, Timestamp(10, 0)).unwrap());
+
+        // A used up grant is not counted any further
+        assert!(!try_use());
+        let store = CapGrantUseStore::new(env.clone().into()).unwrap();
+        assert_eq!(store.uses(&grant_hash).unwrap(), 2);
+
+        // A refunded use can be used again
+        env.guard()
+            .with_commit(|writer| store.refund(writer, &grant))
+            .unwrap();
+        assert_eq!(store.uses(&grant_hash).unwrap(), 1);
+        assert!(try_use());
+
+        // Expired grants are never allowed
+        let unused = CapGrantUseStore::new(env.clone().into()).unwrap();
+        let mut expiring = grant.clone();
+        expiring.limits.max_uses = None;
+        assert!(!unused
+            .allows(&grant_hash, &expiring, Timestamp(100, 0))
+            .unwrap());
+    }
+}
@@ -85,6 +85,12 @@ impl ChainSequenceBuf {
         self.current_head.as_ref()
     }
 
+    /// Get the chain head as it was when this buffer was created,
+    /// ignoring any headers added since. None if the chain was empty.
+    pub fn persisted_head(&self) -> Option<&HeaderHash> {
+        self.persisted_head.as_ref()
+    }
+
     /// empty if len is 0
     pub fn is_empty(&self) -> bool {
         self.len() == 0
@@ -31,6 +31,13 @@ pub struct AuthoredDhtOpsValue {
     pub receipt_count: u32,
     /// Time last published, None if never published
     pub last_publish_time: Option<Timestamp>,
+    /// Times the health check has handed this op back to be published again
+    #[serde(default)]
+    pub republish_count: u32,
+    /// The health check has handed this op back to be published once more,
+    /// regardless of its receipts
+    #[serde(default)]
+    pub republish: bool,
 }
 
 impl AuthoredDhtOpsValue {
@@ -40,6 +47,8 @@ impl AuthoredDhtOpsValue {
             op,
             receipt_count: 0,
             last_publish_time: None,
+            republish_count: 0,
+            republish: false,
         }
     }
 }
@@ -91,6 +100,19 @@ pub struct IntegratedDhtOpsValue {
     pub op: DhtOpLight,
     /// Time when the op was integrated
     pub when_integrated: Timestamp,
+    /// The timestamp of the op's header, which is the same for every
+    /// authority so it's what ops are windowed by for gossip.
+    /// None for ops integrated before this was recorded.
+    #[serde(default)]
+    pub header_time: Option<Timestamp>,
+}
+
+impl IntegratedDhtOpsValue {
+    /// The time this op is gossiped at: its header's timestamp,
+    /// or when it was integrated if that wasn't recorded
+    pub fn gossip_time(&self) -> Timestamp {
+        self.header_time.unwrap_or(self.when_integrated)
+    }
 }
 
 /// A type for storing in databases that only need the hashes.
@@ -117,8 +139,8 @@ impl IntegratedDhtOpsBuf {
     }
 
     /// Get ops that match optional queries:
-    /// - from a time (Inclusive)
-    /// - to a time (Exclusive)
+    /// - from a gossip time (Inclusive)
+    /// - to a gossip time (Exclusive)
     /// - match a dht location
     pub fn query<'r, R: Readable>(
         &'r self,
@@ -137,12 +159,12 @@ impl IntegratedDhtOpsBuf {
                 .iter(r)?
                 .map(move |(k, v)| Ok((DhtOpHash::from_raw_39_panicky(k.to_vec()), v)))
                 .filter_map(move |(k, v)| match from {
-                    Some(time) if v.when_integrated >= time => Ok(Some((k, v))),
+                    Some(time) if v.gossip_time() >= time => Ok(Some((k, v))),
                     None => Ok(Some((k, v))),
                     _ => Ok(None),
                 })
                 .filter_map(move |(k, v)| match to {
-                    Some(time) if v.when_integrated < time => Ok(Some((k, v))),
+                    Some(time) if v.gossip_time() < time => Ok(Some((k, v))),
                     None => Ok(Some((k, v))),
                     _ => Ok(None),
                 })
@@ -193,6 +215,7 @@ mod tests {
                 validation_status: ValidationStatus::Valid,
                 op: DhtOpLight::RegisterAgentActivity(fixt!(HeaderHash), basis.next().unwrap()),
                 when_integrated: when_integrated.into(),
+                header_time: None,
             });
 
         // Put them in the db
@@ -290,4 +313,45 @@ mod tests {
             assert_eq!(r.len(), 3);
         }
     }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_query_by_header_time() {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let env_ref = env.guard();
+
+        // Integrated now but authored long ago
+        let now = Utc::now();
+        let authored = now - Duration::hours(100);
+        let value = IntegratedDhtOpsValue {
+            validation_status: ValidationStatus::Valid,
+            op: DhtOpLight::RegisterAgentActivity(fixt!(HeaderHash), fixt!(AnyDhtHash)),
+            when_integrated: now.into(),
+            header_time: Some(authored.into()),
+        };
+        {
+            let mut buf = IntegratedDhtOpsBuf::new(env.clone().into()).unwrap();
+            buf.put(fixt!(DhtOpHash), value.clone()).unwrap();
+            env_ref
+                .with_commit(|writer| buf.flush_to_txn(writer))
+                .unwrap();
+        }
+
+        let reader = env_ref.reader().unwrap();
+        let buf = IntegratedDhtOpsBuf::new(env.clone().into()).unwrap();
+        let query = |from: chrono::DateTime<Utc>, to: chrono::DateTime<Utc>| {
+            buf.query(&reader, Some(from.into()), Some(to.into()), None)
+                .unwrap()
+                .map(|(_, v)| Ok(v))
+                .collect::<Vec<_>>()
+                .unwrap()
+        };
+        // The op is in the window of its header
+        assert_eq!(
+            query(authored - Duration::hours(1), authored + Duration::hours(1)),
+            vec![value]
+        );
+        // and not the window it was integrated in
+        assert!(query(now - Duration::hours(1), now + Duration::hours(1)).is_empty());
+    }
 }
@@ -32,6 +32,7 @@
 //! source: https://textik.com/#d7907793784e17e9
 //! ```
 
+pub mod cap_grant_use_db;
 #[allow(missing_docs)]
 pub mod chain_sequence;
 pub mod dht_op_integration;
@@ -40,10 +41,12 @@ pub mod element_buf;
 pub mod metadata;
 #[allow(missing_docs)]
 pub mod prelude;
+pub mod scheduled_fn_db;
 #[allow(missing_docs)]
 pub mod source_chain;
 pub mod validation_db;
 pub mod validation_receipts_db;
+pub mod warrant_db;
 #[allow(missing_docs)]
 pub mod wasm;
 pub mod workspace;
@@ -929,7 +929,7 @@ where
                 let diff = observed
                     .hash
                     .into_iter()
-                    .filter(|h| prev_observed.hash.contains(h))
+                    .filter(|h| !prev_observed.hash.contains(h))
                     .collect::<Vec<_>>();
                 prev_observed.hash.extend(diff);
 
@@ -163,3 +163,52 @@ async fn check_large_seq_queries() {
         &[(1, h2_hash), (256, h1_hash)]
     );
 }
+
+#[tokio::test(threaded_scheduler)]
+async fn forks_are_recorded() {
+    let (_te, mut meta_buf, mut h1, mut h2, agent_pubkey) = setup();
+    h1.header_seq = 0;
+    h2.header_seq = 0;
+    let h1_hash = HeaderHash::with_data_sync(&Header::Create(h1.clone()));
+    let h2_hash = HeaderHash::with_data_sync(&Header::Create(h2.clone()));
+
+    meta_buf
+        .register_activity(&h1.into(), ValidationStatus::Valid)
+        .unwrap();
+    meta_buf
+        .register_activity(&h2.into(), ValidationStatus::Valid)
+        .unwrap();
+
+    match meta_buf.get_activity_status(&agent_pubkey).unwrap() {
+        Some(ChainStatus::Forked(fork)) => {
+            assert_eq!(fork.fork_seq, 0);
+            let mut forked = vec![fork.first_header, fork.second_header];
+            forked.sort();
+            let mut expected = vec![h1_hash.clone(), h2_hash.clone()];
+            expected.sort();
+            assert_eq!(forked, expected);
+        }
+        status => panic!("Expected a forked chain but got {:?}", status),
+    }
+
+    // Observing both headers at the same position keeps both
+    for hash in vec![h1_hash.clone(), h2_hash.clone(), h1_hash.clone()] {
+        meta_buf
+            .register_activity_observed(
+                &agent_pubkey,
+                HighestObserved {
+                    header_seq: 0,
+                    hash: vec![hash],
+                },
+            )
+            .unwrap();
+    }
+    let observed = meta_buf.get_activity_observed(&agent_pubkey).unwrap();
+    assert_eq!(
+        observed,
+        Some(HighestObserved {
+            header_seq: 0,
+            hash: vec![h1_hash, h2_hash],
+        })
+    );
+}
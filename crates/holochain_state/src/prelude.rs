@@ -1,10 +1,13 @@
+pub use crate::cap_grant_use_db::*;
 pub use crate::chain_sequence::*;
 pub use crate::dht_op_integration::*;
 pub use crate::element_buf::*;
 pub use crate::metadata::*;
+pub use crate::scheduled_fn_db::*;
 pub use crate::source_chain::*;
 pub use crate::validation_db::*;
 pub use crate::validation_receipts_db::*;
+pub use crate::warrant_db::*;
 pub use crate::wasm::*;
 pub use crate::workspace::*;
 pub use crate::*;
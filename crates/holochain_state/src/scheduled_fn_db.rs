@@ -0,0 +1,173 @@
+//! # Scheduled Function Database Types
+
+use fallible_iterator::FallibleIterator;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::SCHEDULED_FNS;
+use holochain_lmdb::error::DatabaseResult;
+use holochain_lmdb::key::BufKey;
+use holochain_lmdb::prelude::EnvironmentRead;
+use holochain_lmdb::prelude::GetDb;
+use holochain_lmdb::prelude::Readable;
+use holochain_serialized_bytes::prelude::*;
+use holochain_types::prelude::*;
+use shrinkwraprs::Shrinkwrap;
+use std::convert::TryFrom;
+use std::convert::TryInto;
+
+/// Length of the hash of the [ScheduledFn] in a [ScheduledFnKey]
+const SCHEDULED_FN_HASH_LEN: usize = 32;
+/// Total length of a [ScheduledFnKey]
+const SCHEDULED_FN_KEY_LEN: usize = TS_SIZE + SCHEDULED_FN_HASH_LEN;
+
+#[derive(Shrinkwrap)]
+#[shrinkwrap(mutable)]
+/// The database of zome functions waiting for their scheduled time
+pub struct ScheduledFnStore(pub KvBufFresh<ScheduledFnKey, ScheduledFn>);
+
+/// A zome function that has been scheduled to run later
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ScheduledFn {
+    /// The zome that scheduled the function and that it will be called in
+    pub zome_name: ZomeName,
+    /// The function to call
+    pub fn_name: FunctionName,
+    /// The input to the function
+    pub payload: ExternIO,
+    /// The function should not be called before this time
+    pub due: Timestamp,
+}
+
+/// Key to the scheduled fns db.
+///
+/// The due time comes first so that iterating the db visits the functions in
+/// the order they are due. The hash of the scheduled fn follows so that
+/// several functions can be due at the same time.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ScheduledFnKey([u8; SCHEDULED_FN_KEY_LEN]);
+
+impl ScheduledFnKey {
+    /// The time this scheduled fn is due
+    pub fn due(&self) -> Timestamp {
+        TimestampKey::from(&self.0[..TS_SIZE]).into()
+    }
+}
+
+impl TryFrom<&ScheduledFn> for ScheduledFnKey {
+    type Error = SerializedBytesError;
+    fn try_from(scheduled_fn: &ScheduledFn) -> Result<Self, Self::Error> {
+        let bytes = holochain_serialized_bytes::encode(scheduled_fn)?;
+        let hash = holo_hash::encode::blake2b_256(&bytes);
+        let mut key = [0; SCHEDULED_FN_KEY_LEN];
+        key[..TS_SIZE].copy_from_slice(TimestampKey::from(scheduled_fn.due).as_ref());
+        key[TS_SIZE..].copy_from_slice(&hash);
+        Ok(Self(key))
+    }
+}
+
+impl AsRef<[u8]> for ScheduledFnKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl BufKey for ScheduledFnKey {
+    fn from_key_bytes_or_friendly_panic(bytes: &[u8]) -> Self {
+        Self(bytes.try_into().unwrap_or_else(|_| {
+            panic!(
+                "Holochain detected database corruption.\n\nInvalid ScheduledFnKey: expected {} bytes but got {}",
+                SCHEDULED_FN_KEY_LEN,
+                bytes.len()
+            )
+        }))
+    }
+}
+
+impl ScheduledFnStore {
+    /// Create a new scheduled fns db
+    pub fn new(env: EnvironmentRead) -> DatabaseResult<Self> {
+        let db = env.get_db(&*SCHEDULED_FNS)?;
+        Ok(Self(KvBufFresh::new(env, db)))
+    }
+
+    /// Add a function to the schedule
+    pub fn schedule(&mut self, scheduled_fn: ScheduledFn) -> DatabaseResult<()> {
+        let key = (&scheduled_fn).try_into()?;
+        self.0.put(key, scheduled_fn)
+    }
+
+    /// All the functions that are due at or before `now`, in the order
+    /// they became due
+    pub fn due<R: Readable>(
+        &self,
+        r: &R,
+        now: Timestamp,
+    ) -> DatabaseResult<Vec<(ScheduledFnKey, ScheduledFn)>> {
+        self.0
+            .iter(r)?
+            .map(|(k, v)| Ok((ScheduledFnKey::from_key_bytes_or_friendly_panic(k), v)))
+            .take_while(|(k, _)| Ok(k.due() <= now))
+            .collect()
+    }
+
+    /// The time the next function is due, if anything is scheduled
+    pub fn next_due<R: Readable>(&self, r: &R) -> DatabaseResult<Option<Timestamp>> {
+        Ok(self
+            .0
+            .iter(r)?
+            .next()?
+            .map(|(k, _)| ScheduledFnKey::from_key_bytes_or_friendly_panic(k).due()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_lmdb::buffer::BufferedStore;
+    use holochain_lmdb::env::ReadManager;
+    use holochain_lmdb::env::WriteManager;
+    use holochain_lmdb::test_utils::test_cell_env;
+
+    fn scheduled_fn(fn_name: &str, due: Timestamp) -> ScheduledFn {
+        ScheduledFn {
+            zome_name: "zome".into(),
+            fn_name: fn_name.into(),
+            payload: ExternIO::encode(()).unwrap(),
+            due,
+        }
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn scheduled_fns_are_due_in_order() {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+
+        let mut store = ScheduledFnStore::new(env.clone().into()).unwrap();
+        store.schedule(scheduled_fn("c", Timestamp(30, 0))).unwrap();
+        store.schedule(scheduled_fn("a", Timestamp(10, 0))).unwrap();
+        store.schedule(scheduled_fn("b", Timestamp(20, 5))).unwrap();
+        // Same time as another fn must not overwrite it
+        store.schedule(scheduled_fn("d", Timestamp(20, 5))).unwrap();
+
+        env.guard()
+            .with_commit(|writer| store.flush_to_txn_ref(writer))
+            .unwrap();
+
+        let store = ScheduledFnStore::new(env.clone().into()).unwrap();
+        let env_ref = env.guard();
+        let reader = env_ref.reader().unwrap();
+
+        assert_eq!(store.next_due(&reader).unwrap(), Some(Timestamp(10, 0)));
+
+        let due = store.due(&reader, Timestamp(20, 5)).unwrap();
+        let mut names = due
+            .iter()
+            .map(|(_, f)| f.fn_name.0.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(names[0], "a");
+        names[1..].sort();
+        assert_eq!(names, vec!["a", "b", "d"]);
+        assert_eq!(due[0].0.due(), Timestamp(10, 0));
+
+        assert!(store.due(&reader, Timestamp(5, 0)).unwrap().is_empty());
+    }
+}
@@ -37,6 +37,10 @@ impl SourceChain {
         self.0.chain_head().ok_or(SourceChainError::ChainEmpty)
     }
 
+    pub fn persisted_head(&self) -> SourceChainResult<&HeaderHash> {
+        self.0.persisted_head().ok_or(SourceChainError::ChainEmpty)
+    }
+
     pub fn new(env: EnvironmentRead) -> DatabaseResult<Self> {
         Ok(SourceChainBuf::new(env)?.into())
     }
@@ -91,11 +95,16 @@ impl SourceChain {
     /// NB: [B-01676] the entry must be persisted for this to work. Once we have a
     /// proper capability index DB, OR a proper iterator that respects the
     /// scratch space, that will no longer be the case.
+    ///
+    /// Grants that have expired at `now` or have been used up according to
+    /// `uses` are not valid.
     pub fn valid_cap_grant(
         &self,
         check_function: &GrantedFunction,
         check_agent: &AgentPubKey,
         check_secret: Option<&CapSecret>,
+        now: Timestamp,
+        uses: &CapGrantUseStore,
     ) -> SourceChainResult<Option<CapGrant>> {
         // most calls for most apps are going to be the local agent calling itself locally
         // for this case we want to short circuit without iterating the whole source chain
@@ -182,6 +191,13 @@ impl SourceChain {
             .filter(|entry| {
                 Ok(live_cap_grants.contains(entry.as_hash()))
             })
+            // filter out grants that have expired or been used up
+            .filter(|entry| {
+                Ok(match entry.as_content() {
+                    Entry::CapGrant(grant) => uses.allows(entry.as_hash(), grant, now)?,
+                    _ => true,
+                })
+            })
             .filter_map(|entry| Ok(entry.as_cap_grant()))
             // filter down to only the grants for this function
             .filter(|grant| {
@@ -298,6 +314,60 @@ impl SourceChain {
             })
             .collect()
     }
+
+    /// All the live capability grants on this chain that pass the query.
+    pub fn cap_grants(&self, query: &CapQuery) -> SourceChainResult<Vec<CapGrantInfo>> {
+        Ok(self
+            .live_elements(EntryType::CapGrant)?
+            .into_iter()
+            .filter_map(|element| match element.entry().as_option() {
+                Some(Entry::CapGrant(grant)) if query.check(&grant.tag) => Some(CapGrantInfo {
+                    header_hash: element.header_address().clone(),
+                    grant: grant.clone(),
+                }),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// All the live capability claims on this chain that pass the query.
+    pub fn cap_claims(&self, query: &CapQuery) -> SourceChainResult<Vec<CapClaimInfo>> {
+        Ok(self
+            .live_elements(EntryType::CapClaim)?
+            .into_iter()
+            .filter_map(|element| match element.entry().as_option() {
+                Some(Entry::CapClaim(claim)) if query.check(claim.tag()) => Some(CapClaimInfo {
+                    header_hash: element.header_address().clone(),
+                    claim: claim.clone(),
+                }),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// The elements of the given entry type, with their entries, that have not
+    /// been updated or deleted by a later header on this chain.
+    fn live_elements(&self, entry_type: EntryType) -> SourceChainResult<Vec<Element>> {
+        let deletes = self.query(&ChainQueryFilter::new().header_type(HeaderType::Delete))?;
+        let elements = self.query(
+            &ChainQueryFilter::new()
+                .entry_type(entry_type)
+                .include_entries(true),
+        )?;
+        let references: HashSet<HeaderHash> = deletes
+            .iter()
+            .chain(elements.iter())
+            .filter_map(|element| match element.header() {
+                Header::Update(update) => Some(update.original_header_address.clone()),
+                Header::Delete(delete) => Some(delete.deletes_address.clone()),
+                _ => None,
+            })
+            .collect();
+        Ok(elements
+            .into_iter()
+            .filter(|element| !references.contains(element.header_address()))
+            .collect())
+    }
 }
 
 impl From<SourceChainBuf> for SourceChain {
@@ -334,12 +404,19 @@ pub mod tests {
         let secret = Some(CapSecretFixturator::new(Unpredictable).next().unwrap());
         let access = CapAccess::from(secret.unwrap());
 
-        // @todo curry
-        let _curry = CurryPayloadsFixturator::new(Empty).next().unwrap();
+        let now = timestamp::now();
+        let uses = CapGrantUseStore::new(env.clone().into())?;
+        let curry = CurryPayloadsFixturator::new(Empty).next().unwrap();
         let function: GrantedFunction = ("foo".into(), "bar".into());
         let mut functions: GrantedFunctions = HashSet::new();
         functions.insert(function.clone());
-        let grant = ZomeCallCapGrant::new("tag".into(), access.clone(), functions.clone());
+        let grant = ZomeCallCapGrant::new(
+            "tag".into(),
+            access.clone(),
+            functions.clone(),
+            curry.clone(),
+            CapGrantLimits::default(),
+        );
         let mut agents = AgentPubKeyFixturator::new(Predictable);
         let alice = agents.next().unwrap();
         let bob = agents.next().unwrap();
@@ -353,13 +430,13 @@ pub mod tests {
         {
             let chain = SourceChain::new(env.clone().into())?;
             assert_eq!(
-                chain.valid_cap_grant(&function, &alice, secret.as_ref())?,
+                chain.valid_cap_grant(&function, &alice, secret.as_ref(), now, &uses)?,
                 Some(CapGrant::ChainAuthor(alice.clone())),
             );
 
             // bob should not match anything as the secret hasn't been committed yet
             assert_eq!(
-                chain.valid_cap_grant(&function, &bob, secret.as_ref())?,
+                chain.valid_cap_grant(&function, &bob, secret.as_ref(), now, &uses)?,
                 None
             );
         }
@@ -385,16 +462,26 @@ pub mod tests {
             // alice should find her own authorship with higher priority than the committed grant
             // even if she passes in the secret
             assert_eq!(
-                chain.valid_cap_grant(&function, &alice, secret.as_ref())?,
+                chain.valid_cap_grant(&function, &alice, secret.as_ref(), now, &uses)?,
                 Some(CapGrant::ChainAuthor(alice.clone())),
             );
 
             // bob should be granted with the committed grant as it matches the secret he passes to
             // alice at runtime
             assert_eq!(
-                chain.valid_cap_grant(&function, &bob, secret.as_ref())?,
+                chain.valid_cap_grant(&function, &bob, secret.as_ref(), now, &uses)?,
                 Some(grant.clone().into())
             );
+
+            // the grant is live and can be found by its tag
+            assert_eq!(
+                chain.cap_grants(&CapQuery::tag("tag".into()))?,
+                vec![CapGrantInfo {
+                    header_hash: original_header_address.clone(),
+                    grant: grant.clone(),
+                }]
+            );
+            assert!(chain.cap_grants(&CapQuery::tag("other".into()))?.is_empty());
         }
 
         // let's roll the secret and assign the grant to bob specifically
@@ -402,7 +489,13 @@ pub mod tests {
         assignees.insert(bob.clone());
         let updated_secret = Some(CapSecretFixturator::new(Unpredictable).next().unwrap());
         let updated_access = CapAccess::from((updated_secret.clone().unwrap(), assignees));
-        let updated_grant = ZomeCallCapGrant::new("tag".into(), updated_access.clone(), functions);
+        let updated_grant = ZomeCallCapGrant::new(
+            "tag".into(),
+            updated_access.clone(),
+            functions,
+            curry,
+            CapGrantLimits::default(),
+        );
 
         let (updated_header_hash, updated_entry_hash) = {
             let mut chain = SourceChain::new(env.clone().into())?;
@@ -427,22 +520,31 @@ pub mod tests {
             // alice should find her own authorship with higher priority than the committed grant
             // even if she passes in the secret
             assert_eq!(
-                chain.valid_cap_grant(&function, &alice, secret.as_ref())?,
+                chain.valid_cap_grant(&function, &alice, secret.as_ref(), now, &uses)?,
                 Some(CapGrant::ChainAuthor(alice.clone())),
             );
             assert_eq!(
-                chain.valid_cap_grant(&function, &alice, updated_secret.as_ref())?,
+                chain.valid_cap_grant(&function, &alice, updated_secret.as_ref(), now, &uses)?,
                 Some(CapGrant::ChainAuthor(alice.clone())),
             );
 
             // bob MUST provide the updated secret as the old one is invalidated by the new one
             assert_eq!(
-                chain.valid_cap_grant(&function, &bob, secret.as_ref())?,
+                chain.valid_cap_grant(&function, &bob, secret.as_ref(), now, &uses)?,
                 None
             );
             assert_eq!(
-                chain.valid_cap_grant(&function, &bob, updated_secret.as_ref())?,
-                Some(updated_grant.into())
+                chain.valid_cap_grant(&function, &bob, updated_secret.as_ref(), now, &uses)?,
+                Some(updated_grant.clone().into())
+            );
+
+            // only the updated grant is live
+            assert_eq!(
+                chain.cap_grants(&CapQuery::all())?,
+                vec![CapGrantInfo {
+                    header_hash: updated_header_hash.clone(),
+                    grant: updated_grant,
+                }]
             );
         }
 
@@ -462,25 +564,148 @@ pub mod tests {
             let chain = SourceChain::new(env.clone().into())?;
             // alice should find her own authorship
             assert_eq!(
-                chain.valid_cap_grant(&function, &alice, secret.as_ref())?,
+                chain.valid_cap_grant(&function, &alice, secret.as_ref(), now, &uses)?,
                 Some(CapGrant::ChainAuthor(alice.clone())),
             );
             assert_eq!(
-                chain.valid_cap_grant(&function, &alice, updated_secret.as_ref())?,
+                chain.valid_cap_grant(&function, &alice, updated_secret.as_ref(), now, &uses)?,
                 Some(CapGrant::ChainAuthor(alice)),
             );
 
             // bob has no access
             assert_eq!(
-                chain.valid_cap_grant(&function, &bob, secret.as_ref())?,
+                chain.valid_cap_grant(&function, &bob, secret.as_ref(), now, &uses)?,
                 None
             );
             assert_eq!(
-                chain.valid_cap_grant(&function, &bob, updated_secret.as_ref())?,
+                chain.valid_cap_grant(&function, &bob, updated_secret.as_ref(), now, &uses)?,
                 None
             );
+
+            // no grants are live
+            assert!(chain.cap_grants(&CapQuery::all())?.is_empty());
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_cap_grant_limits() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let secret = Some(CapSecretFixturator::new(Unpredictable).next().unwrap());
+        let function: GrantedFunction = ("foo".into(), "bar".into());
+        let mut functions: GrantedFunctions = HashSet::new();
+        functions.insert(function.clone());
+        let grant = ZomeCallCapGrant::new(
+            "tag".into(),
+            CapAccess::from(secret.clone().unwrap()),
+            functions,
+            CurryPayloads::default(),
+            CapGrantLimits::new(Some(Timestamp(100, 0)), Some(1)),
+        );
+        let mut agents = AgentPubKeyFixturator::new(Predictable);
+        let alice = agents.next().unwrap();
+        let bob = agents.next().unwrap();
+        {
+            let mut store = SourceChainBuf::new(env.clone().into())?;
+            store.genesis(fake_dna_hash(1), alice.clone(), None).await?;
+            env.guard()
+                .with_commit(|writer| store.flush_to_txn(writer))?;
+        }
+        {
+            let mut chain = SourceChain::new(env.clone().into())?;
+            let (entry, entry_hash) =
+                EntryHashed::from_content_sync(Entry::CapGrant(grant.clone())).into_inner();
+            let header_builder = builder::Create {
+                entry_type: EntryType::CapGrant,
+                entry_hash,
+            };
+            chain.put(header_builder, Some(entry)).await?;
+            env.guard()
+                .with_commit(|writer| chain.flush_to_txn(writer))?;
         }
 
+        let chain = SourceChain::new(env.clone().into())?;
+        let uses = CapGrantUseStore::new(env.clone().into())?;
+
+        // the grant is valid before it expires
+        assert_eq!(
+            chain.valid_cap_grant(&function, &bob, secret.as_ref(), Timestamp(10, 0), &uses)?,
+            Some(grant.clone().into())
+        );
+        // but not from the expiry time on
+        assert_eq!(
+            chain.valid_cap_grant(&function, &bob, secret.as_ref(), Timestamp(100, 0), &uses)?,
+            None
+        );
+
+        // the grant is not valid once it has been used up
+        assert!(env
+            .guard()
+            .with_commit(|writer| uses.try_use(writer, &grant))?);
+        assert_eq!(
+            chain.valid_cap_grant(&function, &bob, secret.as_ref(), Timestamp(10, 0), &uses)?,
+            None
+        );
+
+        // limits never apply to the author
+        assert_eq!(
+            chain.valid_cap_grant(&function, &alice, None, Timestamp(100, 0), &uses)?,
+            Some(CapGrant::ChainAuthor(alice.clone()))
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_cap_claims() -> SourceChainResult<()> {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let mut agents = AgentPubKeyFixturator::new(Predictable);
+        let alice = agents.next().unwrap();
+        let bob = agents.next().unwrap();
+        let claim = CapClaim::new(
+            "tag".into(),
+            bob.clone(),
+            CapSecretFixturator::new(Unpredictable).next().unwrap(),
+        );
+        let other_claim = CapClaim::new(
+            "other".into(),
+            bob,
+            CapSecretFixturator::new(Unpredictable).next().unwrap(),
+        );
+
+        let mut header_hashes = vec![];
+        {
+            let mut store = SourceChainBuf::new(env.clone().into())?;
+            store.genesis(fake_dna_hash(1), alice, None).await?;
+            let mut chain = SourceChain::from(store);
+            for claim in vec![claim.clone(), other_claim] {
+                let (entry, entry_hash) =
+                    EntryHashed::from_content_sync(Entry::CapClaim(claim)).into_inner();
+                let header_builder = builder::Create {
+                    entry_type: EntryType::CapClaim,
+                    entry_hash,
+                };
+                header_hashes.push(chain.put(header_builder, Some(entry)).await?);
+            }
+            env.guard()
+                .with_commit(|writer| chain.flush_to_txn(writer))?;
+        }
+
+        let chain = SourceChain::new(env.clone().into())?;
+        assert_eq!(chain.cap_claims(&CapQuery::all())?.len(), 2);
+        assert_eq!(
+            chain.cap_claims(&CapQuery::tag("tag".into()))?,
+            vec![CapClaimInfo {
+                header_hash: header_hashes[0].clone(),
+                claim,
+            }]
+        );
+        // claims are never grants
+        assert!(chain.cap_grants(&CapQuery::all())?.is_empty());
+
         Ok(())
     }
 
@@ -63,6 +63,11 @@ impl SourceChainBuf {
         self.sequence.chain_head()
     }
 
+    /// The chain head before anything was written to this buffer
+    pub fn persisted_head(&self) -> Option<&HeaderHash> {
+        self.sequence.persisted_head()
+    }
+
     /// true if len is 0
     pub fn is_empty(&self) -> bool {
         self.len() == 0
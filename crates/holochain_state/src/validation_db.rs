@@ -37,6 +37,8 @@ pub struct ValidationLimboValue {
     pub num_tries: u32,
     /// The agent that sent you this op
     pub from_agent: Option<AgentPubKey>,
+    /// Whether the author asked for a validation receipt once the op is valid
+    pub send_receipt: bool,
 }
 
 /// The status of a [DhtOp] in limbo
@@ -50,6 +52,10 @@ pub enum ValidationLimboStatus {
     SysValidated,
     /// Is waiting for dependencies so the op can proceed to app validation
     AwaitingAppDeps(Vec<AnyDhtHash>),
+    /// Is waiting for DPKI to say whether the author key is valid.
+    /// The wait is on this conductor and not the op, so these ops are
+    /// never abandoned.
+    AwaitingAuthorKey,
 }
 
 impl ValidationLimboStore {
@@ -0,0 +1,267 @@
+//! # Warrant Database Types
+//!
+//! Warrants are issued by authorities when validation finds that an agent's
+//! chain is invalid or forked. They are kept here, keyed by the agent they
+//! are against, so they can be served alongside that agent's activity.
+
+use holo_hash::AgentPubKey;
+use holochain_keystore::AgentPubKeyExt;
+use holochain_keystore::KeystoreSender;
+use holochain_lmdb::buffer::KvBufFresh;
+use holochain_lmdb::db::WARRANTS;
+use holochain_lmdb::error::DatabaseResult;
+use holochain_lmdb::prelude::EnvironmentRead;
+use holochain_lmdb::prelude::GetDb;
+use holochain_types::prelude::*;
+use shrinkwraprs::Shrinkwrap;
+
+/// The most invalid op claims held against a single agent.
+/// Once this is reached the oldest claim is dropped for the new one.
+/// Chain fork proofs are always held and don't count towards this.
+pub const MAX_CLAIMS_PER_AGENT: usize = 16;
+
+#[derive(Shrinkwrap)]
+#[shrinkwrap(mutable)]
+/// The database of warrants held against each agent
+pub struct WarrantStore(pub KvBufFresh<AgentPubKey, Vec<SignedWarrant>>);
+
+impl WarrantStore {
+    /// Create a new warrants db
+    pub fn new(env: EnvironmentRead) -> DatabaseResult<Self> {
+        let db = env.get_db(&*WARRANTS)?;
+        Ok(Self(KvBufFresh::new(env, db)))
+    }
+
+    /// All the warrants held against this agent
+    pub fn warrants(&self, agent: &AgentPubKey) -> DatabaseResult<Vec<SignedWarrant>> {
+        Ok(self.0.get(agent)?.unwrap_or_default())
+    }
+
+    /// Hold a warrant against its author.
+    /// A warrant with the same proof as one already held is ignored.
+    /// Only [`MAX_CLAIMS_PER_AGENT`] invalid op claims are held, dropping
+    /// the oldest claim, so claims can never push out a chain fork proof.
+    pub fn add(&mut self, warrant: SignedWarrant) -> DatabaseResult<()> {
+        let author = warrant.warrant.proof.author().clone();
+        let mut warrants = self.warrants(&author)?;
+        if warrants
+            .iter()
+            .any(|w| w.warrant.proof == warrant.warrant.proof)
+        {
+            return Ok(());
+        }
+        if is_claim(&warrant) {
+            let claims = warrants.iter().filter(|w| is_claim(w)).count();
+            if claims >= MAX_CLAIMS_PER_AGENT {
+                let mut excess = claims + 1 - MAX_CLAIMS_PER_AGENT;
+                warrants.retain(|w| {
+                    if excess > 0 && is_claim(w) {
+                        excess -= 1;
+                        false
+                    } else {
+                        true
+                    }
+                });
+            }
+        }
+        warrants.push(warrant);
+        self.0.put(author, warrants)
+    }
+
+    /// Issue a warrant with this proof as the issuer and hold it
+    pub async fn issue(
+        &mut self,
+        proof: WarrantProof,
+        issuer: AgentPubKey,
+        keystore: &KeystoreSender,
+    ) -> DatabaseResult<()> {
+        let warrant = issue_warrant(proof, issuer, keystore).await?;
+        self.add(warrant)
+    }
+}
+
+/// An invalid op warrant is only the issuer's claim, unlike a chain fork
+/// which the warrant itself proves
+fn is_claim(warrant: &SignedWarrant) -> bool {
+    matches!(warrant.warrant.proof, WarrantProof::InvalidOp { .. })
+}
+
+/// Create a warrant with this proof and sign it as the issuer
+pub async fn issue_warrant(
+    proof: WarrantProof,
+    issuer: AgentPubKey,
+    keystore: &KeystoreSender,
+) -> DatabaseResult<SignedWarrant> {
+    let warrant = Warrant {
+        proof,
+        issuer,
+        issued_at: timestamp::now(),
+    };
+    let signature = warrant.issuer.sign(keystore, warrant.clone()).await?;
+    Ok(SignedWarrant { warrant, signature })
+}
+
+/// Check a warrant received from another agent.
+///
+/// The issuer must have signed the warrant and the author must have signed
+/// every header in the proof. A fork must be two different headers from the
+/// same author at the same position in their chain.
+///
+/// This proves a fork, but for an invalid op it only shows the issuer
+/// made the claim about a header the author signed. The failure itself
+/// isn't validated again so it must be treated as the issuer's claim.
+pub async fn verify_warrant(signed_warrant: &SignedWarrant) -> DatabaseResult<bool> {
+    let SignedWarrant { warrant, signature } = signed_warrant;
+    if !warrant
+        .issuer
+        .verify_signature(signature, warrant.clone())
+        .await?
+    {
+        return Ok(false);
+    }
+    let headers = match &warrant.proof {
+        WarrantProof::InvalidOp { header, .. } => vec![header],
+        WarrantProof::ChainFork { first, second } => {
+            let (a, b) = (first.header(), second.header());
+            if a.author() != b.author() || a.header_seq() != b.header_seq() || a == b {
+                return Ok(false);
+            }
+            vec![first, second]
+        }
+    };
+    for header in headers {
+        if !header
+            .header()
+            .author()
+            .verify_signature(header.signature(), header.header())
+            .await?
+        {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use holochain_lmdb::buffer::BufferedStore;
+    use holochain_lmdb::env::WriteManager;
+    use holochain_lmdb::test_utils::test_cell_env;
+    use holochain_lmdb::test_utils::test_keystore;
+    use holochain_zome_types::test_utils::fake_agent_pubkey_1;
+    use holochain_zome_types::test_utils::fake_agent_pubkey_2;
+
+    async fn signed_create(keystore: &KeystoreSender, seq: u32) -> SignedHeader {
+        let mut create = fixt!(Create);
+        create.author = fake_agent_pubkey_1();
+        create.header_seq = seq;
+        let header = Header::Create(create);
+        let signature = header.author().sign(keystore, &header).await.unwrap();
+        SignedHeader(header, signature)
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn warrants_are_verified_and_stored() {
+        let test_env = test_cell_env();
+        let env = test_env.env();
+        let keystore = test_keystore();
+        let author = fake_agent_pubkey_1();
+        let issuer = fake_agent_pubkey_2();
+
+        let first = signed_create(&keystore, 3).await;
+        let second = signed_create(&keystore, 3).await;
+        let fork = issue_warrant(
+            WarrantProof::ChainFork {
+                first: first.clone(),
+                second: second.clone(),
+            },
+            issuer.clone(),
+            &keystore,
+        )
+        .await
+        .unwrap();
+        assert!(verify_warrant(&fork).await.unwrap());
+
+        let invalid = issue_warrant(
+            WarrantProof::InvalidOp {
+                header: first.clone(),
+                failure: "bad entry".into(),
+            },
+            issuer.clone(),
+            &keystore,
+        )
+        .await
+        .unwrap();
+        assert!(verify_warrant(&invalid).await.unwrap());
+
+        // A tampered warrant fails the issuer's signature
+        let mut tampered = invalid.clone();
+        tampered.warrant.issuer = author.clone();
+        assert!(!verify_warrant(&tampered).await.unwrap());
+
+        // Two headers at different positions are not a fork
+        let later = signed_create(&keystore, 4).await;
+        let not_a_fork = issue_warrant(
+            WarrantProof::ChainFork {
+                first: first.clone(),
+                second: later,
+            },
+            issuer.clone(),
+            &keystore,
+        )
+        .await
+        .unwrap();
+        assert!(!verify_warrant(&not_a_fork).await.unwrap());
+
+        // A header the author didn't sign is not evidence
+        let mut forged = first.clone();
+        forged.1 = second.signature().clone();
+        let forged = issue_warrant(
+            WarrantProof::InvalidOp {
+                header: forged,
+                failure: "bad entry".into(),
+            },
+            issuer.clone(),
+            &keystore,
+        )
+        .await
+        .unwrap();
+        assert!(!verify_warrant(&forged).await.unwrap());
+
+        let mut store = WarrantStore::new(env.clone().into()).unwrap();
+        store.add(fork.clone()).unwrap();
+        store.add(invalid.clone()).unwrap();
+        store.add(invalid.clone()).unwrap();
+        env.guard()
+            .with_commit(|writer| store.flush_to_txn_ref(writer))
+            .unwrap();
+
+        let mut store = WarrantStore::new(env.clone().into()).unwrap();
+        assert_eq!(
+            store.warrants(&author).unwrap(),
+            vec![fork.clone(), invalid]
+        );
+        assert!(store.warrants(&issuer).unwrap().is_empty());
+
+        // Only the newest claims are held against an agent
+        // and they never push out the fork proof
+        let mut newest = vec![fork];
+        for seq in 0..MAX_CLAIMS_PER_AGENT as u32 {
+            let warrant = issue_warrant(
+                WarrantProof::InvalidOp {
+                    header: signed_create(&keystore, seq).await,
+                    failure: "bad entry".into(),
+                },
+                issuer.clone(),
+                &keystore,
+            )
+            .await
+            .unwrap();
+            store.add(warrant.clone()).unwrap();
+            newest.push(warrant);
+        }
+        assert_eq!(store.warrants(&author).unwrap(), newest);
+    }
+}
@@ -20,6 +20,8 @@ pub struct AgentActivityResponse<T = SignedHeaderHashed> {
     /// The highest chain header that has
     /// been observed by this authority.
     pub highest_observed: Option<HighestObserved>,
+    /// Warrants this authority holds against the agent
+    pub warrants: Vec<SignedWarrant>,
 }
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
@@ -35,28 +37,55 @@ pub enum ChainItems<T = SignedHeaderHashed> {
 
 impl From<AgentActivityResponse<Element>> for holochain_zome_types::query::AgentActivity {
     fn from(a: AgentActivityResponse<Element>) -> Self {
-        let valid_activity = match a.valid_activity {
-            ChainItems::Full(elements) => elements
-                .into_iter()
-                .map(|el| (el.header().header_seq(), el.header_address().clone()))
-                .collect(),
-            ChainItems::Hashes(h) => h,
-            ChainItems::NotRequested => Vec::new(),
-        };
-        let rejected_activity = match a.rejected_activity {
-            ChainItems::Full(elements) => elements
-                .into_iter()
-                .map(|el| (el.header().header_seq(), el.header_address().clone()))
-                .collect(),
-            ChainItems::Hashes(h) => h,
-            ChainItems::NotRequested => Vec::new(),
-        };
+        let (valid_activity, valid_elements) = split_chain_items(a.valid_activity);
+        let (rejected_activity, rejected_elements) = split_chain_items(a.rejected_activity);
+        // Only a fork is proven by the warrant itself, an invalid op
+        // is the issuer's claim until it's validated again.
+        let (claims, warrants): (Vec<_>, Vec<_>) =
+            a.warrants.into_iter().partition(|signed_warrant| {
+                matches!(signed_warrant.warrant.proof, WarrantProof::InvalidOp { .. })
+            });
+        let rejected_elements = rejected_elements
+            .into_iter()
+            .map(|element| {
+                let reason =
+                    claims
+                        .iter()
+                        .find_map(|signed_warrant| match &signed_warrant.warrant.proof {
+                            WarrantProof::InvalidOp { header, failure }
+                                if header.header() == element.header() =>
+                            {
+                                Some(failure.clone())
+                            }
+                            _ => None,
+                        });
+                RejectedElement { element, reason }
+            })
+            .collect();
         Self {
             valid_activity,
             rejected_activity,
+            valid_elements,
+            rejected_elements,
             status: a.status,
             highest_observed: a.highest_observed,
-            warrants: Vec::with_capacity(0),
+            warrants,
+            claims,
         }
     }
 }
+
+/// The hashes of the chain items along with the elements if they are full
+fn split_chain_items(items: ChainItems<Element>) -> (Vec<(u32, HeaderHash)>, Vec<Element>) {
+    match items {
+        ChainItems::Full(elements) => (
+            elements
+                .iter()
+                .map(|el| (el.header().header_seq(), el.header_address().clone()))
+                .collect(),
+            elements,
+        ),
+        ChainItems::Hashes(h) => (h, Vec::new()),
+        ChainItems::NotRequested => (Vec::new(), Vec::new()),
+    }
+}
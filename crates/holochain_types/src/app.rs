@@ -66,10 +66,10 @@ pub struct RegisterDnaPayload {
 pub struct CreateCloneCellPayload {
     /// Properties to override when installing this Dna
     pub properties: Option<YamlProperties>,
-    /// The DNA to clone
+    /// The DNA to clone, which must be the DNA of the base cell of the slot
     pub dna_hash: DnaHash,
-    /// The Agent key with which to create this Cell
-    /// (TODO: should this be derived from the App?)
+    /// The Agent key with which to create this Cell, which must be the
+    /// agent of the base cell of the slot
     pub agent_key: AgentPubKey,
     /// The App with which to associate the newly created Cell
     pub installed_app_id: InstalledAppId,
@@ -81,7 +81,7 @@ pub struct CreateCloneCellPayload {
 }
 
 impl CreateCloneCellPayload {
-    /// Get the CellId of the to-be-created clone cell
+    /// Get the CellId of the base cell to be cloned
     pub fn cell_id(&self) -> CellId {
         CellId::new(self.dna_hash.clone(), self.agent_key.clone())
     }
@@ -310,6 +310,7 @@ impl InstalledApp {
                     base_cell_id: cell_id,
                     is_provisioned: true,
                     clones: HashSet::new(),
+                    archived_clones: HashSet::new(),
                     clone_limit: 0,
                 };
                 (cell_nick, slot)
@@ -327,6 +328,11 @@ impl InstalledApp {
         &self.installed_app_id
     }
 
+    /// Accessor
+    pub fn agent_key(&self) -> &AgentPubKey {
+        &self._agent_key
+    }
+
     /// Accessor
     pub fn provisioned_cells(&self) -> impl Iterator<Item = (&SlotId, &CellId)> {
         self.slots
@@ -346,6 +352,15 @@ impl InstalledApp {
         self.slots.iter().map(|(_, slot)| &slot.clones).flatten()
     }
 
+    /// Iterator of the base cells of slots which were never provisioned.
+    /// These cells exist, but are never run.
+    pub fn disabled_cells(&self) -> impl Iterator<Item = &CellId> {
+        self.slots
+            .values()
+            .filter(|slot| slot.provisioned_cell().is_none())
+            .map(|slot| slot.cell_id())
+    }
+
     /// Iterator of all cells, both provisioned and cloned
     pub fn all_cells(&self) -> impl Iterator<Item = &CellId> {
         self.provisioned_cells()
@@ -391,10 +406,46 @@ impl InstalledApp {
         Ok(())
     }
 
-    /// Remove a cloned cell
+    /// Remove a cloned cell, whether it is archived or not
     pub fn remove_clone(&mut self, slot_id: &SlotId, cell_id: &CellId) -> AppResult<bool> {
         let slot = self.slot_mut(slot_id)?;
-        Ok(slot.clones.remove(cell_id))
+        Ok(slot.clones.remove(cell_id) || slot.archived_clones.remove(cell_id))
+    }
+
+    /// Archive a cloned cell.
+    /// An archived clone keeps its data but is not run, and does not count
+    /// towards the clone limit of its slot.
+    pub fn archive_clone(&mut self, slot_id: &SlotId, cell_id: &CellId) -> AppResult<()> {
+        let slot = self.slot_mut(slot_id)?;
+        if !slot.clones.remove(cell_id) {
+            return Err(AppError::CloneCellMissing(slot_id.clone(), cell_id.clone()));
+        }
+        slot.archived_clones.insert(cell_id.clone());
+        Ok(())
+    }
+
+    /// Restore an archived cell, as long as the clone limit of its slot allows
+    pub fn restore_clone(&mut self, slot_id: &SlotId, cell_id: &CellId) -> AppResult<()> {
+        let slot = self.slot_mut(slot_id)?;
+        if !slot.archived_clones.contains(cell_id) {
+            return Err(AppError::CloneCellMissing(slot_id.clone(), cell_id.clone()));
+        }
+        if slot.clones.len() as u32 >= slot.clone_limit {
+            return Err(AppError::CloneLimitExceeded(slot.clone_limit, slot.clone()));
+        }
+        slot.archived_clones.remove(cell_id);
+        slot.clones.insert(cell_id.clone());
+        Ok(())
+    }
+
+    /// The slot of a cloned cell, whether it is archived or not
+    pub fn clone_slot(&self, cell_id: &CellId) -> Option<&SlotId> {
+        self.slots
+            .iter()
+            .find(|(_, slot)| {
+                slot.clones.contains(cell_id) || slot.archived_clones.contains(cell_id)
+            })
+            .map(|(slot_id, _)| slot_id)
     }
 }
 
@@ -415,6 +466,10 @@ pub struct AppSlot {
     /// Cells which were cloned at runtime. The length cannot grow beyond
     /// `clone_limit`
     clones: HashSet<CellId>,
+    /// Cloned cells which were archived. Their data is kept but they are not
+    /// run until they are restored.
+    #[serde(default)]
+    archived_clones: HashSet<CellId>,
 }
 
 impl AppSlot {
@@ -425,6 +480,7 @@ impl AppSlot {
             is_provisioned,
             clone_limit,
             clones: HashSet::new(),
+            archived_clones: HashSet::new(),
         }
     }
 
@@ -452,6 +508,16 @@ impl AppSlot {
         }
     }
 
+    /// Accessor
+    pub fn clones(&self) -> &HashSet<CellId> {
+        &self.clones
+    }
+
+    /// Accessor
+    pub fn archived_clones(&self) -> &HashSet<CellId> {
+        &self.archived_clones
+    }
+
     /// Transformer
     pub fn into_provisioned_cell(self) -> Option<CellId> {
         if self.is_provisioned {
@@ -515,4 +581,46 @@ mod tests {
             app.all_cells().collect::<HashSet<_>>()
         );
     }
+
+    #[test]
+    fn clone_archiving() {
+        let base_cell_id = fixt!(CellId);
+        let agent = base_cell_id.agent_pubkey().clone();
+        let new_clone = || CellId::new(fixt!(DnaHash), agent.clone());
+        let slot1 = AppSlot::new(base_cell_id, false, 1);
+        let slot_id: SlotId = "slot_id".into();
+        let mut app = InstalledApp::new("app", agent.clone(), vec![(slot_id.clone(), slot1)]);
+
+        let clones: Vec<_> = vec![new_clone(), new_clone()];
+        app.add_clone(&slot_id, clones[0].clone()).unwrap();
+
+        // An archived clone is no longer one of the app's cells
+        app.archive_clone(&slot_id, &clones[0]).unwrap();
+        assert_eq!(app.all_cells().count(), 0);
+        assert_eq!(app.clone_slot(&clones[0]), Some(&slot_id));
+        matches::assert_matches!(
+            app.archive_clone(&slot_id, &clones[0]),
+            Err(AppError::CloneCellMissing(_, _))
+        );
+
+        // and doesn't count towards the clone limit
+        app.add_clone(&slot_id, clones[1].clone()).unwrap();
+        matches::assert_matches!(
+            app.restore_clone(&slot_id, &clones[0]),
+            Err(AppError::CloneLimitExceeded(1, _))
+        );
+
+        // Archived clones can be restored
+        assert_eq!(app.remove_clone(&slot_id, &clones[1]).unwrap(), true);
+        app.restore_clone(&slot_id, &clones[0]).unwrap();
+        assert_eq!(
+            app.all_cells().collect::<HashSet<_>>(),
+            maplit::hashset! { &clones[0] }
+        );
+
+        // Archived clones can be removed
+        app.archive_clone(&slot_id, &clones[0]).unwrap();
+        assert_eq!(app.remove_clone(&slot_id, &clones[0]).unwrap(), true);
+        assert_eq!(app.clone_slot(&clones[0]), None);
+    }
 }
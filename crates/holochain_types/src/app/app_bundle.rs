@@ -2,7 +2,10 @@ use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use self::error::AppBundleResult;
 
-use super::{dna_gamut::DnaGamut, AppManifest, AppManifestValidated};
+use super::{
+    dna_gamut::{CellResolution, DnaGamut, DnaResolution},
+    AppManifest, AppManifestValidated,
+};
 use crate::prelude::*;
 
 #[allow(missing_docs)]
@@ -42,14 +45,14 @@ impl AppBundle {
     pub async fn resolve_cells(
         self,
         agent: AgentPubKey,
-        _gamut: DnaGamut,
+        gamut: DnaGamut,
         membrane_proofs: HashMap<SlotId, MembraneProof>,
     ) -> AppBundleResult<CellSlotResolution> {
         let AppManifestValidated { name: _, slots } = self.manifest().clone().validate()?;
         let bundle = Arc::new(self);
         let tasks = slots.into_iter().map(|(slot_id, slot)| async {
             let bundle = bundle.clone();
-            Ok((slot_id, bundle.resolve_cell(slot).await?))
+            Ok((slot_id, bundle.resolve_cell(slot, &gamut, &agent).await?))
         });
         let resolution = futures::future::join_all(tasks)
             .await
@@ -75,12 +78,17 @@ impl AppBundle {
                                 let slot = AppSlot::new(cell_id, true, clone_limit);
                                 resolution.slots.push((slot_id, slot));
                             }
-                            CellProvisioningOp::Noop(cell_id, clone_limit) => {
+                            CellProvisioningOp::Disabled(cell_id, clone_limit) => {
+                                let proof = membrane_proofs.get(&slot_id).cloned();
+                                resolution.disabled_cells.push((cell_id.clone(), proof));
                                 resolution
                                     .slots
                                     .push((slot_id, AppSlot::new(cell_id, false, clone_limit)));
                             }
-                            _ => todo!(),
+                            CellProvisioningOp::NoMatch => {
+                                return Err(AppBundleError::CellResolutionFailure(slot_id));
+                            }
+                            CellProvisioningOp::Conflict(conflict) => match conflict {},
                         }
                         Ok(resolution)
                     } else {
@@ -96,6 +104,8 @@ impl AppBundle {
     async fn resolve_cell(
         &self,
         slot: AppSlotManifestValidated,
+        gamut: &DnaGamut,
+        agent: &AgentPubKey,
     ) -> AppBundleResult<CellProvisioningOp> {
         Ok(match slot {
             AppSlotManifestValidated::Create {
@@ -108,42 +118,44 @@ impl AppBundle {
                     .await?
             }
 
-            AppSlotManifestValidated::CreateClone { .. } => {
-                unimplemented!("`create_clone` provisioning strategy is currently unimplemented")
+            AppSlotManifestValidated::CreateClone {
+                location,
+                version,
+                clone_limit,
+                properties,
+                ..
+            } => {
+                self.resolve_cell_create_clone(&location, version.as_ref(), clone_limit, properties)
+                    .await?
             }
             AppSlotManifestValidated::UseExisting {
                 version,
                 clone_limit,
                 ..
-            } => self.resolve_cell_existing(&version, clone_limit),
+            } => Self::resolve_cell_existing(gamut, agent, version, clone_limit),
             AppSlotManifestValidated::CreateIfNotExists {
                 location,
                 version,
                 clone_limit,
                 ..
-            } => match self.resolve_cell_existing(&version, clone_limit) {
+            } => match Self::resolve_cell_existing(gamut, agent, version.clone(), clone_limit) {
                 op @ CellProvisioningOp::Existing(_, _) => op,
                 CellProvisioningOp::NoMatch => {
                     self.resolve_cell_create(&location, Some(&version), clone_limit)
                         .await?
                 }
-                CellProvisioningOp::Conflict(_) => {
-                    unimplemented!("conflicts are not handled, or even possible yet")
-                }
+                CellProvisioningOp::Conflict(conflict) => match conflict {},
                 CellProvisioningOp::Create(_, _) => {
                     unreachable!("resolve_cell_existing will never return a Create op")
                 }
-                CellProvisioningOp::Noop(_, _) => {
-                    unreachable!("resolve_cell_existing will never return a Noop")
+                CellProvisioningOp::Disabled(_, _) => {
+                    unreachable!("resolve_cell_existing will never return a Disabled op")
                 }
             },
             AppSlotManifestValidated::Disabled {
-                version: _,
-                clone_limit: _,
-            } => {
-                unimplemented!("`disabled` provisioning strategy is currently unimplemented")
-                // CellProvisioningOp::Noop(clone_limit)
-            }
+                version,
+                clone_limit,
+            } => Self::resolve_cell_disabled(gamut, agent, version, clone_limit),
         })
     }
 
@@ -164,12 +176,65 @@ impl AppBundle {
         Ok(CellProvisioningOp::Create(dna_file, clone_limit))
     }
 
-    fn resolve_cell_existing(
+    async fn resolve_cell_create_clone(
         &self,
-        _version: &DnaVersionSpec,
-        _clone_limit: u32,
+        location: &mr_bundle::Location,
+        version: Option<&DnaVersionSpec>,
+        clone_limit: u32,
+        properties: Option<YamlProperties>,
+    ) -> AppBundleResult<CellProvisioningOp> {
+        Ok(
+            match self
+                .resolve_cell_create(location, version, clone_limit)
+                .await?
+            {
+                // The version spec applies to the bundled DNA, not the clone
+                // which is given a unique UUID and so its own network
+                CellProvisioningOp::Create(dna_file, clone_limit) => {
+                    let dna_file = match properties {
+                        Some(properties) => dna_file.modify_phenotype(random_uuid(), properties)?,
+                        None => dna_file.with_uuid(random_uuid()).await?,
+                    };
+                    CellProvisioningOp::Create(dna_file, clone_limit)
+                }
+                op => op,
+            },
+        )
+    }
+
+    fn resolve_cell_existing(
+        gamut: &DnaGamut,
+        agent: &AgentPubKey,
+        version: DnaVersionSpec,
+        clone_limit: u32,
     ) -> CellProvisioningOp {
-        unimplemented!("Reusing existing cells is not yet implemented")
+        match gamut.resolve_cell(version, agent) {
+            CellResolution::Match(cell_id, _) => CellProvisioningOp::Existing(cell_id, clone_limit),
+            CellResolution::NoMatch => CellProvisioningOp::NoMatch,
+            CellResolution::Conflict => {
+                unreachable!("the gamut does not produce conflicts without DPKI")
+            }
+        }
+    }
+
+    fn resolve_cell_disabled(
+        gamut: &DnaGamut,
+        agent: &AgentPubKey,
+        version: DnaVersionSpec,
+        clone_limit: u32,
+    ) -> CellProvisioningOp {
+        // The cell is created but never run, and clones are made from the
+        // registered DNA
+        match gamut.resolve_dna(version) {
+            DnaResolution::Match(dna_hash, _) => CellProvisioningOp::Disabled(
+                CellId::new(dna_hash.into(), agent.clone()),
+                clone_limit,
+            ),
+            DnaResolution::NoMatch => CellProvisioningOp::NoMatch,
+            DnaResolution::Conflict => {
+                unreachable!("the gamut does not produce conflicts without DPKI")
+            }
+        }
     }
 }
 
@@ -180,6 +245,8 @@ impl AppBundle {
 pub struct CellSlotResolution {
     pub agent: AgentPubKey,
     pub dnas_to_register: Vec<(DnaFile, Option<MembraneProof>)>,
+    /// Cells of registered DNAs which are created but never provisioned
+    pub disabled_cells: Vec<(CellId, Option<MembraneProof>)>,
     pub slots: Vec<(SlotId, AppSlot)>,
 }
 
@@ -189,11 +256,13 @@ impl CellSlotResolution {
         Self {
             agent,
             dnas_to_register: Default::default(),
+            disabled_cells: Default::default(),
             slots: Default::default(),
         }
     }
 
-    /// Return the IDs of new cells to be created as part of the resolution.
+    /// Return the IDs of new cells to be created as part of the resolution,
+    /// including disabled cells which are created but never provisioned.
     /// Does not return existing cells to be reused.
     // TODO: remove clone of MembraneProof
     pub fn cells_to_create(&self) -> Vec<(CellId, Option<MembraneProof>)> {
@@ -205,6 +274,7 @@ impl CellSlotResolution {
                     proof.clone(),
                 )
             })
+            .chain(self.disabled_cells.iter().cloned())
             .collect()
     }
 }
@@ -216,9 +286,9 @@ pub enum CellProvisioningOp {
     Create(DnaFile, u32),
     /// Use an existing Cell
     Existing(CellId, u32),
-    /// No provisioning needed, but there might be a clone_limit, and so we need
-    /// to know which DNA and Agent to use for making clones
-    Noop(CellId, u32),
+    /// Create a Cell which is never provisioned. There might be a clone_limit,
+    /// and so we need to know which DNA and Agent to use for making clones
+    Disabled(CellId, u32),
     /// Couldn't find a DNA that matches the version spec; can't provision (should this be an Err?)
     NoMatch,
     /// Ambiguous result, needs manual resolution; can't provision (should this be an Err?)
@@ -234,11 +304,17 @@ mod tests {
 
     use crate::prelude::*;
     use ::fixt::prelude::*;
-    use app_manifest_v1::tests::app_manifest_fixture;
+    use app_manifest_v1::tests::app_manifest_fixture_provisioned;
 
     use super::AppBundle;
 
     async fn app_bundle_fixture() -> (AppBundle, DnaFile) {
+        app_bundle_fixture_provisioned(CellProvisioning::Create { deferred: false }).await
+    }
+
+    async fn app_bundle_fixture_provisioned(
+        provisioning: CellProvisioning,
+    ) -> (AppBundle, DnaFile) {
         let dna_wasm = DnaWasmHashed::from_content(DnaWasm::new_invalid()).await;
         let fake_wasms = vec![dna_wasm.clone().into_content()];
         let fake_zomes = vec![Zome::new(
@@ -253,9 +329,10 @@ mod tests {
 
         let path1 = PathBuf::from(format!("{}", dna1.dna_hash()));
 
-        let (manifest, _dna_hashes) = app_manifest_fixture(
+        let (manifest, _dna_hashes) = app_manifest_fixture_provisioned(
             Some(DnaLocation::Bundled(path1.clone())),
             vec![dna1.dna_def().clone(), dna2.dna_def().clone()],
+            provisioning,
         )
         .await;
 
@@ -285,8 +362,94 @@ mod tests {
         let expected = CellSlotResolution {
             agent,
             dnas_to_register: vec![(dna, None)],
+            disabled_cells: vec![],
             slots: vec![("nick".into(), slot)],
         };
         assert_eq!(resolution, expected);
     }
+
+    /// Test that a clone of the bundled DNA is created for a CreateClone slot
+    #[tokio::test]
+    async fn provisioning_1_create_clone() {
+        let agent = fixt!(AgentPubKey);
+        let (bundle, dna) =
+            app_bundle_fixture_provisioned(CellProvisioning::CreateClone { deferred: false }).await;
+
+        let resolution = bundle
+            .resolve_cells(
+                agent.clone(),
+                DnaGamut::new(vec![], vec![]),
+                Default::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(resolution.dnas_to_register.len(), 1);
+        let clone = &resolution.dnas_to_register[0].0;
+        assert_ne!(clone.dna_hash(), dna.dna_hash());
+        assert_eq!(clone.dna_def().zomes, dna.dna_def().zomes);
+        let cell_id = CellId::new(clone.dna_hash().clone(), agent);
+        assert_eq!(resolution.slots[0].1.provisioned_cell(), Some(&cell_id));
+    }
+
+    /// Test that UseExisting and CreateIfNotExists slots share a cell of the
+    /// same agent which already exists, and that UseExisting fails without one
+    #[tokio::test]
+    async fn provisioning_1_use_existing() {
+        let agent = fixt!(AgentPubKey);
+        let (_, dna) = app_bundle_fixture().await;
+        let cell_id = CellId::new(dna.dna_hash().to_owned(), agent.clone());
+        let other_cell_id = CellId::new(dna.dna_hash().to_owned(), fixt!(AgentPubKey));
+
+        for provisioning in vec![
+            CellProvisioning::UseExisting { deferred: false },
+            CellProvisioning::CreateIfNotExists { deferred: false },
+        ] {
+            let (bundle, _) = app_bundle_fixture_provisioned(provisioning).await;
+            let gamut = DnaGamut::new(vec![], vec![cell_id.clone(), other_cell_id.clone()]);
+            let resolution = bundle
+                .resolve_cells(agent.clone(), gamut, Default::default())
+                .await
+                .unwrap();
+            let expected = CellSlotResolution {
+                agent: agent.clone(),
+                dnas_to_register: vec![],
+                disabled_cells: vec![],
+                slots: vec![("nick".into(), AppSlot::new(cell_id.clone(), true, 50))],
+            };
+            assert_eq!(resolution, expected);
+        }
+
+        // The cell of another agent is not used
+        let (bundle, _) =
+            app_bundle_fixture_provisioned(CellProvisioning::UseExisting { deferred: false }).await;
+        let gamut = DnaGamut::new(vec![], vec![other_cell_id]);
+        assert!(matches!(
+            bundle.resolve_cells(agent, gamut, Default::default()).await,
+            Err(AppBundleError::CellResolutionFailure(_))
+        ));
+    }
+
+    /// Test that a Disabled slot creates a cell which is never provisioned,
+    /// and can be cloned from a registered DNA
+    #[tokio::test]
+    async fn provisioning_1_disabled() {
+        let agent = fixt!(AgentPubKey);
+        let (bundle, dna) = app_bundle_fixture_provisioned(CellProvisioning::Disabled).await;
+        let cell_id = CellId::new(dna.dna_hash().to_owned(), agent.clone());
+
+        let gamut = DnaGamut::new(vec![dna.dna_hash().clone()], vec![]);
+        let resolution = bundle
+            .resolve_cells(agent.clone(), gamut, Default::default())
+            .await
+            .unwrap();
+        let expected = CellSlotResolution {
+            agent,
+            dnas_to_register: vec![],
+            disabled_cells: vec![(cell_id.clone(), None)],
+            slots: vec![("nick".into(), AppSlot::new(cell_id.clone(), false, 50))],
+        };
+        assert_eq!(resolution, expected);
+        assert_eq!(resolution.cells_to_create(), vec![(cell_id, None)]);
+    }
 }
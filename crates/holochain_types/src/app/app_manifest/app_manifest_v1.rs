@@ -276,6 +276,19 @@ pub mod tests {
     pub async fn app_manifest_fixture<I: IntoIterator<Item = DnaDef>>(
         location: Option<mr_bundle::Location>,
         dnas: I,
+    ) -> (AppManifest, Vec<DnaHashB64>) {
+        app_manifest_fixture_provisioned(
+            location,
+            dnas,
+            CellProvisioning::Create { deferred: false },
+        )
+        .await
+    }
+
+    pub async fn app_manifest_fixture_provisioned<I: IntoIterator<Item = DnaDef>>(
+        location: Option<mr_bundle::Location>,
+        dnas: I,
+        provisioning: CellProvisioning,
     ) -> (AppManifest, Vec<DnaHashB64>) {
         let props = Props {
             salad: "bar".to_string(),
@@ -298,7 +311,7 @@ pub mod tests {
                 version: Some(version),
                 clone_limit: 50,
             },
-            provisioning: Some(CellProvisioning::Create { deferred: false }),
+            provisioning: Some(provisioning),
         }];
         let manifest = AppManifest::V1(AppManifestV1 {
             name: "Test app".to_string(),
@@ -29,9 +29,15 @@ pub struct DnaGamut(HashMap<DnaHash, HashSet<AgentPubKey>>);
 pub struct DnaVersion;
 
 impl DnaGamut {
-    /// Constructor. Restructure a list of CellIds into the proper format.
-    pub fn new<I: IntoIterator<Item = CellId>>(cells: I) -> Self {
-        let mut map: HashMap<DnaHash, HashSet<AgentPubKey>> = HashMap::new();
+    /// Constructor. Restructure a list of registered DNAs and a list of
+    /// CellIds into the proper format.
+    pub fn new<D, C>(dnas: D, cells: C) -> Self
+    where
+        D: IntoIterator<Item = DnaHash>,
+        C: IntoIterator<Item = CellId>,
+    {
+        let mut map: HashMap<DnaHash, HashSet<AgentPubKey>> =
+            dnas.into_iter().map(|dna| (dna, HashSet::new())).collect();
         for cell in cells {
             let (dna, agent) = cell.into_dna_and_agent();
             match map.entry(dna) {
@@ -49,7 +55,7 @@ impl DnaGamut {
     #[deprecated = "Stop using the placeholder"]
     #[allow(missing_docs)]
     pub fn placeholder() -> Self {
-        Self::new(std::iter::empty())
+        Self::new(std::iter::empty(), std::iter::empty())
     }
 
     /// Given a version spec, return the best-matching DNA in the gamut
@@ -63,17 +69,17 @@ impl DnaGamut {
     }
 
     /// Given a version spec, return the best-matching CellId
-    // TODO: use DPKI to filter Cells which belong to Agents that are not
+    // TODO: use DPKI to also match Cells which belong to Agents that are
     //       associated with the provided agent
-    pub fn resolve_cell(&self, spec: DnaVersionSpec, _agent: &AgentPubKey) -> CellResolution {
+    pub fn resolve_cell(&self, spec: DnaVersionSpec, agent: &AgentPubKey) -> CellResolution {
         for hash in spec.dna_hashes() {
-            if let Some(agent) = self
+            // Without DPKI the only agent associated with the provided agent
+            // is the agent itself
+            if self
                 .0
                 .get(hash.as_ref())
-                // TODO: this is where an agent check could go, but for now we
-                //       just return the first one available
-                .map(|agents| agents.iter().next())
-                .unwrap_or(None)
+                .map(|agents| agents.contains(agent))
+                .unwrap_or(false)
             {
                 return CellResolution::Match(
                     CellId::new(hash.clone().into(), agent.clone()),
@@ -11,6 +11,12 @@ pub enum AppError {
     #[error("Tried to access missing slot id: '{0}'")]
     SlotIdMissing(SlotId),
 
+    #[error("Slot '{0}' has no clone cell {1:?}")]
+    CloneCellMissing(SlotId, CellId),
+
+    #[error("Slot '{0}' can't be cloned from cell {1:?} which is not its base cell")]
+    CloneBaseCellMismatch(SlotId, CellId),
+
     #[error("Tried to install app '{0}' which contains duplicate slot ids. The following slot ids have duplicates: {1:?}")]
     DuplicateSlotIds(InstalledAppId, Vec<SlotId>),
 }
@@ -1,9 +1,13 @@
 //! Holochain autonomic type helpers.
 
+use crate::Timestamp;
+
 /// The various processes which run "autonomically", aka subconsciously.
-/// These are currently not used.
 pub enum AutonomicProcess {
     /// Validation / Correction may propagate much slower.
+    /// Retry validating the ops which are waiting in the validation limbo,
+    /// fetching their missing dependencies again, and abandon the ops
+    /// whose dependencies never arrive.
     SlowHeal,
 
     /// See how many validators we can find on the network for all of our entries
@@ -13,14 +17,63 @@ pub enum AutonomicProcess {
 
 /// A cue that the autonomic system should perform one of its functions now,
 /// rather than at the next scheduled time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AutonomicCue {
-    // /// Cue sent when it is known that entries are ready for initial publishing,
-// /// i.e. after committing new entries to your source chain
-// Publish(Address),
+    /// Cue sent when a new peer joins the network of a cell.
+    /// Dependencies which could not be found before may now be fetched,
+    /// so this cues a [`AutonomicProcess::SlowHeal`].
+    PeerJoined,
+    /// Cue sent when a peer leaves the network of a cell.
+    /// Some of our ops may have lost a holder,
+    /// so this cues a [`AutonomicProcess::HealthCheck`].
+    PeerLeft,
 }
 
 impl From<AutonomicCue> for AutonomicProcess {
     fn from(cue: AutonomicCue) -> AutonomicProcess {
-        match cue {}
+        match cue {
+            AutonomicCue::PeerJoined => AutonomicProcess::SlowHeal,
+            AutonomicCue::PeerLeft => AutonomicProcess::HealthCheck,
+        }
+    }
+}
+
+/// The result of a [`AutonomicProcess::HealthCheck`] of a cell:
+/// how well the ops the cell has authored are held on the DHT.
+///
+/// An op is covered once it has at least as many valid validation
+/// receipts as its entry def requires.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CellHealth {
+    /// When the check was made
+    pub checked_at: Timestamp,
+    /// The number of authored ops which are published
+    pub published_ops: usize,
+    /// The number of published ops with enough valid receipts
+    pub covered_ops: usize,
+    /// The number of valid receipts still missing across all
+    /// published ops which are not covered
+    pub missing_receipts: usize,
+}
+
+impl CellHealth {
+    /// An empty report for a check made at this time
+    pub fn new(checked_at: Timestamp) -> Self {
+        Self {
+            checked_at,
+            published_ops: 0,
+            covered_ops: 0,
+            missing_receipts: 0,
+        }
+    }
+
+    /// The number of published ops without enough valid receipts
+    pub fn uncovered_ops(&self) -> usize {
+        self.published_ops - self.covered_ops
+    }
+
+    /// Are all the published ops covered?
+    pub fn is_healthy(&self) -> bool {
+        self.uncovered_ops() == 0
     }
 }
@@ -4,6 +4,12 @@
 //! - System-defined signals are produced in various places in the system
 
 use crate::impl_from;
+use holo_hash::AgentPubKey;
+use holo_hash::AnyDhtHash;
+use holo_hash::DhtOpHash;
+use holo_hash::DnaHash;
+use holo_hash::EntryHash;
+use holo_hash::HeaderHash;
 use holochain_serialized_bytes::prelude::*;
 use holochain_zome_types::prelude::*;
 
@@ -17,21 +23,144 @@ pub enum Signal {
     System(SystemSignal),
 }
 
+impl Signal {
+    /// The kind of this signal
+    pub fn kind(&self) -> SignalKind {
+        match self {
+            Self::App(_, _) => SignalKind::App,
+            Self::System(signal) => match signal {
+                SystemSignal::OpIntegrated { .. } => SignalKind::OpIntegrated,
+                SystemSignal::LinkAdded { .. } => SignalKind::LinkAdded,
+                SystemSignal::AuthoredOpRejected { .. } => SignalKind::AuthoredOpRejected,
+                SystemSignal::CellInitCompleted { .. } => SignalKind::CellInitCompleted,
+                SystemSignal::CellInitFailed { .. } => SignalKind::CellInitFailed,
+                SystemSignal::PeerJoined { .. } => SignalKind::PeerJoined,
+                SystemSignal::PeerLeft { .. } => SignalKind::PeerLeft,
+            },
+        }
+    }
+
+    /// The DHT basis this signal is about, if it is about DHT data
+    pub fn basis(&self) -> Option<AnyDhtHash> {
+        match self {
+            Self::System(SystemSignal::OpIntegrated { basis, .. }) => Some(basis.clone()),
+            Self::System(SystemSignal::LinkAdded { base, .. }) => Some(base.clone().into()),
+            _ => None,
+        }
+    }
+
+    /// Is this signal about the given Cell?
+    /// Signals about a network are about every Cell of the network's DNA.
+    pub fn is_about_cell(&self, cell_id: &CellId) -> bool {
+        match self {
+            Self::App(id, _) => id == cell_id,
+            Self::System(SystemSignal::PeerJoined { dna_hash, .. })
+            | Self::System(SystemSignal::PeerLeft { dna_hash, .. }) => {
+                dna_hash == cell_id.dna_hash()
+            }
+            Self::System(signal) => signal.cell_id() == Some(cell_id),
+        }
+    }
+}
+
+/// The kinds of Signal, used to choose which signals an interface receives
+#[derive(Clone, Copy, Debug, Hash, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalKind {
+    /// Signals emitted by a Cell with `emit_signal`
+    App,
+    /// [SystemSignal::OpIntegrated]
+    OpIntegrated,
+    /// [SystemSignal::LinkAdded]
+    LinkAdded,
+    /// [SystemSignal::AuthoredOpRejected]
+    AuthoredOpRejected,
+    /// [SystemSignal::CellInitCompleted]
+    CellInitCompleted,
+    /// [SystemSignal::CellInitFailed]
+    CellInitFailed,
+    /// [SystemSignal::PeerJoined]
+    PeerJoined,
+    /// [SystemSignal::PeerLeft]
+    PeerLeft,
+}
+
 /// A Signal which originates from within the Holochain system, as opposed to
 /// from within a Cell
-///
-/// TODO, decide what these will be. For instance, maybe there is a
-/// DataAvailable signal for doing async network requests
 #[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
 pub enum SystemSignal {
-    /// Since we have no real system signals, we use a test signal for testing
-    /// TODO: replace instances of this with something real
-    Test(String),
+    /// A valid op was integrated into the DHT data held by a Cell
+    OpIntegrated {
+        /// The Cell which integrated the op
+        cell_id: CellId,
+        /// The basis hash the op is stored at
+        basis: AnyDhtHash,
+        /// The hash of the op
+        op_hash: DhtOpHash,
+    },
+    /// A valid link was integrated on a base held by a Cell
+    LinkAdded {
+        /// The Cell which integrated the link
+        cell_id: CellId,
+        /// The base the link is on
+        base: EntryHash,
+        /// The target of the link
+        target: EntryHash,
+        /// The tag of the link
+        tag: LinkTag,
+        /// The hash of the CreateLink header
+        create_link_hash: HeaderHash,
+    },
+    /// An op authored by the agent of a Cell was rejected by validation
+    AuthoredOpRejected {
+        /// The Cell whose agent authored the op
+        cell_id: CellId,
+        /// The hash of the op
+        op_hash: DhtOpHash,
+        /// The hash of the header the op was produced from
+        header_hash: HeaderHash,
+    },
+    /// The init callbacks of every zome in a Cell passed
+    CellInitCompleted {
+        /// The Cell which was initialized
+        cell_id: CellId,
+    },
+    /// An init callback of a Cell did not pass
+    CellInitFailed {
+        /// The Cell which failed to initialize
+        cell_id: CellId,
+        /// The result of the init callback
+        reason: String,
+    },
+    /// A remote agent joined the network of a DNA run by this conductor
+    PeerJoined {
+        /// The DNA of the network
+        dna_hash: DnaHash,
+        /// The agent which joined
+        agent: AgentPubKey,
+    },
+    /// The agent info of a remote agent expired without being renewed, so the
+    /// agent has left the network of a DNA run by this conductor
+    PeerLeft {
+        /// The DNA of the network
+        dna_hash: DnaHash,
+        /// The agent which left
+        agent: AgentPubKey,
+    },
 }
 
-/// Create a test signal
-pub fn test_signal(s: &str) -> Signal {
-    SystemSignal::Test(s.to_string()).into()
+impl SystemSignal {
+    /// The Cell this signal is about, if it is about a single Cell
+    pub fn cell_id(&self) -> Option<&CellId> {
+        match self {
+            Self::OpIntegrated { cell_id, .. }
+            | Self::LinkAdded { cell_id, .. }
+            | Self::AuthoredOpRejected { cell_id, .. }
+            | Self::CellInitCompleted { cell_id }
+            | Self::CellInitFailed { cell_id, .. } => Some(cell_id),
+            Self::PeerJoined { .. } | Self::PeerLeft { .. } => None,
+        }
+    }
 }
 
 impl_from! {
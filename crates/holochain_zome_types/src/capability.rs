@@ -26,7 +26,9 @@
 
 mod claim;
 mod grant;
+mod query;
 mod secret;
 pub use claim::*;
 pub use grant::*;
+pub use query::*;
 pub use secret::*;
@@ -1,4 +1,5 @@
 use super::CapSecret;
+use crate::timestamp::Timestamp;
 use crate::zome::FunctionName;
 use crate::zome::ZomeName;
 use holo_hash::*;
@@ -39,9 +40,55 @@ impl From<holo_hash::AgentPubKey> for CapGrant {
 }
 
 #[derive(Default, PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize)]
-/// @todo Ability to forcibly curry payloads into functions that are called with a claim.
+/// Payloads that a grant forcibly curries into the functions it grants.
+///
+/// When a zome call is authorized by the grant, the curried payload for the called function is
+/// merged into the payload provided by the caller.
+/// Both payloads must be maps, and the curried entries replace every caller entry with the same
+/// key. A call whose payload can't be merged fails.
+/// Functions without a curried payload are called with the caller's payload as is.
 pub struct CurryPayloads(pub BTreeMap<GrantedFunction, SerializedBytes>);
 
+impl CurryPayloads {
+    /// The payload curried into the given function, if any.
+    pub fn get(&self, function: &GrantedFunction) -> Option<&SerializedBytes> {
+        self.0.get(function)
+    }
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+/// Limits on how long and how often a grant can be used.
+///
+/// The conductor checks the limits every time it authorizes a zome call.
+/// Uses are counted locally by the cell that made the grant so they are never published.
+pub struct CapGrantLimits {
+    /// The grant does not authorize any calls from this time on.
+    pub expires_at: Option<Timestamp>,
+    /// The grant authorizes at most this many successful calls.
+    pub max_uses: Option<u32>,
+}
+
+impl CapGrantLimits {
+    /// Constructor
+    pub fn new(expires_at: Option<Timestamp>, max_uses: Option<u32>) -> Self {
+        Self {
+            expires_at,
+            max_uses,
+        }
+    }
+
+    /// Does a grant with these limits still authorize calls at `now`, having been used `uses` times?
+    pub fn allows(&self, now: Timestamp, uses: u32) -> bool {
+        self.expires_at
+            .map(|expires_at| now < expires_at)
+            .unwrap_or(true)
+            && self
+                .max_uses
+                .map(|max_uses| uses < max_uses)
+                .unwrap_or(true)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 /// The entry for the ZomeCall capability grant.
 /// This data is committed to the callee's source chain as a private entry.
@@ -55,8 +102,12 @@ pub struct ZomeCallCapGrant {
     pub access: CapAccess,
     /// Set of functions to which this capability grants ZomeCall access
     pub functions: GrantedFunctions,
-    // @todo the payloads to curry to the functions
-    // pub curry_payloads: CurryPayloads,
+    /// The payloads to curry to the functions
+    #[serde(default)]
+    pub curry_payloads: CurryPayloads,
+    /// Expiry and maximum uses of the grant
+    #[serde(default)]
+    pub limits: CapGrantLimits,
 }
 
 impl ZomeCallCapGrant {
@@ -65,13 +116,15 @@ impl ZomeCallCapGrant {
         tag: String,
         access: CapAccess,
         functions: GrantedFunctions,
-        // @todo curry_payloads: CurryPayloads,
+        curry_payloads: CurryPayloads,
+        limits: CapGrantLimits,
     ) -> Self {
         Self {
             tag,
             access,
             functions,
-            // @todo curry_payloads,
+            curry_payloads,
+            limits,
         }
     }
 }
@@ -87,6 +140,9 @@ impl CapGrant {
     /// Given a grant, is it valid in isolation?
     /// In a world of CRUD, some new entry might update or delete an existing one, but we can check
     /// if a grant is valid in a standalone way.
+    ///
+    /// The limits of the grant are not checked here as they depend on the time and local usage.
+    /// See `CapGrantLimits::allows()`
     pub fn is_valid(
         &self,
         check_function: &GrantedFunction,
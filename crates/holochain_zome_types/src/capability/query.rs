@@ -0,0 +1,63 @@
+use super::CapClaim;
+use super::CapGrant;
+use super::ZomeCallCapGrant;
+use holo_hash::*;
+use holochain_serialized_bytes::prelude::*;
+
+/// Input to the `capability_grants` and `capability_claims` host functions.
+///
+/// Only grants and claims that are live on the local source chain are ever
+/// returned, i.e. they have been committed and not since updated or deleted.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, SerializedBytes)]
+pub struct CapQuery {
+    /// Only match grants or claims with exactly this tag.
+    /// All tags match if this is `None`.
+    pub tag: Option<String>,
+}
+
+impl CapQuery {
+    /// Match every live grant or claim.
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Match the live grants or claims with the given tag.
+    pub fn tag(tag: String) -> Self {
+        Self { tag: Some(tag) }
+    }
+
+    /// Does the given tag pass this query?
+    pub fn check(&self, tag: &str) -> bool {
+        self.tag.as_ref().map(|t| t == tag).unwrap_or(true)
+    }
+}
+
+/// A live grant on the local source chain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, SerializedBytes)]
+pub struct CapGrantInfo {
+    /// The header that created the grant.
+    /// Pass this to `delete_cap_grant` or `update_cap_grant` to revoke the grant.
+    pub header_hash: HeaderHash,
+    /// The grant itself.
+    pub grant: ZomeCallCapGrant,
+}
+
+/// A live claim on the local source chain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, SerializedBytes)]
+pub struct CapClaimInfo {
+    /// The header that created the claim.
+    pub header_hash: HeaderHash,
+    /// The claim itself.
+    pub claim: CapClaim,
+}
+
+/// How the current zome call was authorized.
+/// Output of the `capability_info` host function.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, SerializedBytes)]
+pub struct CapabilityInfo {
+    /// The agent that made the call.
+    pub provenance: AgentPubKey,
+    /// The grant that authorized the call.
+    /// This is `CapGrant::ChainAuthor` when the author calls their own cell.
+    pub grant: CapGrant,
+}
@@ -0,0 +1,37 @@
+//! Types for Distributed Public Key Infrastructure (DPKI)
+use crate::timestamp::Timestamp;
+pub use holochain_serialized_bytes::prelude::*;
+
+/// What DPKI knows about an agent key.
+///
+/// A DPKI DNA's key state function returns one of these for the key it is
+/// asked about.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+pub enum KeyState {
+    /// The key is registered and has not been revoked
+    Valid,
+    /// The key has been revoked, i.e. because the device holding it was lost.
+    /// Ops it signed before the time of the revocation can still be trusted.
+    /// Without a time none of its ops can be.
+    Revoked(Option<Timestamp>),
+    /// The key has never been registered
+    Unknown,
+}
+
+impl KeyState {
+    /// Can a key in this state sign new ops?
+    pub fn is_valid(&self) -> bool {
+        matches!(self, KeyState::Valid)
+    }
+
+    /// Can an op signed by a key in this state at this time be trusted?
+    pub fn is_valid_at(&self, timestamp: Timestamp) -> bool {
+        match self {
+            KeyState::Valid => true,
+            KeyState::Revoked(Some(revoked_at)) => timestamp < *revoked_at,
+            KeyState::Revoked(None) | KeyState::Unknown => false,
+        }
+    }
+}
@@ -244,7 +244,9 @@ fixturator!(
                     granted_functions.insert(GrantedFunctionFixturator::new(Empty).next().unwrap());
                 }
                 granted_functions
-            }, // CurryPayloadsFixturator::new(Empty).next().unwrap(),
+            },
+            CurryPayloadsFixturator::new(Empty).next().unwrap(),
+            CapGrantLimits::default(),
         )
     },
     {
@@ -265,7 +267,8 @@ fixturator!(
                 }
                 granted_functions
             },
-            // CurryPayloadsFixturator::new(Unpredictable).next().unwrap(),
+            CurryPayloadsFixturator::new(Unpredictable).next().unwrap(),
+            CapGrantLimits::default(),
         )
     },
     {
@@ -284,7 +287,8 @@ fixturator!(
                 }
                 granted_functions
             },
-            // CurryPayloadsFixturator::new(Predictable).next().unwrap(),
+            CurryPayloadsFixturator::new(Predictable).next().unwrap(),
+            CapGrantLimits::default(),
         )
     }
 );
@@ -0,0 +1,19 @@
+//! Types for the `genesis_self_check` callback
+
+use holo_hash::AgentPubKey;
+use holochain_serialized_bytes::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
+/// The data a DNA can check before an agent's source chain is written.
+///
+/// `genesis_self_check` runs on the agent's own conductor when a cell is
+/// installed, so an app can refuse a membrane proof that will never pass
+/// validation on the network, e.g. a mistyped invite code.
+/// The callback returns a `ValidateCallbackResult` and has no access
+/// to the network or the source chain.
+pub struct GenesisSelfCheckData {
+    /// The membrane proof the agent is joining with
+    pub membrane_proof: Option<SerializedBytes>,
+    /// The agent that is joining
+    pub agent_key: AgentPubKey,
+}
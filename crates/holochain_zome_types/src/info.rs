@@ -1,7 +1,11 @@
+use crate::capability::CapGrant;
+use crate::cell::CellId;
 use crate::header::ZomeId;
+use crate::zome::FunctionName;
 use crate::zome::ZomeName;
 use holo_hash::AgentPubKey;
 use holo_hash::DnaHash;
+use holo_hash::HeaderHash;
 use holochain_serialized_bytes::prelude::*;
 
 /// The properties of the current dna/zome being called.
@@ -29,11 +33,40 @@ pub struct AgentInfo {
     pub agent_latest_pubkey: AgentPubKey,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct AppInfo;
+/// The app that the current cell is installed in.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq)]
+pub struct AppInfo {
+    /// The id the app was installed with.
+    pub installed_app_id: String,
+    /// Every cell in the app, including the current one, with the nick of
+    /// the slot it belongs to.
+    /// Cloned cells have the same nick as the cell they were cloned from.
+    pub cells: Vec<(String, CellId)>,
+}
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct DnaInfo;
+/// The properties of the current dna.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq)]
+pub struct DnaInfo {
+    /// The name of the dna.
+    pub name: String,
+    /// The hash of the dna.
+    pub hash: DnaHash,
+    /// The properties the dna was installed with.
+    pub properties: SerializedBytes,
+    /// The names of all the zomes in the dna, in order.
+    pub zome_names: Vec<ZomeName>,
+}
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CallInfo;
+/// Information about the current zome call.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq)]
+pub struct CallInfo {
+    /// The agent that made the call.
+    pub provenance: AgentPubKey,
+    /// The grant that authorized the call.
+    pub cap_grant: CapGrant,
+    /// The name of the function being called.
+    pub function_name: FunctionName,
+    /// The head of the source chain when the call started.
+    /// Anything committed during the call comes after this header.
+    pub as_at: HeaderHash,
+}
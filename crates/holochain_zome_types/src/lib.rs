@@ -20,10 +20,12 @@ pub mod capability;
 pub mod cell;
 #[allow(missing_docs)]
 pub mod crdt;
+pub mod dpki;
 pub mod element;
 pub mod entry;
 #[allow(missing_docs)]
 pub mod entry_def;
+pub mod genesis;
 #[allow(missing_docs)]
 pub mod header;
 #[allow(missing_docs)]
@@ -40,6 +42,7 @@ pub mod post_commit;
 pub mod prelude;
 pub mod query;
 pub mod request;
+pub mod schedule;
 pub mod signal;
 pub mod signature;
 pub mod timestamp;
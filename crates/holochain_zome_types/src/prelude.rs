@@ -9,11 +9,13 @@ pub use crate::capability::*;
 pub use crate::cell::*;
 pub use crate::cell::*;
 pub use crate::crdt::*;
+pub use crate::dpki::*;
 pub use crate::element::*;
 pub use crate::entry::*;
 pub use crate::entry::*;
 pub use crate::entry_def::*;
 pub use crate::entry_def::*;
+pub use crate::genesis::*;
 pub use crate::header::conversions::*;
 pub use crate::header::*;
 pub use crate::header::*;
@@ -26,6 +28,7 @@ pub use crate::post_commit::*;
 pub use crate::query::ChainQueryFilter as QueryFilter;
 pub use crate::query::*;
 pub use crate::request::*;
+pub use crate::schedule::*;
 pub use crate::signal::*;
 pub use crate::signature::*;
 pub use crate::timestamp::*;
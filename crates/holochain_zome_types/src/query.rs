@@ -1,9 +1,10 @@
 //! Types for source chain queries
 
+use crate::element::Element;
 use crate::header::EntryType;
 use crate::header::Header;
 use crate::header::HeaderType;
-use crate::warrant::Warrant;
+use crate::warrant::SignedWarrant;
 use holo_hash::HeaderHash;
 pub use holochain_serialized_bytes::prelude::*;
 
@@ -32,14 +33,24 @@ pub struct AgentActivity {
     pub valid_activity: Vec<(u32, HeaderHash)>,
     /// Rejected headers on this chain.
     pub rejected_activity: Vec<(u32, HeaderHash)>,
+    /// Valid elements on this chain when they are requested
+    /// with [`ActivityRequest::Elements`].
+    pub valid_elements: Vec<Element>,
+    /// Rejected elements on this chain when they are requested
+    /// with [`ActivityRequest::Elements`].
+    pub rejected_elements: Vec<RejectedElement>,
     /// The status of this chain.
     pub status: ChainStatus,
     /// The highest chain header that has
     /// been observed by this authority.
     pub highest_observed: Option<HighestObserved>,
-    /// Warrants about this AgentActivity.
-    /// Placeholder for future.
-    pub warrants: Vec<Warrant>,
+    /// Chain fork warrants against the author of this chain.
+    /// The author's own signatures on both headers prove these.
+    pub warrants: Vec<SignedWarrant>,
+    /// Invalid op warrants against the author of this chain.
+    /// The signatures have been checked but the op has not been
+    /// validated again, so these are only the issuer's claim.
+    pub claims: Vec<SignedWarrant>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
@@ -49,6 +60,21 @@ pub enum ActivityRequest {
     Status,
     /// Request all the activity
     Full,
+    /// Request all the activity along with the elements.
+    /// Entries are included when the query asks for them
+    /// and they are public.
+    Elements,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+/// An element which an authority rejected
+pub struct RejectedElement {
+    /// The rejected element
+    pub element: Element,
+    /// Why the element was rejected, as claimed by the issuer
+    /// of an invalid op warrant against the author.
+    /// This is not validated again on receipt.
+    pub reason: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Hash, Eq, serde::Serialize, serde::Deserialize)]
@@ -0,0 +1,34 @@
+//! Types for scheduling zome functions to run at a later time.
+
+use crate::zome::FunctionName;
+use crate::ExternIO;
+
+/// Input to the `schedule` host function.
+///
+/// The scheduled function is always called in the same zome that scheduled
+/// it, as the author of the cell, once `execute_after` has elapsed.
+/// The payload is passed to the scheduled function as its input.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Schedule {
+    /// The name of the function to call once the duration has elapsed.
+    pub scheduled_fn: FunctionName,
+    /// The serialized input for the scheduled function.
+    pub payload: ExternIO,
+    /// How long to wait before calling the function.
+    pub execute_after: core::time::Duration,
+}
+
+impl Schedule {
+    /// Constructor.
+    pub fn new(
+        scheduled_fn: FunctionName,
+        payload: ExternIO,
+        execute_after: core::time::Duration,
+    ) -> Self {
+        Self {
+            scheduled_fn,
+            payload,
+            execute_after,
+        }
+    }
+}
@@ -1,6 +1,74 @@
 //! Types for warrants
+//!
+//! A warrant is evidence that an agent's source chain is invalid.
+//! Its proof carries the author's own signatures, so anyone can check that
+//! the author really did write the offending headers, and it is signed by the
+//! authority which issued it.
+
+use crate::element::SignedHeader;
+use crate::signature::Signature;
+use crate::timestamp::Timestamp;
+use holo_hash::AgentPubKey;
 pub use holochain_serialized_bytes::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
-/// Placeholder for warrant type
-pub struct Warrant;
+/// The evidence against the author of a chain
+pub enum WarrantProof {
+    /// Validation rejected an op about this header
+    InvalidOp {
+        /// The header as signed by its author
+        header: SignedHeader,
+        /// Why validation rejected the op
+        failure: String,
+    },
+    /// The author signed two different headers
+    /// at the same position in their chain
+    ChainFork {
+        /// One header at the fork, as signed by the author
+        first: SignedHeader,
+        /// The other header at the fork, as signed by the author
+        second: SignedHeader,
+    },
+}
+
+impl WarrantProof {
+    /// Evidence that the author signed both of these headers.
+    /// The headers are ordered by their signatures so the same
+    /// fork always makes the same proof, whoever finds it.
+    pub fn chain_fork(a: SignedHeader, b: SignedHeader) -> Self {
+        let (first, second) = if a.signature() <= b.signature() {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        WarrantProof::ChainFork { first, second }
+    }
+
+    /// The agent this is evidence against
+    pub fn author(&self) -> &AgentPubKey {
+        match self {
+            WarrantProof::InvalidOp { header, .. } => header.header().author(),
+            WarrantProof::ChainFork { first, .. } => first.header().author(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+/// A claim by an authority that an agent's chain is invalid
+pub struct Warrant {
+    /// The evidence against the author
+    pub proof: WarrantProof,
+    /// The authority which found the problem
+    pub issuer: AgentPubKey,
+    /// When the warrant was issued
+    pub issued_at: Timestamp,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+/// A warrant signed by its issuer
+pub struct SignedWarrant {
+    /// The content of the warrant
+    pub warrant: Warrant,
+    /// The issuer's signature of the warrant
+    pub signature: Signature,
+}
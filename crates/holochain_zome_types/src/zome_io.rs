@@ -84,13 +84,13 @@ wasm_io_types! {
     // Info about the calling agent.
     fn agent_info (()) -> zt::info::AgentInfo;
 
-    // @todo
+    // Info about the app the current cell is installed in.
     fn app_info (()) -> zt::info::AppInfo;
 
-    // @todo
+    // Info about the current dna.
     fn dna_info (()) -> zt::info::DnaInfo;
 
-    // @todo
+    // Info about the current zome call.
     fn call_info (()) -> zt::info::CallInfo;
 
     // Header hash of the DeleteLink element.
@@ -98,14 +98,14 @@ wasm_io_types! {
 
     fn call (zt::call::Call) -> zt::ZomeCallResponse;
 
-    // @todo List all the local capability claims.
-    fn capability_claims (()) -> ();
+    // List all the live local capability claims, optionally filtered by tag.
+    fn capability_claims (zt::capability::CapQuery) -> Vec<zt::capability::CapClaimInfo>;
 
-    // @todo List all the local capability grants.
-    fn capability_grants (()) -> ();
+    // List all the live local capability grants, optionally filtered by tag.
+    fn capability_grants (zt::capability::CapQuery) -> Vec<zt::capability::CapGrantInfo>;
 
-    // @todo Get the capability for the current zome call.
-    fn capability_info (()) -> ();
+    // Get the capability that authorized the current zome call.
+    fn capability_info (()) -> zt::capability::CapabilityInfo;
 
     // Create a link between two entries.
     fn create_link (zt::link::CreateLinkInput) -> holo_hash::HeaderHash;
@@ -156,8 +156,8 @@ wasm_io_types! {
     // // @todo
     // fn send (()) -> ();
 
-    // @todo
-    fn schedule (core::time::Duration) -> ();
+    // Schedule a function in the current zome to be called after a duration.
+    fn schedule (zt::schedule::Schedule) -> ();
 
     // @todo
     fn sleep (core::time::Duration) -> ();
@@ -0,0 +1,83 @@
+use kitsune_p2p_bootstrap::*;
+use kitsune_p2p_types::dependencies::ghost_actor;
+use structopt::StructOpt;
+
+/// Option Parsing
+#[derive(structopt::StructOpt, Debug)]
+#[structopt(name = "kitsune-bootstrap")]
+pub struct Opt {
+    /// To which network interface / port should we bind?
+    /// Default: "127.0.0.1:0".
+    #[structopt(short = "b", long)]
+    pub bind_to: Option<std::net::SocketAddr>,
+
+    /// Maximum number of agents stored per space.
+    /// Default: 1000.
+    #[structopt(long)]
+    pub space_limit: Option<usize>,
+
+    /// Maximum number of spaces stored.
+    /// Default: 1000.
+    #[structopt(long)]
+    pub max_spaces: Option<usize>,
+
+    /// Maximum number of agents stored across all spaces.
+    /// Default: 100000.
+    #[structopt(long)]
+    pub max_agents: Option<usize>,
+
+    /// Maximum number of agents one `random` query returns.
+    /// Default: 64.
+    #[structopt(long)]
+    pub random_limit: Option<u32>,
+
+    /// Agent infos are dropped this many milliseconds after signing at the
+    /// latest. Default: 3600000 (one hour).
+    #[structopt(long)]
+    pub max_expires_after_ms: Option<u64>,
+}
+
+impl From<Opt> for BootstrapConfig {
+    fn from(o: Opt) -> Self {
+        let mut out = Self::default();
+        if let Some(b) = o.bind_to {
+            out.bind_to = b;
+        }
+        if let Some(l) = o.space_limit {
+            out.space_limit = l;
+        }
+        if let Some(l) = o.max_spaces {
+            out.max_spaces = l;
+        }
+        if let Some(l) = o.max_agents {
+            out.max_agents = l;
+        }
+        if let Some(l) = o.random_limit {
+            out.random_limit = l;
+        }
+        if let Some(ms) = o.max_expires_after_ms {
+            out.max_expires_after_ms = ms;
+        }
+        out
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let _ = ghost_actor::dependencies::tracing::subscriber::set_global_default(
+        tracing_subscriber::FmtSubscriber::builder()
+            .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+            .finish(),
+    );
+
+    let server = match run(Opt::from_args().into()).await {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("{:?}", e);
+            std::process::exit(1);
+        }
+    };
+    println!("{}", server.url());
+
+    let _ = tokio::signal::ctrl_c().await;
+}
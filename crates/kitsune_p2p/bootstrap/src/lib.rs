@@ -0,0 +1,257 @@
+#![deny(missing_docs)]
+//! Self-hosted bootstrap service for kitsune-p2p.
+//!
+//! Serves the same `put` / `now` / `random` api as the public bootstrap
+//! service that `kitsune_p2p::bootstrap` talks to, from an in-memory store,
+//! so private networks can bootstrap without reaching the internet.
+
+use futures::stream::StreamExt;
+use hyper::service::make_service_fn;
+use hyper::service::service_fn;
+use hyper::Body;
+use hyper::Method;
+use hyper::Request;
+use hyper::Response;
+use hyper::Server;
+use hyper::StatusCode;
+use kitsune_p2p::actor::KitsuneP2pResult;
+use kitsune_p2p::agent_store::AgentInfoSigned;
+use kitsune_p2p::bootstrap::RandomQuery;
+use kitsune_p2p::bootstrap::OP_HEADER;
+use kitsune_p2p::bootstrap::OP_NOW;
+use kitsune_p2p::bootstrap::OP_PUT;
+use kitsune_p2p::bootstrap::OP_RANDOM;
+use kitsune_p2p::KitsuneP2pError;
+use kitsune_p2p_types::dependencies::ghost_actor::dependencies::tracing;
+use kitsune_p2p_types::dependencies::serde;
+use parking_lot::Mutex;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use url2::Url2;
+
+mod store;
+pub use store::*;
+
+/// Requests with bigger bodies are refused, agent infos are much smaller.
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// How often expired agent infos are swept out of spaces nobody queries.
+const PRUNE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Configuration for a bootstrap server.
+#[derive(Clone, Debug)]
+pub struct BootstrapConfig {
+    /// Address to listen on, port 0 picks a free port.
+    pub bind_to: SocketAddr,
+    /// Maximum number of agents stored per space.
+    pub space_limit: usize,
+    /// Maximum number of spaces stored.
+    pub max_spaces: usize,
+    /// Maximum number of agents stored across all spaces.
+    pub max_agents: usize,
+    /// Maximum number of agents one `random` query returns.
+    pub random_limit: u32,
+    /// Agent infos are dropped this long after they were signed at the
+    /// latest, whatever expiry they ask for.
+    pub max_expires_after_ms: u64,
+    /// Agent infos signed further in the future than this are refused.
+    pub max_clock_skew_ms: u64,
+}
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        Self {
+            bind_to: ([127, 0, 0, 1], 0).into(),
+            space_limit: 1000,
+            max_spaces: 1000,
+            max_agents: 100_000,
+            random_limit: 64,
+            max_expires_after_ms: 1000 * 60 * 60,
+            max_clock_skew_ms: 1000 * 60,
+        }
+    }
+}
+
+/// A running bootstrap server, it shuts down when dropped.
+pub struct BootstrapServer {
+    addr: SocketAddr,
+    shutdown: Option<futures::channel::oneshot::Sender<()>>,
+}
+
+impl BootstrapServer {
+    /// The address the server is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The url to give `kitsune_p2p::bootstrap` or the `bootstrap_service`
+    /// of a `KitsuneP2pConfig`.
+    pub fn url(&self) -> Url2 {
+        url2::url2!("http://{}", self.addr)
+    }
+}
+
+impl Drop for BootstrapServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+/// Start a bootstrap server on the current tokio runtime.
+pub async fn run(config: BootstrapConfig) -> KitsuneP2pResult<BootstrapServer> {
+    let config = Arc::new(config);
+    let store = Arc::new(Mutex::new(Store::new(&config)));
+
+    let make_svc = {
+        let config = config.clone();
+        let store = store.clone();
+        make_service_fn(move |_| {
+            let config = config.clone();
+            let store = store.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    handle(config.clone(), store.clone(), req)
+                }))
+            }
+        })
+    };
+    let server = Server::try_bind(&config.bind_to)
+        .map_err(KitsuneP2pError::other)?
+        .serve(make_svc);
+    let addr = server.local_addr();
+
+    let (shutdown, stop) = futures::channel::oneshot::channel::<()>();
+    tokio::task::spawn(async move {
+        let server = server.with_graceful_shutdown(async move {
+            let _ = stop.await;
+        });
+        if let Err(e) = server.await {
+            tracing::error!(?e, "bootstrap server failed");
+        }
+    });
+
+    // the sweep stops once the server has dropped the store
+    let store = Arc::downgrade(&store);
+    tokio::task::spawn(async move {
+        let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+        loop {
+            interval.tick().await;
+            match store.upgrade() {
+                Some(store) => store.lock().prune(now_ms()),
+                None => break,
+            }
+        }
+    });
+
+    tracing::info!(%addr, "bootstrap server listening");
+    Ok(BootstrapServer {
+        addr,
+        shutdown: Some(shutdown),
+    })
+}
+
+/// Failed requests answer with a status and a reason, which the client
+/// returns as a `KitsuneP2pError::Bootstrap`.
+type OpResult = Result<Vec<u8>, (StatusCode, String)>;
+
+async fn handle(
+    config: Arc<BootstrapConfig>,
+    store: Arc<Mutex<Store>>,
+    req: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    Ok(match respond(&config, &store, req).await {
+        Ok(body) => Response::new(Body::from(body)),
+        Err((status, reason)) => {
+            let mut res = Response::new(Body::from(reason));
+            *res.status_mut() = status;
+            res
+        }
+    })
+}
+
+async fn respond(config: &BootstrapConfig, store: &Mutex<Store>, req: Request<Body>) -> OpResult {
+    if req.method() != Method::POST {
+        return Err((
+            StatusCode::METHOD_NOT_ALLOWED,
+            "bootstrap ops must be POSTed".into(),
+        ));
+    }
+    let op = req
+        .headers()
+        .get(OP_HEADER)
+        .and_then(|op| op.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let body = read_body(req.into_body()).await?;
+    let now = now_ms();
+
+    match op.as_str() {
+        OP_PUT => {
+            let info_signed: AgentInfoSigned = decode(&body)?;
+            let info = info_signed.verify().await.map_err(bad_request)?;
+            if info.signed_at_ms() > now.saturating_add(config.max_clock_skew_ms) {
+                return Err(bad_request("agent info is signed in the future"));
+            }
+            store
+                .lock()
+                .put(info_signed, &info, now)
+                .map_err(|e| (StatusCode::SERVICE_UNAVAILABLE, e.to_string()))?;
+            encode(())
+        }
+        OP_NOW => encode(now),
+        OP_RANDOM => {
+            let query: RandomQuery = decode(&body)?;
+            let limit = u32::from(query.limit).min(config.random_limit) as usize;
+            let infos = store.lock().random(&query.space, limit, now);
+            let mut out = Vec::with_capacity(infos.len());
+            for info in infos {
+                out.push(serde_bytes::ByteBuf::from(encode(info)?));
+            }
+            encode(out)
+        }
+        _ => Err(bad_request(format!("unknown op {:?}", op))),
+    }
+}
+
+async fn read_body(mut body: Body) -> Result<Vec<u8>, (StatusCode, String)> {
+    let mut out = Vec::new();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(bad_request)?;
+        if out.len() + chunk.len() > MAX_BODY_BYTES {
+            return Err((
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "bootstrap request body too large".into(),
+            ));
+        }
+        out.extend_from_slice(&chunk);
+    }
+    Ok(out)
+}
+
+fn decode<D>(mut data: &[u8]) -> Result<D, (StatusCode, String)>
+where
+    for<'de> D: serde::Deserialize<'de>,
+{
+    kitsune_p2p_types::codec::rmp_decode(&mut data).map_err(bad_request)
+}
+
+fn encode<S: serde::Serialize>(item: S) -> OpResult {
+    let mut out = Vec::new();
+    kitsune_p2p_types::codec::rmp_encode(&mut out, item)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(out)
+}
+
+fn bad_request(e: impl std::fmt::Display) -> (StatusCode, String) {
+    (StatusCode::BAD_REQUEST, e.to_string())
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
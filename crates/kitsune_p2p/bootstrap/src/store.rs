@@ -0,0 +1,286 @@
+//! In-memory store of the agent infos a bootstrap server hands out.
+
+use crate::BootstrapConfig;
+use kitsune_p2p::agent_store::AgentInfo;
+use kitsune_p2p::agent_store::AgentInfoSigned;
+use kitsune_p2p::KitsuneAgent;
+use kitsune_p2p::KitsuneSpace;
+use rand::seq::IteratorRandom;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+struct StoreEntry {
+    info: AgentInfoSigned,
+    signed_at_ms: u64,
+    expires_at_ms: u64,
+}
+
+/// The store already holds as many spaces or agents as it may.
+#[derive(Debug)]
+pub struct StoreFull;
+
+impl std::fmt::Display for StoreFull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bootstrap store is full")
+    }
+}
+
+impl std::error::Error for StoreFull {}
+
+/// Verified agent infos by space then agent, each kept until it expires.
+pub struct Store {
+    space_limit: usize,
+    max_spaces: usize,
+    max_agents: usize,
+    max_expires_after_ms: u64,
+    agent_count: usize,
+    spaces: HashMap<Arc<KitsuneSpace>, HashMap<Arc<KitsuneAgent>, StoreEntry>>,
+}
+
+impl Store {
+    /// An empty store with the limits and expiry cap of the config.
+    pub fn new(config: &BootstrapConfig) -> Self {
+        Self {
+            space_limit: config.space_limit.max(1),
+            max_spaces: config.max_spaces.max(1),
+            max_agents: config.max_agents.max(1),
+            max_expires_after_ms: config.max_expires_after_ms,
+            agent_count: 0,
+            spaces: HashMap::new(),
+        }
+    }
+
+    /// Store agent info that has already been verified.
+    ///
+    /// Info older than what is stored for the agent is ignored, so replays
+    /// can't roll an agent back. When the space is full the agent that
+    /// would expire first makes room, but new spaces and agents are refused
+    /// once the whole store is full, so nobody can exhaust it by making up
+    /// spaces.
+    pub fn put(
+        &mut self,
+        info_signed: AgentInfoSigned,
+        info: &AgentInfo,
+        now_ms: u64,
+    ) -> Result<(), StoreFull> {
+        let expires_after_ms = info.expires_after_ms().min(self.max_expires_after_ms);
+        let entry = StoreEntry {
+            info: info_signed,
+            signed_at_ms: info.signed_at_ms(),
+            expires_at_ms: info.signed_at_ms().saturating_add(expires_after_ms),
+        };
+        if entry.expires_at_ms <= now_ms {
+            return Ok(());
+        }
+
+        let space = Arc::new(info.as_space_ref().clone());
+        let agent = Arc::new(info.as_agent_ref().clone());
+        if self.is_full_for(&space, &agent) {
+            self.prune(now_ms);
+            if self.is_full_for(&space, &agent) {
+                return Err(StoreFull);
+            }
+        }
+
+        let agents = self.spaces.entry(space).or_default();
+        self.agent_count -= retain_unexpired(agents, now_ms);
+
+        match agents.get(&agent) {
+            Some(existing) if existing.signed_at_ms >= entry.signed_at_ms => return Ok(()),
+            Some(_) => (),
+            None if agents.len() >= self.space_limit => {
+                let first = agents
+                    .iter()
+                    .min_by_key(|(_, e)| e.expires_at_ms)
+                    .map(|(a, _)| a.clone());
+                if let Some(first) = first {
+                    agents.remove(&first);
+                    self.agent_count -= 1;
+                }
+            }
+            None => (),
+        }
+        if agents.insert(agent, entry).is_none() {
+            self.agent_count += 1;
+        }
+        Ok(())
+    }
+
+    /// Would storing this agent grow the store past its global limits.
+    /// A new agent in a full space replaces another so doesn't grow it.
+    fn is_full_for(&self, space: &KitsuneSpace, agent: &KitsuneAgent) -> bool {
+        match self.spaces.get(space) {
+            None => self.spaces.len() >= self.max_spaces || self.agent_count >= self.max_agents,
+            Some(agents) => {
+                !agents.contains_key(agent)
+                    && agents.len() < self.space_limit
+                    && self.agent_count >= self.max_agents
+            }
+        }
+    }
+
+    /// Up to `limit` unexpired agent infos from the space in random order.
+    pub fn random(
+        &mut self,
+        space: &Arc<KitsuneSpace>,
+        limit: usize,
+        now_ms: u64,
+    ) -> Vec<AgentInfoSigned> {
+        let agents = match self.spaces.get_mut(space) {
+            Some(agents) => agents,
+            None => return Vec::new(),
+        };
+        self.agent_count -= retain_unexpired(agents, now_ms);
+        let mut out: Vec<AgentInfoSigned> = agents
+            .values()
+            .map(|e| e.info.clone())
+            .choose_multiple(&mut rand::thread_rng(), limit);
+        // choose_multiple keeps the order of the first picks, shuffle them
+        rand::seq::SliceRandom::shuffle(&mut out[..], &mut rand::thread_rng());
+        if agents.is_empty() {
+            self.spaces.remove(space);
+        }
+        out
+    }
+
+    /// Drop expired agent infos, and spaces left empty, from every space.
+    pub fn prune(&mut self, now_ms: u64) {
+        let mut removed = 0;
+        self.spaces.retain(|_, agents| {
+            removed += retain_unexpired(agents, now_ms);
+            !agents.is_empty()
+        });
+        self.agent_count -= removed;
+    }
+
+    /// The number of agents stored in a space, expired or not.
+    pub fn space_len(&self, space: &KitsuneSpace) -> usize {
+        self.spaces.get(space).map(|a| a.len()).unwrap_or(0)
+    }
+
+    /// The number of agents stored in all spaces, expired or not.
+    pub fn len(&self) -> usize {
+        self.agent_count
+    }
+
+    /// True if no agents are stored.
+    pub fn is_empty(&self) -> bool {
+        self.agent_count == 0
+    }
+}
+
+/// Drop the expired agents of a space, returning how many were dropped.
+fn retain_unexpired(agents: &mut HashMap<Arc<KitsuneAgent>, StoreEntry>, now_ms: u64) -> usize {
+    let before = agents.len();
+    agents.retain(|_, e| e.expires_at_ms > now_ms);
+    before - agents.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use kitsune_p2p::fixt::*;
+
+    async fn info_pair(space: &KitsuneSpace) -> (AgentInfoSigned, AgentInfo) {
+        let signed = signed_agent_info(space.clone()).await;
+        let info = signed.verify().await.unwrap();
+        (signed, info)
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_store_space_limit() {
+        let space = fixt!(KitsuneSpace);
+        let mut store = Store::new(&BootstrapConfig {
+            space_limit: 2,
+            max_expires_after_ms: u64::MAX,
+            ..Default::default()
+        });
+        let mut infos = Vec::new();
+        for _ in 0..3 {
+            infos.push(info_pair(&space).await);
+        }
+        let now = infos[0].1.signed_at_ms();
+
+        for (signed, info) in infos.iter() {
+            store.put(signed.clone(), info, now).unwrap();
+        }
+        assert_eq!(2, store.space_len(&space));
+
+        // the latest put is always kept
+        let all = store.random(&Arc::new(space.clone()), 10, now);
+        assert_eq!(2, all.len());
+        assert!(all.contains(&infos[2].0));
+        assert_eq!(1, store.random(&Arc::new(space), 1, now).len());
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_store_expiry() {
+        let space = fixt!(KitsuneSpace);
+        let mut store = Store::new(&BootstrapConfig {
+            max_expires_after_ms: 1000,
+            ..Default::default()
+        });
+        let (signed, info) = info_pair(&space).await;
+        let signed_at = info.signed_at_ms();
+
+        // already expired infos are never stored
+        store.put(signed.clone(), &info, signed_at + 1000).unwrap();
+        assert_eq!(0, store.space_len(&space));
+
+        // the store caps the expiry of long lived infos
+        store.put(signed, &info, signed_at).unwrap();
+        assert_eq!(1, store.space_len(&space));
+        let space = Arc::new(space);
+        assert_eq!(1, store.random(&space, 10, signed_at + 999).len());
+        assert!(store.random(&space, 10, signed_at + 1000).is_empty());
+        assert_eq!(0, store.space_len(&space));
+
+        let (signed, info) = info_pair(&space).await;
+        store.put(signed, &info, signed_at).unwrap();
+        assert_eq!(1, store.space_len(&space));
+        store.prune(info.signed_at_ms() + 1000);
+        assert_eq!(0, store.space_len(&space));
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_store_global_limits() {
+        let mut store = Store::new(&BootstrapConfig {
+            max_spaces: 2,
+            max_agents: 3,
+            max_expires_after_ms: 500,
+            ..Default::default()
+        });
+        let spaces = vec![
+            fixt!(KitsuneSpace),
+            fixt!(KitsuneSpace),
+            fixt!(KitsuneSpace),
+        ];
+        let (a, a_info) = info_pair(&spaces[0]).await;
+        let now = a_info.signed_at_ms();
+        store.put(a.clone(), &a_info, now).unwrap();
+        let (b, b_info) = info_pair(&spaces[1]).await;
+        store.put(b, &b_info, now).unwrap();
+
+        // no room for a third space
+        let (c, c_info) = info_pair(&spaces[2]).await;
+        assert!(store.put(c, &c_info, now).is_err());
+        assert_eq!(0, store.space_len(&spaces[2]));
+
+        // known spaces fill up to the agent cap
+        let (d, d_info) = info_pair(&spaces[0]).await;
+        store.put(d, &d_info, now).unwrap();
+        let (e, e_info) = info_pair(&spaces[1]).await;
+        assert!(store.put(e, &e_info, now).is_err());
+        assert_eq!(3, store.len());
+
+        // known agents can still refresh their info
+        store.put(a, &a_info, now).unwrap();
+
+        // and room frees up once infos expire
+        tokio::time::delay_for(std::time::Duration::from_millis(600)).await;
+        let (c, c_info) = info_pair(&spaces[2]).await;
+        store.put(c, &c_info, c_info.signed_at_ms()).unwrap();
+        assert_eq!(1, store.len());
+    }
+}
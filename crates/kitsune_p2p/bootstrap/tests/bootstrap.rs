@@ -0,0 +1,119 @@
+use ::fixt::prelude::*;
+use kitsune_p2p::agent_store::AgentInfoSigned;
+use kitsune_p2p::bootstrap;
+use kitsune_p2p::fixt::*;
+use kitsune_p2p::KitsuneSpace;
+use kitsune_p2p_bootstrap::*;
+use std::sync::Arc;
+
+async fn random(
+    server: &BootstrapServer,
+    space: &KitsuneSpace,
+    limit: u32,
+) -> Vec<AgentInfoSigned> {
+    let mut out = bootstrap::random(
+        Some(server.url()),
+        bootstrap::RandomQuery {
+            space: Arc::new(space.clone()),
+            limit: limit.into(),
+        },
+    )
+    .await
+    .unwrap();
+    out.sort();
+    out
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn test_put_random() {
+    let server = run(BootstrapConfig::default()).await.unwrap();
+    let space = fixt!(KitsuneSpace);
+
+    let mut expected = Vec::new();
+    for _ in 0..2 {
+        let info = signed_agent_info(space.clone()).await;
+        bootstrap::put(Some(server.url()), info.clone())
+            .await
+            .unwrap();
+        expected.push(info);
+    }
+    expected.sort();
+
+    assert_eq!(expected, random(&server, &space, 16).await);
+    let single = random(&server, &space, 1).await;
+    assert_eq!(1, single.len());
+    assert!(expected.contains(&single[0]));
+
+    // other spaces are empty
+    assert!(random(&server, &fixt!(KitsuneSpace), 16).await.is_empty());
+
+    // infos without a valid signature are refused
+    assert!(bootstrap::put(Some(server.url()), fixt!(AgentInfoSigned))
+        .await
+        .is_err());
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn test_now() {
+    let server = run(BootstrapConfig::default()).await.unwrap();
+    let local_now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+
+    let remote_now = bootstrap::now(Some(server.url())).await.unwrap();
+    assert!(remote_now >= local_now);
+    assert!(remote_now - local_now < 5000);
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn test_space_limit() {
+    let server = run(BootstrapConfig {
+        space_limit: 2,
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    let space = fixt!(KitsuneSpace);
+
+    for _ in 0..3 {
+        let info = signed_agent_info(space.clone()).await;
+        bootstrap::put(Some(server.url()), info).await.unwrap();
+    }
+    assert_eq!(2, random(&server, &space, 16).await.len());
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn test_expiry() {
+    let server = run(BootstrapConfig {
+        max_expires_after_ms: 500,
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    let space = fixt!(KitsuneSpace);
+
+    let info = signed_agent_info(space.clone()).await;
+    bootstrap::put(Some(server.url()), info).await.unwrap();
+    assert_eq!(1, random(&server, &space, 16).await.len());
+
+    tokio::time::delay_for(std::time::Duration::from_millis(600)).await;
+    assert!(random(&server, &space, 16).await.is_empty());
+}
+
+#[tokio::test(threaded_scheduler)]
+async fn test_store_full() {
+    let server = run(BootstrapConfig {
+        max_spaces: 1,
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let info = signed_agent_info(fixt!(KitsuneSpace)).await;
+    bootstrap::put(Some(server.url()), info).await.unwrap();
+
+    // fresh spaces can't be used to fill the server up
+    let info = signed_agent_info(fixt!(KitsuneSpace)).await;
+    assert!(bootstrap::put(Some(server.url()), info).await.is_err());
+}
@@ -17,24 +17,43 @@ pub const BOOTSTRAP_SERVICE_DEV: &str = "https://bootstrap-dev.holohost.workers.
 #[allow(missing_docs)]
 pub struct KitsuneP2pTuningParams {
     pub gossip_loop_iteration_delay_ms: u32,
+    pub gossip_time_window_s: u32,
     pub default_notify_remote_agent_count: u32,
     pub default_notify_timeout_ms: u32,
     pub default_rpc_single_timeout_ms: u32,
     pub default_rpc_multi_remote_agent_count: u32,
     pub default_rpc_multi_timeout_ms: u32,
     pub agent_info_expires_after_ms: u32,
+    pub arc_target_redundancy: u32,
+    pub arc_storage_limit_ops: u32,
 }
 
 impl Default for KitsuneP2pTuningParams {
     fn default() -> Self {
         Self {
             gossip_loop_iteration_delay_ms: 10,
+            gossip_time_window_s: 60 * 60 * 24, // 1 day
             default_notify_remote_agent_count: 5,
             default_notify_timeout_ms: 1000,
             default_rpc_single_timeout_ms: 2000,
             default_rpc_multi_remote_agent_count: 2,
             default_rpc_multi_timeout_ms: 2000,
             agent_info_expires_after_ms: 1000 * 60 * 20, // 20 minutes
+            arc_target_redundancy: 50,
+            arc_storage_limit_ops: 0, // no limit
+        }
+    }
+}
+
+impl KitsuneP2pTuningParams {
+    /// The limits local agents' arcs are resized within.
+    pub fn arc_resize_params(&self) -> crate::dht_arc::ArcResizeParams {
+        crate::dht_arc::ArcResizeParams {
+            target_redundancy: self.arc_target_redundancy,
+            storage_limit_ops: match self.arc_storage_limit_ops {
+                0 => None,
+                limit => Some(limit as u64),
+            },
         }
     }
 }
@@ -50,6 +69,10 @@ impl serde::Serialize for KitsuneP2pTuningParams {
             "gossip_loop_iteration_delay_ms",
             &format!("{}", self.gossip_loop_iteration_delay_ms),
         )?;
+        m.serialize_entry(
+            "gossip_time_window_s",
+            &format!("{}", self.gossip_time_window_s),
+        )?;
         m.serialize_entry(
             "default_notify_remote_agent_count",
             &format!("{}", self.default_notify_remote_agent_count),
@@ -74,6 +97,14 @@ impl serde::Serialize for KitsuneP2pTuningParams {
             "agent_info_expires_after_ms",
             &format!("{}", self.agent_info_expires_after_ms),
         )?;
+        m.serialize_entry(
+            "arc_target_redundancy",
+            &format!("{}", self.arc_target_redundancy),
+        )?;
+        m.serialize_entry(
+            "arc_storage_limit_ops",
+            &format!("{}", self.arc_storage_limit_ops),
+        )?;
         m.end()
     }
 }
@@ -91,6 +122,10 @@ impl<'de> serde::Deserialize<'de> for KitsuneP2pTuningParams {
                     Ok(v) => out.gossip_loop_iteration_delay_ms = v,
                     Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
                 },
+                "gossip_time_window_s" => match v.parse::<u32>() {
+                    Ok(v) => out.gossip_time_window_s = v,
+                    Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
+                },
                 "default_notify_remote_agent_count" => match v.parse::<u32>() {
                     Ok(v) => out.default_notify_remote_agent_count = v,
                     Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
@@ -115,6 +150,14 @@ impl<'de> serde::Deserialize<'de> for KitsuneP2pTuningParams {
                     Ok(v) => out.agent_info_expires_after_ms = v,
                     Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
                 },
+                "arc_target_redundancy" => match v.parse::<u32>() {
+                    Ok(v) => out.arc_target_redundancy = v,
+                    Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
+                },
+                "arc_storage_limit_ops" => match v.parse::<u32>() {
+                    Ok(v) => out.arc_storage_limit_ops = v,
+                    Err(e) => tracing::warn!("failed to parse {}: {}", k, e),
+                },
                 _ => tracing::warn!("INVALID TUNING PARAM: '{}'", k),
             }
         }
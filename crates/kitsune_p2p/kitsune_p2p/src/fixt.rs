@@ -4,11 +4,13 @@ use crate::agent_store::AgentInfo;
 use crate::agent_store::AgentInfoSigned;
 use crate::agent_store::Urls;
 use crate::dependencies::url2;
+use crate::dht_arc::DhtArc;
 use crate::KitsuneAgent;
 use crate::KitsuneBinType;
 use crate::KitsuneSignature;
 use crate::KitsuneSpace;
 use ::fixt::prelude::*;
+use lair_keystore_api::internal::sign_ed25519::sign_ed25519_keypair_new_from_entropy;
 use url2::url2;
 
 fixturator!(
@@ -51,9 +53,14 @@ fixturator!(
     from SixtyFourBytes;
 );
 
+fixturator!(
+    DhtArc;
+    constructor fn new(U32, U32);
+);
+
 fixturator!(
     AgentInfo;
-    constructor fn new(KitsuneSpace, KitsuneAgent, Urls, U64, U64);
+    constructor fn new(KitsuneSpace, KitsuneAgent, Urls, U64, U64, DhtArc);
 );
 
 fixturator!(
@@ -61,28 +68,56 @@ fixturator!(
     curve Empty {
         let mut data = Vec::new();
         kitsune_p2p_types::codec::rmp_encode(&mut data, &fixt!(AgentInfo, Empty)).unwrap();
-        AgentInfoSigned::try_new(
+        AgentInfoSigned::new_unchecked(
             fixt!(KitsuneAgent, Empty),
             fixt!(KitsuneSignature, Empty),
             data,
-        ).unwrap()
+        )
     };
     curve Unpredictable {
         let mut data = Vec::new();
         kitsune_p2p_types::codec::rmp_encode(&mut data, &fixt!(AgentInfo)).unwrap();
-        AgentInfoSigned::try_new(
+        AgentInfoSigned::new_unchecked(
             fixt!(KitsuneAgent),
             fixt!(KitsuneSignature),
             data,
-        ).unwrap()
+        )
     };
     curve Predictable {
         let mut data = Vec::new();
         kitsune_p2p_types::codec::rmp_encode(&mut data, &fixt!(AgentInfo, Predictable)).unwrap();
-        AgentInfoSigned::try_new(
+        AgentInfoSigned::new_unchecked(
             fixt!(KitsuneAgent, Predictable),
             fixt!(KitsuneSignature, Predictable),
             data,
-        ).unwrap()
+        )
     };
 );
+
+/// Agent info for a new agent in this space, signed by that agent
+/// so it passes verification.
+pub async fn signed_agent_info(space: KitsuneSpace) -> AgentInfoSigned {
+    let keypair = sign_ed25519_keypair_new_from_entropy().await.unwrap();
+    let agent = KitsuneAgent::new((*keypair.pub_key.0).clone());
+    let signed_at_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    let agent_info = AgentInfo::new(
+        space,
+        agent.clone(),
+        fixt!(Urls),
+        signed_at_ms,
+        1000 * 60 * 20,
+        DhtArc::new(agent.get_loc(), crate::dht_arc::MAX_HALF_LENGTH),
+    );
+    let mut data = Vec::new();
+    kitsune_p2p_types::codec::rmp_encode(&mut data, &agent_info).unwrap();
+    let signature = keypair
+        .sign(std::sync::Arc::new(data.clone()))
+        .await
+        .unwrap();
+    AgentInfoSigned::try_new(agent, KitsuneSignature((*signature.0).clone()), data)
+        .await
+        .unwrap()
+}
@@ -2,6 +2,7 @@ use crate::actor::*;
 use crate::event::*;
 
 mod actor;
+pub use actor::bootstrap;
 use actor::*;
 
 /// Spawn a new KitsuneP2p actor.
@@ -17,7 +17,8 @@ use std::sync::Arc;
 
 /// The bootstrap service is much more thoroughly documented in the default service implementation.
 /// See https://github.com/holochain/bootstrap
-mod bootstrap;
+/// and the in-tree `kitsune_p2p_bootstrap` server.
+pub mod bootstrap;
 mod discover;
 mod gossip;
 mod space;
@@ -39,6 +40,8 @@ pub(crate) struct KitsuneP2pActor {
     transport: ghost_actor::GhostSender<TransportListener>,
     spaces: HashMap<Arc<KitsuneSpace>, AsyncLazy<ghost_actor::GhostSender<KitsuneP2p>>>,
     config: Arc<KitsuneP2pConfig>,
+    /// When remote agents last started gossip with our agents
+    gossip_heard: gossip::GossipHeard,
 }
 
 fn build_transport(
@@ -119,10 +122,13 @@ impl KitsuneP2pActor {
             t_pool.push_sub_transport(l, e).await?;
         }
 
+        let gossip_heard = gossip::GossipHeard::default();
         tokio::task::spawn({
             let evt_sender = evt_sender.clone();
+            let gossip_heard = gossip_heard.clone();
             t_event.for_each_concurrent(/* limit */ 10, move |event| {
                 let evt_sender = evt_sender.clone();
+                let gossip_heard = gossip_heard.clone();
                 async move {
                     let evt_sender = &evt_sender;
                     match event {
@@ -197,6 +203,12 @@ impl KitsuneP2pActor {
                                     until_utc_epoch_s,
                                     last_count,
                                 }) => {
+                                    // the remote agent started gossip with our agent
+                                    gossip_heard.heard(
+                                        space.clone(),
+                                        to_agent.clone(),
+                                        from_agent.clone(),
+                                    );
                                     let input = ReqOpHashesEvt::new(
                                         from_agent,
                                         to_agent,
@@ -222,19 +234,7 @@ impl KitsuneP2pActor {
                                         }
                                         Ok(r) => r,
                                     };
-                                    let hashes = match last_count {
-                                        OpCount::Consistent(last_count) => {
-                                            // Requester is consistent,
-                                            // now check if we are consistent.
-                                            if last_count == hashes.len() as u64 {
-                                                OpConsistency::Consistent
-                                            } else {
-                                                OpConsistency::Variance(hashes)
-                                            }
-                                        }
-                                        // Requester has a variance so we must return hashes.
-                                        OpCount::Variance => OpConsistency::Variance(hashes),
-                                    };
+                                    let hashes = OpConsistency::reconcile(&last_count, hashes);
                                     let resp =
                                         wire::Wire::fetch_op_hashes_response(hashes, agent_hashes)
                                             .encode_vec()
@@ -350,6 +350,7 @@ impl KitsuneP2pActor {
             transport,
             spaces: HashMap::new(),
             config: Arc::new(config),
+            gossip_heard,
         })
     }
 }
@@ -415,7 +416,14 @@ impl KitsuneP2pEventHandler for KitsuneP2pActor {
         &mut self,
         input: crate::event::PutAgentInfoSignedEvt,
     ) -> KitsuneP2pEventHandlerResult<()> {
-        Ok(self.evt_sender.put_agent_info_signed(input))
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            // last line of defense - nothing unverified reaches the store
+            input.agent_info_signed.verify().await?;
+            evt_sender.put_agent_info_signed(input).await
+        }
+        .boxed()
+        .into())
     }
 
     fn handle_get_agent_info_signed(
@@ -512,10 +520,11 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         let space2 = space.clone();
         let transport = self.transport.clone();
         let config = Arc::clone(&self.config);
+        let gossip_heard = self.gossip_heard.clone();
         let space_sender = match self.spaces.entry(space.clone()) {
             Entry::Occupied(entry) => entry.into_mut(),
             Entry::Vacant(entry) => entry.insert(AsyncLazy::new(async move {
-                let (send, evt_recv) = spawn_space(space2, transport, config)
+                let (send, evt_recv) = spawn_space(space2, transport, config, gossip_heard)
                     .await
                     .expect("cannot fail to create space");
                 internal_sender
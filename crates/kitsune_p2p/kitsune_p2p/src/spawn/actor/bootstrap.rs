@@ -25,13 +25,13 @@ const RANDOM_LIMIT_DEFAULT: u32 = 16;
 pub static NOW_OFFSET_MILLIS: OnceCell<i64> = OnceCell::new();
 
 /// The HTTP header name for setting the op on POST requests.
-const OP_HEADER: &str = "X-Op";
+pub const OP_HEADER: &str = "X-Op";
 /// The header op to tell the service to put a signed agent info.
-const OP_PUT: &str = "put";
+pub const OP_PUT: &str = "put";
 /// The header op to tell the service to return its opinion of 'now' in milliseconds.
-const OP_NOW: &str = "now";
+pub const OP_NOW: &str = "now";
 /// The header op to tell the service to return a random set of agents in a specific space.
-const OP_RANDOM: &str = "random";
+pub const OP_RANDOM: &str = "random";
 
 /// Standard interface to the remote bootstrap service.
 ///
@@ -145,9 +145,9 @@ pub async fn now_once(url: Option<Url2>) -> crate::types::actor::KitsuneP2pResul
 /// Struct to be encoded for the `random` op.
 #[derive(serde::Deserialize, serde::Serialize)]
 pub struct RandomQuery {
-    // The space to get random agents from.
+    /// The space to get random agents from.
     pub space: Arc<KitsuneSpace>,
-    // The maximum number of random agents to retrieve for this query.
+    /// The maximum number of random agents to retrieve for this query.
     pub limit: RandomLimit,
 }
 
@@ -162,6 +162,7 @@ impl Default for RandomQuery {
     }
 }
 
+/// The maximum number of agents a `random` query returns.
 #[derive(serde::Deserialize, serde::Serialize, derive_more::From, derive_more::Into)]
 pub struct RandomLimit(u32);
 
@@ -227,6 +228,7 @@ mod tests {
             urls,
             (millis - 100).try_into().unwrap(),
             1000 * 60 * 20,
+            fixt!(DhtArc),
         );
         let mut data = Vec::new();
         kitsune_p2p_types::codec::rmp_encode(&mut data, &agent_info).unwrap();
@@ -236,6 +238,7 @@ mod tests {
             .unwrap();
         let agent_info_signed =
             AgentInfoSigned::try_new(agent, KitsuneSignature((*signature.0).clone()), data)
+                .await
                 .unwrap();
 
         // Simply hitting the endpoint should be OK.
@@ -312,6 +315,7 @@ mod tests {
                 fixt!(Urls),
                 now,
                 1000 * 60 * 20,
+                fixt!(DhtArc),
             );
             let mut data = Vec::new();
             kitsune_p2p_types::codec::rmp_encode(&mut data, &agent_info).unwrap();
@@ -321,6 +325,7 @@ mod tests {
                 KitsuneSignature((*signature.0).clone()),
                 data,
             )
+            .await
             .unwrap();
 
             super::put(
@@ -129,6 +129,9 @@ pub(crate) fn peer_discover(
             }))
             .await?;
 
+            // don't trust or connect to the result unless it verifies
+            let info = req_info.verify().await?;
+
             // we got a result - let's add it to our store for the future
             let _ = evt_sender
                 .put_agent_info_signed(PutAgentInfoSignedEvt {
@@ -139,7 +142,6 @@ pub(crate) fn peer_discover(
                 .await;
 
             // we got a result, try to connect to it
-            let info = types::agent_store::AgentInfo::try_from(&req_info)?;
             let url = info
                 .as_urls_ref()
                 .get(0)
@@ -189,8 +191,7 @@ pub(crate) fn message_neighborhood<T, F>(
     target_node_count: u8,
     stage_1_timeout_if_any_ms: u64,
     stage_2_timeout_even_if_none_ms: u64,
-    // ignored while full-sync
-    _basis: Arc<KitsuneBasis>,
+    basis: Arc<KitsuneBasis>,
     payload: wire::Wire,
     accept_result_cb: F,
 ) -> MustBoxFuture<'static, Vec<T>>
@@ -238,7 +239,7 @@ where
             if let Ok(nodes) = get_5_or_less_non_local_agents_near_basis(
                 space.clone(),
                 from_agent.clone(),
-                _basis.clone(),
+                basis.clone(),
                 i_s.clone(),
                 evt_sender.clone(),
                 bootstrap_service.clone(),
@@ -307,16 +308,18 @@ where
 }
 
 /// search for agents to contact
+/// only agents whose storage arc covers the basis are returned, so agents
+/// that have shrunk their arc are not sent data they won't hold
 pub(crate) fn get_5_or_less_non_local_agents_near_basis(
     space: Arc<KitsuneSpace>,
     from_agent: Arc<KitsuneAgent>,
-    // ignored while full-sync
-    _basis: Arc<KitsuneBasis>,
+    basis: Arc<KitsuneBasis>,
     i_s: ghost_actor::GhostSender<SpaceInternal>,
     evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
     bootstrap_service: Option<url2::Url2>,
 ) -> MustBoxFuture<'static, KitsuneP2pResult<HashSet<AgentInfo>>> {
     async move {
+        let basis_loc = basis.get_loc();
         let mut out = HashSet::new();
 
         if let Ok(mut list) = evt_sender
@@ -330,6 +333,9 @@ pub(crate) fn get_5_or_less_non_local_agents_near_basis(
             rand::seq::SliceRandom::shuffle(&mut list[..], &mut rand::thread_rng());
             for item in list {
                 if let Ok(info) = AgentInfo::try_from(&item) {
+                    if !info.storage_arc().contains(basis_loc) {
+                        continue;
+                    }
                     if let Ok(is_local) = i_s
                         .is_agent_local(Arc::new(info.as_agent_ref().clone()))
                         .await
@@ -356,8 +362,8 @@ pub(crate) fn get_5_or_less_non_local_agents_near_basis(
         .await
         {
             for item in list {
-                // TODO - someday some validation here
-                if let Ok(info) = AgentInfo::try_from(&item) {
+                // bootstrap results are only as good as their signatures
+                if let Ok(info) = item.verify().await {
                     if let Ok(is_local) = i_s
                         .is_agent_local(Arc::new(info.as_agent_ref().clone()))
                         .await
@@ -371,7 +377,9 @@ pub(crate) fn get_5_or_less_non_local_agents_near_basis(
                                     agent_info_signed: item.clone(),
                                 })
                                 .await;
-                            out.insert(info);
+                            if info.storage_arc().contains(basis_loc) {
+                                out.insert(info);
+                            }
                         }
                     }
                 }
@@ -409,8 +417,8 @@ pub(crate) fn add_5_or_less_non_local_agents(
         .await
         {
             for item in list {
-                // TODO - someday some validation here
-                if let Ok(info) = AgentInfo::try_from(&item) {
+                // bootstrap results are only as good as their signatures
+                if let Ok(info) = item.verify().await {
                     if let Ok(is_local) = i_s
                         .is_agent_local(Arc::new(info.as_agent_ref().clone()))
                         .await
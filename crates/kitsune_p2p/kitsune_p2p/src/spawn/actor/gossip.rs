@@ -1,5 +1,13 @@
-//! This is a temporary quick-hack gossip module for use with the
-//! in-memory / full-sync / non-sharded networking module
+//! Gossip between the agents of a space.
+//!
+//! Each pair of agents only gossips the part of the dht they both store,
+//! the overlap of their arcs. Ops are compared one bounded time window at
+//! a time: the current window every round, and one page of older windows
+//! each round, walking back through history until a page is empty.
+//! Ops are windowed by the time they were authored, so an op is in the
+//! same window on every node.
+//! Each window is reconciled by sending a fingerprint of our hashes,
+//! so only the hashes in buckets that differ come back.
 
 use crate::types::actor::KitsuneP2pResult;
 use crate::types::gossip::*;
@@ -11,6 +19,7 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::iter::FromIterator;
 use std::sync::Arc;
+use std::time::Instant;
 
 ghost_actor::ghost_chan! {
     /// "Event" requests emitted by the gossip module
@@ -37,23 +46,77 @@ ghost_actor::ghost_chan! {
 
 pub type GossipEventReceiver = futures::channel::mpsc::Receiver<GossipEvent>;
 
+/// When remote agents last started gossip with our local agents.
+/// Incoming gossip is received outside of the space so this is shared
+/// between the incoming requests and the gossip module of each space.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct GossipHeard(
+    Arc<std::sync::Mutex<HashMap<(Arc<KitsuneSpace>, GossipPairKey), Instant>>>,
+);
+
+/// A local agent and the remote agent it gossips with
+type GossipPairKey = (Arc<KitsuneAgent>, Arc<KitsuneAgent>);
+
+impl GossipHeard {
+    /// A remote agent has started gossip with a local agent
+    pub fn heard(
+        &self,
+        space: Arc<KitsuneSpace>,
+        local_agent: Arc<KitsuneAgent>,
+        remote_agent: Arc<KitsuneAgent>,
+    ) {
+        self.0
+            .lock()
+            .expect("gossip heard lock poisoned")
+            .insert((space, (local_agent, remote_agent)), Instant::now());
+    }
+
+    /// Has the remote agent started gossip with the local agent since this time?
+    fn heard_since(&self, space: &Arc<KitsuneSpace>, pair: &GossipPairKey, since: Instant) -> bool {
+        self.0
+            .lock()
+            .expect("gossip heard lock poisoned")
+            .get(&(space.clone(), pair.clone()))
+            .map_or(false, |heard| *heard >= since)
+    }
+
+    /// Forget the pairs of this space that are no longer neighbors
+    fn retain(&self, space: &Arc<KitsuneSpace>, pairs: &HashSet<GossipPairKey>) {
+        self.0
+            .lock()
+            .expect("gossip heard lock poisoned")
+            .retain(|(s, pair), _| s != space || pairs.contains(pair));
+    }
+}
+
 /// spawn a gossip module to control gossip for a space
-pub fn spawn_gossip_module(config: Arc<KitsuneP2pConfig>) -> GossipEventReceiver {
+pub(crate) fn spawn_gossip_module(
+    config: Arc<KitsuneP2pConfig>,
+    space: Arc<KitsuneSpace>,
+    heard: GossipHeard,
+) -> GossipEventReceiver {
     let (evt_send, evt_recv) = futures::channel::mpsc::channel(10);
 
-    tokio::task::spawn(gossip_loop(config, evt_send));
+    tokio::task::spawn(gossip_loop(config, space, heard, evt_send));
 
     evt_recv
 }
 
-#[tracing::instrument(skip(evt_send))]
+#[tracing::instrument(skip(evt_send, heard))]
 /// the gossip module is not an actor because we want to pause while
 /// awaiting requests - not process requests in parallel.
 async fn gossip_loop(
     config: Arc<KitsuneP2pConfig>,
+    space: Arc<KitsuneSpace>,
+    heard: GossipHeard,
     evt_send: futures::channel::mpsc::Sender<GossipEvent>,
 ) -> KitsuneP2pResult<()> {
-    let mut gossip_data = GossipData::new(evt_send);
+    let mut gossip_data = GossipData::new(
+        evt_send,
+        space,
+        heard,
+        config.tuning_params.gossip_time_window_s as i64,
+    );
     loop {
         match gossip_data.take_action().await {
             Err(KitsuneP2pError::GhostError(GhostError::Disconnected)) => {
@@ -73,18 +136,51 @@ async fn gossip_loop(
     }
 }
 
+/// Two agents that will gossip and the parts of the dht they both store.
+#[derive(Debug)]
+struct GossipPair {
+    from_agent: Arc<KitsuneAgent>,
+    to_agent: Arc<KitsuneAgent>,
+    overlap: Vec<DhtArc>,
+}
+
+/// How far a pair has got through gossiping their history.
+#[derive(Debug, Clone, Copy)]
+enum HistoryPage {
+    /// The window of this length that ends at this time.
+    Window(i64),
+    /// Everything before this time.
+    /// Once a window is empty there is unlikely to be much left
+    /// so the rest of history is checked in one go.
+    Rest(i64),
+}
+
 struct GossipData {
     evt_send: futures::channel::mpsc::Sender<GossipEvent>,
-    pending_gossip_list: Vec<(Arc<KitsuneAgent>, Arc<KitsuneAgent>)>,
-    last_counts: HashMap<Arc<KitsuneAgent>, (u64, u64)>,
+    space: Arc<KitsuneSpace>,
+    heard: GossipHeard,
+    time_window_s: i64,
+    pending_gossip_list: Vec<GossipPair>,
+    history: HashMap<GossipPairKey, HistoryPage>,
+    /// When the previous round of gossip started
+    previous_round: Option<Instant>,
 }
 
 impl GossipData {
-    pub fn new(evt_send: futures::channel::mpsc::Sender<GossipEvent>) -> Self {
+    pub fn new(
+        evt_send: futures::channel::mpsc::Sender<GossipEvent>,
+        space: Arc<KitsuneSpace>,
+        heard: GossipHeard,
+        time_window_s: i64,
+    ) -> Self {
         Self {
             evt_send,
+            space,
+            heard,
+            time_window_s: std::cmp::max(time_window_s, 1),
             pending_gossip_list: Vec::new(),
-            last_counts: HashMap::new(),
+            history: HashMap::new(),
+            previous_round: None,
         }
     }
 
@@ -98,97 +194,193 @@ impl GossipData {
     }
 
     async fn fetch_pending_gossip_list(&mut self) -> KitsuneP2pResult<()> {
+        let round_started = Instant::now();
         let (local_agents, remote_agents) = self.evt_send.list_neighbor_agents().await?;
-        // super naive gossip just processes all combinations
-        // also causes duplication because it runs pairs from both sides
-        for (i, a1) in local_agents.iter().enumerate() {
-            for a2 in local_agents.iter().skip(i) {
-                // at the very least, avoid gossiping with ourselves
-                if a1 != a2 {
-                    self.pending_gossip_list.push((a1.clone(), a2.clone()));
+        let mut pairs = HashSet::new();
+        for (i, (a1, arc1)) in local_agents.iter().enumerate() {
+            // each pair of local agents only gossips once
+            for (a2, arc2) in local_agents.iter().skip(i + 1) {
+                if pairs.insert((a1.clone(), a2.clone())) {
+                    self.push_pair(a1, arc1, a2, arc2);
                 }
             }
-            for a2 in remote_agents.iter() {
-                self.pending_gossip_list.push((a1.clone(), a2.clone()));
+            for (a2, arc2) in remote_agents.iter() {
+                let pair = (a1.clone(), a2.clone());
+                if self.starts_remote_gossip(&pair) && pairs.insert(pair) {
+                    self.push_pair(a1, arc1, a2, arc2);
+                }
             }
         }
+        // forget the progress of pairs that are no longer neighbors
+        self.history.retain(|pair, _| pairs.contains(pair));
+        let remote_pairs = local_agents
+            .iter()
+            .flat_map(|(a1, _)| {
+                remote_agents
+                    .iter()
+                    .map(move |(a2, _)| (a1.clone(), a2.clone()))
+            })
+            .collect();
+        self.heard.retain(&self.space, &remote_pairs);
+        self.previous_round = Some(round_started);
         Ok(())
     }
 
+    /// Does the local agent start gossip with the remote agent this round?
+    /// The remote node is running this same loop so the lower agent of the
+    /// pair starts the gossip. The remote agent might not know about the
+    /// local agent though, so the higher agent also starts it if it hasn't
+    /// heard from the lower agent for a whole round.
+    fn starts_remote_gossip(&self, (local_agent, remote_agent): &GossipPairKey) -> bool {
+        if local_agent < remote_agent {
+            return true;
+        }
+        match self.previous_round {
+            Some(previous_round) => !self.heard.heard_since(
+                &self.space,
+                &(local_agent.clone(), remote_agent.clone()),
+                previous_round,
+            ),
+            // give the lower agent a round to start
+            None => false,
+        }
+    }
+
+    fn push_pair(
+        &mut self,
+        from_agent: &Arc<KitsuneAgent>,
+        from_arc: &DhtArc,
+        to_agent: &Arc<KitsuneAgent>,
+        to_arc: &DhtArc,
+    ) {
+        let overlap = from_arc.overlap(to_arc);
+        // agents that don't store any of the same ops have nothing to gossip
+        if !overlap.is_empty() {
+            self.pending_gossip_list.push(GossipPair {
+                from_agent: from_agent.clone(),
+                to_agent: to_agent.clone(),
+                overlap,
+            });
+        }
+    }
+
     #[tracing::instrument(skip(self))]
     async fn process_next_gossip(&mut self) -> KitsuneP2pResult<()> {
         // !is_empty() checked above in take_action
-        let (from_agent, to_agent) = self.pending_gossip_list.remove(0);
-        let span = tracing::debug_span!("next_gossip", ?from_agent, ?to_agent);
+        let GossipPair {
+            from_agent,
+            to_agent,
+            overlap,
+        } = self.pending_gossip_list.remove(0);
 
-        // Get the last count for this interaction
-        let last_count = self.last_counts.entry(to_agent.clone()).or_insert((0, 0));
+        // new ops land in the current window so it's gossiped every round
+        let now = now_s()?;
+        let current = now - now.rem_euclid(self.time_window_s);
+        for arc in &overlap {
+            self.gossip_window(&from_agent, &to_agent, *arc, current, i64::MAX)
+                .await?;
+        }
+
+        // then one page of history
+        let key = (from_agent.clone(), to_agent.clone());
+        let page = *self
+            .history
+            .get(&key)
+            .unwrap_or(&HistoryPage::Window(current));
+        let (since, until) = match page {
+            HistoryPage::Window(until) => (until.saturating_sub(self.time_window_s), until),
+            HistoryPage::Rest(until) => (i64::MIN, until),
+        };
+        let mut count = 0;
+        for arc in &overlap {
+            count += self
+                .gossip_window(&from_agent, &to_agent, *arc, since, until)
+                .await?;
+        }
+        let next_page = match page {
+            HistoryPage::Window(_) if since == i64::MIN => HistoryPage::Window(current),
+            HistoryPage::Window(_) if count == 0 => HistoryPage::Rest(since),
+            HistoryPage::Window(_) => HistoryPage::Window(since),
+            // all of history has been gossiped so start again from now
+            HistoryPage::Rest(_) => HistoryPage::Window(current),
+        };
+        self.history.insert(key, next_page);
+        Ok(())
+    }
+
+    /// Gossip the ops in one arc and time window between two agents.
+    /// Returns how many hashes were compared, which is only zero
+    /// if neither agent holds any ops in the window.
+    #[tracing::instrument(skip(self))]
+    async fn gossip_window(
+        &mut self,
+        from_agent: &Arc<KitsuneAgent>,
+        to_agent: &Arc<KitsuneAgent>,
+        dht_arc: DhtArc,
+        since_utc_epoch_s: i64,
+        until_utc_epoch_s: i64,
+    ) -> KitsuneP2pResult<u64> {
+        let span = tracing::debug_span!("next_gossip", ?from_agent, ?to_agent);
 
         // required so from_iters below know the build_hasher type
         type S = HashSet<Arc<KitsuneOpHash>>;
         type A = HashSet<(Arc<KitsuneAgent>, u64)>;
 
-        // we'll just fetch all with no constraints for now
         let (op_hashes_from, agent_info_from) = self
             .evt_send
             .req_op_hashes(ReqOpHashesEvt::new(
                 from_agent.clone(), // from not to because we're initiating
                 from_agent.clone(),
-                DhtArc::new(0, u32::MAX),
-                i64::MIN,
-                i64::MAX,
+                dht_arc,
+                since_utc_epoch_s,
+                until_utc_epoch_s,
                 Default::default(), // This is ignored because requesting from self
             ))
             .await?;
         let op_hashes_from = match op_hashes_from {
             OpConsistency::Variance(h) => h,
             // Not currently used
-            OpConsistency::Consistent => {
+            OpConsistency::Consistent | OpConsistency::Mismatched { .. } => {
                 unreachable!("We don't track consistency of hashes for local requests")
             }
         };
-        let op_count = if last_count.0 == op_hashes_from.len() as u64 {
-            // We have nothing new for them but
-            // they might still have something new
-            // for us.
-            OpCount::Consistent(last_count.1)
-        } else {
-            // We have new gossip for them so
-            // they need to tell us what they have
-            // so we can compute the difference.
-            OpCount::Variance
-        };
-        last_count.0 = op_hashes_from.len() as u64;
+        let from_count = op_hashes_from.len() as u64;
 
-        let op_hashes_from: S = HashSet::from_iter(op_hashes_from);
-        let agent_info_from: A = HashSet::from_iter(agent_info_from);
-        span.in_scope(|| {
-            tracing::debug!(from_has_len = ?op_hashes_from.len());
-        });
+        // Send a fingerprint of what we have so they
+        // only need to send the hashes that differ.
+        let fingerprint = OpFingerprint::for_hashes(&op_hashes_from);
+        let bucket_count = fingerprint.bucket_count();
 
-        // we'll just fetch all with no constraints for now
         let (op_hashes_to, agent_info_to) = self
             .evt_send
             .req_op_hashes(ReqOpHashesEvt::new(
                 from_agent.clone(),
                 to_agent.clone(),
-                DhtArc::new(0, u32::MAX),
-                i64::MIN,
-                i64::MAX,
-                op_count,
+                dht_arc,
+                since_utc_epoch_s,
+                until_utc_epoch_s,
+                OpCount::Fingerprint(fingerprint),
             ))
             .await?;
-        let op_hashes_to = match op_hashes_to {
-            OpConsistency::Variance(h) => {
-                last_count.1 = h.len() as u64;
-                h
-            }
-            // There's no new gossip from us or them
-            // so our job is done.
-            OpConsistency::Consistent => {
-                return Ok(());
-            }
+        let (op_hashes_from, op_hashes_to) = match op_hashes_to {
+            OpConsistency::Variance(h) => (op_hashes_from, h),
+            // We both hold the same ops but
+            // might still have agents to share.
+            OpConsistency::Consistent => (Vec::new(), Vec::new()),
+            // Only the ops in these buckets need comparing.
+            OpConsistency::Mismatched { buckets, hashes } => (
+                OpFingerprint::filter(op_hashes_from, bucket_count, &buckets),
+                hashes,
+            ),
         };
+        let op_count = from_count + op_hashes_to.len() as u64;
+
+        let op_hashes_from: S = HashSet::from_iter(op_hashes_from);
+        let agent_info_from: A = HashSet::from_iter(agent_info_from);
+        span.in_scope(|| {
+            tracing::debug!(from_has_len = ?op_hashes_from.len());
+        });
+
         let op_hashes_to: S = HashSet::from_iter(op_hashes_to);
         let agent_info_to: A = HashSet::from_iter(agent_info_to);
         span.in_scope(|| {
@@ -287,6 +479,45 @@ impl GossipData {
             }
         }
 
-        Ok(())
+        Ok(op_count)
+    }
+}
+
+/// The current time in seconds since the unix epoch
+fn now_s() -> KitsuneP2pResult<i64> {
+    Ok(std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn higher_agent_starts_gossip_it_has_not_heard() {
+        let space = Arc::new(KitsuneSpace::new(vec![0; 36]));
+        let lower = Arc::new(KitsuneAgent::new(vec![1; 36]));
+        let higher = Arc::new(KitsuneAgent::new(vec![2; 36]));
+        let heard = GossipHeard::default();
+        let (evt_send, _evt_recv) = futures::channel::mpsc::channel(1);
+        let mut gossip_data = GossipData::new(evt_send, space.clone(), heard.clone(), 1);
+
+        // The lower agent always starts
+        assert!(gossip_data.starts_remote_gossip(&(lower.clone(), higher.clone())));
+
+        // The higher agent leaves the first round to the lower agent
+        let pair = (higher.clone(), lower.clone());
+        assert!(!gossip_data.starts_remote_gossip(&pair));
+
+        // and doesn't start while it hears from the lower agent every round
+        gossip_data.previous_round = Some(Instant::now());
+        heard.heard(space.clone(), higher.clone(), lower.clone());
+        assert!(!gossip_data.starts_remote_gossip(&pair));
+
+        // but starts once a round passes without hearing from it
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        gossip_data.previous_round = Some(Instant::now());
+        assert!(gossip_data.starts_remote_gossip(&pair));
     }
 }
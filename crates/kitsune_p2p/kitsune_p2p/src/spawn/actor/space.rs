@@ -4,6 +4,9 @@ use super::*;
 use ghost_actor::dependencies::tracing;
 use ghost_actor::dependencies::tracing_futures::Instrument;
 use kitsune_p2p_types::codec::Codec;
+use kitsune_p2p_types::dht_arc::ArcResizeParams;
+use kitsune_p2p_types::dht_arc::DhtArc;
+use kitsune_p2p_types::dht_arc::MAX_HALF_LENGTH;
 use std::collections::HashSet;
 use std::convert::TryFrom;
 
@@ -19,6 +22,9 @@ ghost_actor::ghost_chan! {
         /// Update / publish our agent info
         fn update_agent_info() -> ();
 
+        /// Record the arc a local agent is now storing
+        fn set_agent_arc(agent: Arc<KitsuneAgent>, arc: DhtArc) -> ();
+
         /// see if an agent is locally joined
         fn is_agent_local(agent: Arc<KitsuneAgent>) -> bool;
     }
@@ -28,6 +34,7 @@ pub(crate) async fn spawn_space(
     space: Arc<KitsuneSpace>,
     transport: ghost_actor::GhostSender<TransportListener>,
     config: Arc<KitsuneP2pConfig>,
+    gossip_heard: gossip::GossipHeard,
 ) -> KitsuneP2pResult<(
     ghost_actor::GhostSender<KitsuneP2p>,
     KitsuneP2pEventReceiver,
@@ -37,7 +44,7 @@ pub(crate) async fn spawn_space(
     let builder = ghost_actor::actor_builder::GhostActorBuilder::new();
 
     // initialize gossip module
-    let gossip_recv = gossip::spawn_gossip_module(config.clone());
+    let gossip_recv = gossip::spawn_gossip_module(config.clone(), space.clone(), gossip_heard);
     builder
         .channel_factory()
         .attach_receiver(gossip_recv)
@@ -64,12 +71,11 @@ impl gossip::GossipEventHandler for Space {
     fn handle_list_neighbor_agents(
         &mut self,
     ) -> gossip::GossipEventHandlerResult<ListNeighborAgents> {
-        // while full-sync this is just a clone of list_by_basis
         let local_agents = self
-            .local_joined_agents
+            .agent_arcs
             .iter()
-            .cloned()
-            .collect::<HashSet<_>>();
+            .map(|(agent, arc)| (agent.clone(), *arc))
+            .collect::<Vec<_>>();
         let agent = self.local_joined_agents.iter().next().cloned();
         let fut = match agent {
             Some(agent) => self
@@ -83,11 +89,15 @@ impl gossip::GossipEventHandler for Space {
         Ok(async move {
             let remote_agents = fut
                 .await?
-                .into_iter()
-                .map(|ai| Arc::new(ai.into_agent()))
-                .filter(|a| !local_agents.contains(a))
+                .iter()
+                .filter(|ai| !local_agents.iter().any(|(l, _)| **l == *ai.as_agent_ref()))
+                .filter_map(|ai| {
+                    let arc = types::agent_store::AgentInfo::try_from(ai)
+                        .ok()?
+                        .storage_arc();
+                    Some((Arc::new(ai.as_agent_ref().clone()), arc))
+                })
                 .collect::<Vec<_>>();
-            let local_agents = local_agents.into_iter().collect::<Vec<_>>();
             Ok((local_agents, remote_agents))
         }
         .boxed()
@@ -99,12 +109,14 @@ impl gossip::GossipEventHandler for Space {
         input: ReqOpHashesEvt,
     ) -> gossip::GossipEventHandlerResult<OpHashesAgentHashes> {
         if self.local_joined_agents.contains(&input.to_agent) {
+            let op_count = input.op_count.clone();
             let fut = local_req_op_hashes(&self.evt_sender, self.space.clone(), input);
-            Ok(
-                async move { fut.await.map(|r| (OpConsistency::Variance(r.0), r.1)) }
-                    .boxed()
-                    .into(),
-            )
+            Ok(async move {
+                fut.await
+                    .map(|r| (OpConsistency::reconcile(&op_count, r.0), r.1))
+            }
+            .boxed()
+            .into())
         } else {
             let ReqOpHashesEvt {
                 to_agent,
@@ -129,6 +141,13 @@ impl gossip::GossipEventHandler for Space {
                     None => return Err(KitsuneP2pError::RoutingAgentError(to_agent)),
                     Some(i) => i,
                 };
+                let info = types::agent_store::AgentInfo::try_from(&info)?;
+                // nodes from before fingerprints can't decode them
+                // so they are asked for all their hashes instead
+                let op_count = match op_count {
+                    OpCount::Fingerprint(_) if !info.gossip_fingerprints() => OpCount::Variance,
+                    op_count => op_count,
+                };
                 let data = wire::Wire::fetch_op_hashes(
                     space,
                     from_agent,
@@ -139,7 +158,6 @@ impl gossip::GossipEventHandler for Space {
                     op_count,
                 )
                 .encode_vec()?;
-                let info = types::agent_store::AgentInfo::try_from(&info)?;
                 let url = info.as_urls_ref().get(0).unwrap().clone();
                 let (_, mut write, read) = transport_tx.create_channel(url).await?;
                 KitsuneMetrics::count(KitsuneMetrics::FetchOpHashes, data.len());
@@ -270,6 +288,43 @@ impl gossip::GossipEventHandler for Space {
     }
 }
 
+/// Resize a local agent's arc from the arcs its peers published
+/// and the number of ops it holds.
+async fn resize_agent_arc(
+    evt_sender: &futures::channel::mpsc::Sender<KitsuneP2pEvent>,
+    space: Arc<KitsuneSpace>,
+    agent: Arc<KitsuneAgent>,
+    arc: DhtArc,
+    params: &ArcResizeParams,
+) -> KitsuneP2pResult<DhtArc> {
+    let peers = evt_sender
+        .query_agent_info_signed(QueryAgentInfoSignedEvt {
+            space: space.clone(),
+            agent: agent.clone(),
+        })
+        .await?
+        .iter()
+        .filter(|ai| *ai.as_agent_ref() != *agent)
+        .filter_map(|ai| types::agent_store::AgentInfo::try_from(ai).ok())
+        .map(|ai| ai.storage_arc())
+        .collect::<Vec<_>>();
+    // counting the ops held is only worth it if storage is limited
+    let ops_held = match params.storage_limit_ops {
+        Some(_) => evt_sender
+            .fetch_op_hashes_for_constraints(FetchOpHashesForConstraintsEvt {
+                space,
+                agent,
+                dht_arc: arc,
+                since_utc_epoch_s: i64::MIN,
+                until_utc_epoch_s: i64::MAX,
+            })
+            .await?
+            .len() as u64,
+        None => 0,
+    };
+    Ok(arc.resize(&peers, ops_held, params))
+}
+
 pub fn local_req_op_hashes(
     evt_sender: &futures::channel::mpsc::Sender<KitsuneP2pEvent>,
     space: Arc<KitsuneSpace>,
@@ -369,11 +424,23 @@ pub fn local_gossip_ops(
     let all_agents = agents
         .into_iter()
         .map(|agent_info_signed| {
-            evt_sender.put_agent_info_signed(PutAgentInfoSignedEvt {
-                space: space.clone(),
-                agent: to_agent.clone(),
-                agent_info_signed,
-            })
+            let evt_sender = evt_sender.clone();
+            let space = space.clone();
+            let to_agent = to_agent.clone();
+            async move {
+                // drop gossiped agent info that doesn't verify
+                if let Err(e) = agent_info_signed.verify().await {
+                    tracing::warn!(msg = "Dropping invalid gossiped agent info", ?e);
+                    return Ok(());
+                }
+                evt_sender
+                    .put_agent_info_signed(PutAgentInfoSignedEvt {
+                        space,
+                        agent: to_agent,
+                        agent_info_signed,
+                    })
+                    .await
+            }
         })
         .collect::<Vec<_>>();
     async move {
@@ -428,24 +495,48 @@ impl SpaceInternalHandler for Space {
 
     fn handle_update_agent_info(&mut self) -> SpaceInternalHandlerResult<()> {
         let space = self.space.clone();
-        let agent_list: Vec<Arc<KitsuneAgent>> = self.local_joined_agents.iter().cloned().collect();
+        let agent_list: Vec<(Arc<KitsuneAgent>, DhtArc)> = self
+            .agent_arcs
+            .iter()
+            .map(|(agent, arc)| (agent.clone(), *arc))
+            .collect();
         let bound_url = self.transport.bound_url();
         let evt_sender = self.evt_sender.clone();
+        let i_s = self.i_s.clone();
         let bootstrap_service = self.config.bootstrap_service.clone();
         let expires_after = self.config.tuning_params.agent_info_expires_after_ms as u64;
+        let arc_params = self.config.tuning_params.arc_resize_params();
         Ok(async move {
             let bound_url = bound_url.await?;
             let urls = bound_url
                 .query_pairs()
                 .map(|(_, sub_url)| url2::url2!("{}", sub_url))
                 .collect::<Vec<_>>();
-            for agent in agent_list {
+            for (agent, arc) in agent_list {
+                // a failed resize must not stop us publishing our info
+                let storage_arc = match resize_agent_arc(
+                    &evt_sender,
+                    space.clone(),
+                    agent.clone(),
+                    arc,
+                    &arc_params,
+                )
+                .await
+                {
+                    Ok(storage_arc) => storage_arc,
+                    Err(e) => {
+                        tracing::warn!(msg = "Failed to resize agent arc", ?agent, ?e);
+                        arc
+                    }
+                };
+                i_s.set_agent_arc(agent.clone(), storage_arc).await?;
                 let agent_info = crate::types::agent_store::AgentInfo::new(
                     (*space).clone(),
                     (*agent).clone(),
                     urls.clone(),
                     crate::spawn::actor::bootstrap::now_once(None).await?,
                     expires_after,
+                    storage_arc,
                 );
                 let mut data = Vec::new();
                 kitsune_p2p_types::codec::rmp_encode(&mut data, &agent_info)?;
@@ -455,11 +546,20 @@ impl SpaceInternalHandler for Space {
                     data: Arc::new(data.clone()),
                 };
                 let sig = evt_sender.sign_network_data(sign_req).await?;
-                let agent_info_signed = crate::types::agent_store::AgentInfoSigned::try_new(
+                let agent_info_signed = match crate::types::agent_store::AgentInfoSigned::try_new(
                     (*agent).clone(),
                     sig.clone(),
                     data,
-                )?;
+                )
+                .await
+                {
+                    Ok(agent_info_signed) => agent_info_signed,
+                    // nobody would accept agent info that doesn't verify
+                    Err(e) => {
+                        tracing::error!(msg = "Failed to sign agent info", ?agent, ?e);
+                        continue;
+                    }
+                };
                 tracing::debug!(?agent_info, ?sig);
                 evt_sender
                     .put_agent_info_signed(PutAgentInfoSignedEvt {
@@ -479,6 +579,18 @@ impl SpaceInternalHandler for Space {
         .into())
     }
 
+    fn handle_set_agent_arc(
+        &mut self,
+        agent: Arc<KitsuneAgent>,
+        arc: DhtArc,
+    ) -> SpaceInternalHandlerResult<()> {
+        // the agent may have left while its arc was being resized
+        if let Some(current) = self.agent_arcs.get_mut(&agent) {
+            *current = arc;
+        }
+        Ok(async move { Ok(()) }.boxed().into())
+    }
+
     fn handle_is_agent_local(
         &mut self,
         agent: Arc<KitsuneAgent>,
@@ -505,6 +617,8 @@ impl KitsuneP2pHandler for Space {
         agent: Arc<KitsuneAgent>,
     ) -> KitsuneP2pHandlerResult<()> {
         self.local_joined_agents.insert(agent.clone());
+        self.agent_arcs
+            .insert(agent.clone(), DhtArc::new(agent.get_loc(), MAX_HALF_LENGTH));
         let fut = self.i_s.update_agent_info();
         let i_s = self.i_s.clone();
         let evt_sender = self.evt_sender.clone();
@@ -544,6 +658,7 @@ impl KitsuneP2pHandler for Space {
         agent: Arc<KitsuneAgent>,
     ) -> KitsuneP2pHandlerResult<()> {
         self.local_joined_agents.remove(&agent);
+        self.agent_arcs.remove(&agent);
         Ok(async move { Ok(()) }.boxed().into())
     }
 
@@ -687,6 +802,8 @@ pub(crate) struct Space {
     pub(crate) evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
     pub(crate) transport: ghost_actor::GhostSender<TransportListener>,
     pub(crate) local_joined_agents: HashSet<Arc<KitsuneAgent>>,
+    /// The arc of the dht each locally joined agent stores.
+    pub(crate) agent_arcs: HashMap<Arc<KitsuneAgent>, DhtArc>,
     pub(crate) config: Arc<KitsuneP2pConfig>,
 }
 
@@ -715,6 +832,7 @@ impl Space {
             evt_sender,
             transport,
             local_joined_agents: HashSet::new(),
+            agent_arcs: HashMap::new(),
             config,
         }
     }
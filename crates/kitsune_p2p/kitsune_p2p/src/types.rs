@@ -36,6 +36,10 @@ pub enum KitsuneP2pError {
     #[error("Bootstrap Error: {0}")]
     Bootstrap(Box<str>),
 
+    /// Agent info failed verification.
+    #[error("Invalid Agent Info for {0:?}: {1}")]
+    InvalidAgentInfo(Arc<KitsuneAgent>, Box<str>),
+
     /// SystemTime call failed.
     #[error(transparent)]
     SystemTime(#[from] std::time::SystemTimeError),
@@ -1,9 +1,15 @@
 //! Data structures to be stored in the agent/peer database.
 
 use crate::types::KitsuneAgent;
+use crate::types::KitsuneBinType;
 use crate::types::KitsuneP2pError;
 use crate::types::KitsuneSignature;
 use crate::types::KitsuneSpace;
+use kitsune_p2p_types::dht_arc::DhtArc;
+use lair_keystore_api::internal::sign_ed25519::SignEd25519PubKey;
+use lair_keystore_api::internal::sign_ed25519::SignEd25519Signature;
+use std::convert::TryFrom;
+use std::sync::Arc;
 use url2::Url2;
 
 /// A list of Urls.
@@ -33,18 +39,70 @@ pub struct AgentInfoSigned {
 
 impl AgentInfoSigned {
     /// Build a new AgentInfoSigned struct given a valid signature of the AgentInfo.
-    // @todo fail this if the signature does not verify against the agent info.
-    // It should not be possible to express a signed agent info type  with no valid signature.
-    pub fn try_new(
+    /// Fails if the signature or the agent don't match the agent info.
+    pub async fn try_new(
         agent: KitsuneAgent,
         signature: KitsuneSignature,
         agent_info: Vec<u8>,
     ) -> Result<Self, KitsuneP2pError> {
-        Ok(Self {
+        let agent_info_signed = Self::new_unchecked(agent, signature, agent_info);
+        agent_info_signed.verify_signature().await?;
+        Ok(agent_info_signed)
+    }
+
+    /// Build a new AgentInfoSigned struct without checking the signature.
+    /// Only for fixtures, anything received must be verified.
+    pub(crate) fn new_unchecked(
+        agent: KitsuneAgent,
+        signature: KitsuneSignature,
+        agent_info: Vec<u8>,
+    ) -> Self {
+        Self {
             agent,
             signature,
             agent_info,
-        })
+        }
+    }
+
+    /// Check agent info received from elsewhere before it is used or stored.
+    /// The agent must have signed the agent info, the agent info must be about
+    /// that agent and it must not have expired.
+    pub async fn verify(&self) -> Result<AgentInfo, KitsuneP2pError> {
+        let agent_info = self.verify_signature().await?;
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_millis() as u64;
+        if agent_info.is_expired(now_ms) {
+            return Err(self.invalid("expired"));
+        }
+        Ok(agent_info)
+    }
+
+    /// Check the agent signed this agent info and that it is about them.
+    async fn verify_signature(&self) -> Result<AgentInfo, KitsuneP2pError> {
+        let agent_info = AgentInfo::try_from(self)?;
+        if agent_info.agent != self.agent {
+            return Err(self.invalid("signed by a different agent"));
+        }
+        // agents are 32 bytes of ed25519 public key followed by 4 location
+        // bytes, only the key verifies
+        if self.agent.0.len() != 36 {
+            return Err(self.invalid("agent is not a 36 byte key and location"));
+        }
+        let pub_key: SignEd25519PubKey = self.agent.0[..32].to_vec().into();
+        let signature: SignEd25519Signature = self.signature.0.clone().into();
+        let valid = pub_key
+            .verify(Arc::new(self.agent_info.clone()), signature)
+            .await
+            .map_err(KitsuneP2pError::other)?;
+        if !valid {
+            return Err(self.invalid("bad signature"));
+        }
+        Ok(agent_info)
+    }
+
+    fn invalid(&self, reason: &str) -> KitsuneP2pError {
+        KitsuneP2pError::InvalidAgentInfo(Arc::new(self.agent.clone()), reason.into())
     }
 
     /// Thin wrapper around AsRef for KitsuneSignature.
@@ -85,6 +143,14 @@ pub struct AgentInfo {
     // The expiry ttl for the agent info relative to the signing time.
     #[as_ref(ignore)]
     expires_after_ms: u64,
+    // The arc of the dht the agent is storing.
+    #[as_ref(ignore)]
+    storage_arc: DhtArc,
+    // Whether the agent reconciles gossip with op hash fingerprints.
+    // Agent info from nodes that don't doesn't have this field.
+    #[as_ref(ignore)]
+    #[serde(default)]
+    gossip_fingerprints: bool,
 }
 
 impl std::convert::TryFrom<&AgentInfoSigned> for AgentInfo {
@@ -104,6 +170,7 @@ impl AgentInfo {
         urls: Urls,
         signed_at_ms: u64,
         expires_after_ms: u64,
+        storage_arc: DhtArc,
     ) -> Self {
         Self {
             space,
@@ -111,6 +178,8 @@ impl AgentInfo {
             urls,
             signed_at_ms,
             expires_after_ms,
+            storage_arc,
+            gossip_fingerprints: true,
         }
     }
 }
@@ -146,6 +215,21 @@ impl AgentInfo {
     pub fn expires_after_ms(&self) -> u64 {
         self.expires_after_ms
     }
+
+    /// Has this agent info expired at this time.
+    pub fn is_expired(&self, now_ms: u64) -> bool {
+        self.signed_at_ms.saturating_add(self.expires_after_ms) <= now_ms
+    }
+
+    /// Accessor for storage_arc.
+    pub fn storage_arc(&self) -> DhtArc {
+        self.storage_arc
+    }
+
+    /// Can this agent reconcile gossip with an `OpCount::Fingerprint`?
+    pub fn gossip_fingerprints(&self) -> bool {
+        self.gossip_fingerprints
+    }
 }
 
 impl From<AgentInfoSigned> for KitsuneAgent {
@@ -159,3 +243,92 @@ impl From<AgentInfo> for KitsuneAgent {
         ai.agent
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixt::*;
+    use ::fixt::prelude::*;
+
+    #[tokio::test(threaded_scheduler)]
+    async fn test_verify_agent_info() {
+        let signed = signed_agent_info(fixt!(KitsuneSpace)).await;
+        let info = signed.verify().await.unwrap();
+        assert_eq!(&info.agent, signed.as_agent_ref());
+
+        // a flipped signature bit must not verify
+        let mut signature = signed.as_signature_ref().clone();
+        signature.0[0] ^= 1;
+        let tampered = AgentInfoSigned::new_unchecked(
+            signed.as_agent_ref().clone(),
+            signature,
+            signed.as_agent_info_ref().to_vec(),
+        );
+        assert!(tampered.verify().await.is_err());
+
+        // nor can one agent vouch for another agent's info
+        let other = signed_agent_info(fixt!(KitsuneSpace)).await;
+        let forged = AgentInfoSigned::new_unchecked(
+            other.as_agent_ref().clone(),
+            other.as_signature_ref().clone(),
+            signed.as_agent_info_ref().to_vec(),
+        );
+        assert!(forged.verify().await.is_err());
+        assert!(AgentInfoSigned::try_new(
+            other.as_agent_ref().clone(),
+            signed.as_signature_ref().clone(),
+            signed.as_agent_info_ref().to_vec(),
+        )
+        .await
+        .is_err());
+    }
+
+    #[test]
+    fn test_agent_info_expiry() {
+        let info = AgentInfo::new(
+            fixt!(KitsuneSpace),
+            fixt!(KitsuneAgent),
+            fixt!(Urls),
+            1000,
+            500,
+            fixt!(DhtArc),
+        );
+        assert!(!info.is_expired(1499));
+        assert!(info.is_expired(1500));
+    }
+    #[test]
+    fn test_agent_info_from_nodes_without_fingerprints() {
+        // Agent info as signed by nodes from before gossip fingerprints
+        #[derive(serde::Serialize)]
+        struct OldAgentInfo {
+            space: KitsuneSpace,
+            agent: KitsuneAgent,
+            urls: Urls,
+            signed_at_ms: u64,
+            expires_after_ms: u64,
+            storage_arc: DhtArc,
+        }
+        let old = OldAgentInfo {
+            space: fixt!(KitsuneSpace),
+            agent: fixt!(KitsuneAgent),
+            urls: fixt!(Urls),
+            signed_at_ms: 1000,
+            expires_after_ms: 500,
+            storage_arc: fixt!(DhtArc),
+        };
+        let mut buf = Vec::new();
+        kitsune_p2p_types::codec::rmp_encode(&mut buf, &old).unwrap();
+        let info: AgentInfo = kitsune_p2p_types::codec::rmp_decode(&mut &*buf).unwrap();
+        assert!(!info.gossip_fingerprints());
+
+        let info = AgentInfo::new(
+            old.space,
+            old.agent,
+            old.urls,
+            old.signed_at_ms,
+            old.expires_after_ms,
+            old.storage_arc,
+        );
+        assert!(info.gossip_fingerprints());
+    }
+}
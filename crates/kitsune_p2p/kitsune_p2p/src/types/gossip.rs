@@ -60,6 +60,48 @@ pub enum OpConsistency {
     Variance(OpHashes),
     /// Gossip is consistent since the last call.
     Consistent,
+    /// Some buckets of the requester's fingerprint don't match.
+    /// Only the hashes in those buckets are sent.
+    Mismatched {
+        /// The buckets that don't match.
+        buckets: Vec<u32>,
+        /// The responder's hashes in those buckets.
+        hashes: OpHashes,
+    },
+}
+
+impl OpConsistency {
+    /// Respond to a request for hashes with the hashes we hold,
+    /// sending as few of them as the request allows.
+    pub fn reconcile(op_count: &OpCount, hashes: OpHashes) -> Self {
+        match op_count {
+            OpCount::Consistent(last_count) => {
+                // Requester is consistent,
+                // now check if we are consistent.
+                if *last_count == hashes.len() as u64 {
+                    OpConsistency::Consistent
+                } else {
+                    OpConsistency::Variance(hashes)
+                }
+            }
+            // Requester has a variance so we must return hashes.
+            OpCount::Variance => OpConsistency::Variance(hashes),
+            // Requester sent a malformed fingerprint.
+            OpCount::Fingerprint(theirs) if theirs.buckets.is_empty() => {
+                OpConsistency::Variance(hashes)
+            }
+            // Only send the hashes in buckets that differ.
+            OpCount::Fingerprint(theirs) => {
+                let ours = OpFingerprint::new(&hashes, theirs.bucket_count());
+                let buckets = ours.mismatched(theirs);
+                if buckets.is_empty() {
+                    return OpConsistency::Consistent;
+                }
+                let hashes = OpFingerprint::filter(hashes, theirs.bucket_count(), &buckets);
+                OpConsistency::Mismatched { buckets, hashes }
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -74,6 +116,112 @@ pub enum OpCount {
     /// and this is the count they saw from
     /// this agent.
     Consistent(u64),
+    /// Requestor's fingerprint of the hashes they hold.
+    /// The responder only needs to reply with hashes
+    /// in the buckets which don't match their own.
+    /// Nodes from before fingerprints can't decode this, so it's only
+    /// sent to agents whose agent info says they support it.
+    Fingerprint(OpFingerprint),
+}
+
+/// Aim for this many hashes in each bucket of a fingerprint.
+const HASHES_PER_BUCKET: usize = 8;
+
+/// Never use more buckets than this in a fingerprint.
+const MAX_BUCKETS: usize = 4096;
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+/// A compact summary of a set of op hashes.
+/// Hashes are split into buckets and each bucket is summarised
+/// by its count and the XOR of its hashes, so two agents can find
+/// which buckets differ without sending every hash.
+pub struct OpFingerprint {
+    /// The fingerprint of each bucket.
+    pub buckets: Vec<BucketFingerprint>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+/// The fingerprint of one bucket of op hashes.
+pub struct BucketFingerprint {
+    /// How many hashes are in the bucket.
+    pub count: u64,
+    /// All the hashes in the bucket XORed together.
+    #[serde(with = "serde_bytes")]
+    pub xor: Vec<u8>,
+}
+
+impl OpFingerprint {
+    /// Fingerprint these hashes using this many buckets.
+    pub fn new(hashes: &[Arc<KitsuneOpHash>], bucket_count: u32) -> Self {
+        let mut buckets = vec![BucketFingerprint::default(); bucket_count.max(1) as usize];
+        for hash in hashes {
+            let bucket = &mut buckets[Self::bucket_of(hash, bucket_count) as usize];
+            bucket.count += 1;
+            if bucket.xor.len() < hash.len() {
+                bucket.xor.resize(hash.len(), 0);
+            }
+            for (x, b) in bucket.xor.iter_mut().zip(hash.iter()) {
+                *x ^= b;
+            }
+        }
+        Self { buckets }
+    }
+
+    /// Fingerprint these hashes with enough buckets that
+    /// a few differences only cost a few hashes to reconcile.
+    pub fn for_hashes(hashes: &[Arc<KitsuneOpHash>]) -> Self {
+        let bucket_count = (hashes.len() / HASHES_PER_BUCKET)
+            .next_power_of_two()
+            .min(MAX_BUCKETS);
+        Self::new(hashes, bucket_count as u32)
+    }
+
+    /// The number of buckets in this fingerprint.
+    pub fn bucket_count(&self) -> u32 {
+        self.buckets.len() as u32
+    }
+
+    /// The buckets where this fingerprint differs from another
+    /// with the same number of buckets.
+    pub fn mismatched(&self, other: &OpFingerprint) -> Vec<u32> {
+        self.buckets
+            .iter()
+            .zip(other.buckets.iter())
+            .enumerate()
+            .filter(|(_, (a, b))| !a.matches(b))
+            .map(|(i, _)| i as u32)
+            .collect()
+    }
+
+    /// The bucket a hash falls in.
+    pub fn bucket_of(hash: &KitsuneOpHash, bucket_count: u32) -> u32 {
+        // The start of the hash is evenly distributed
+        let prefix = hash
+            .get_bytes()
+            .iter()
+            .take(4)
+            .fold(0u32, |acc, b| (acc << 8) | *b as u32);
+        prefix % bucket_count.max(1)
+    }
+
+    /// Keep only the hashes that fall in these buckets.
+    pub fn filter(hashes: OpHashes, bucket_count: u32, buckets: &[u32]) -> OpHashes {
+        let buckets: std::collections::HashSet<_> = buckets.iter().collect();
+        hashes
+            .into_iter()
+            .filter(|h| buckets.contains(&Self::bucket_of(h, bucket_count)))
+            .collect()
+    }
+}
+
+impl BucketFingerprint {
+    /// Two buckets match if they have the same count and XOR.
+    /// An empty XOR is the same as all zeros.
+    fn matches(&self, other: &BucketFingerprint) -> bool {
+        let len = std::cmp::max(self.xor.len(), other.xor.len());
+        let byte = |xor: &[u8], i: usize| xor.get(i).copied().unwrap_or(0);
+        self.count == other.count && (0..len).all(|i| byte(&self.xor, i) == byte(&other.xor, i))
+    }
 }
 
 /// Dht Op hashes that an agent holds
@@ -87,11 +235,68 @@ pub type OpHashesAgentHashes = (OpConsistency, Vec<(Arc<KitsuneAgent>, u64)>);
 pub type LocalOpHashesAgentHashes = (OpHashes, Vec<(Arc<KitsuneAgent>, u64)>);
 /// The Dht op data and agent store information
 pub type OpDataAgentInfo = (Vec<(Arc<KitsuneOpHash>, Vec<u8>)>, Vec<AgentInfoSigned>);
-/// Local and remote neighbors.
-pub type ListNeighborAgents = (Vec<Arc<KitsuneAgent>>, Vec<Arc<KitsuneAgent>>);
+/// Local and remote neighbors with the arc each of them stores.
+pub type ListNeighborAgents = (
+    Vec<(Arc<KitsuneAgent>, DhtArc)>,
+    Vec<(Arc<KitsuneAgent>, DhtArc)>,
+);
 
 impl Default for OpCount {
     fn default() -> Self {
         OpCount::Variance
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(i: u8) -> Arc<KitsuneOpHash> {
+        Arc::new(KitsuneOpHash::new(vec![i; 36]))
+    }
+
+    #[test]
+    fn fingerprints_find_missing_hashes() {
+        let ours: OpHashes = (0..100).map(hash).collect();
+        let mut theirs: OpHashes = (2..100).map(hash).collect();
+        theirs.push(hash(200));
+
+        let fingerprint = OpFingerprint::for_hashes(&theirs);
+        assert_eq!(fingerprint.bucket_count(), 16);
+
+        match OpConsistency::reconcile(&OpCount::Fingerprint(fingerprint), ours.clone()) {
+            OpConsistency::Mismatched { buckets, hashes } => {
+                assert!(buckets.len() <= 3);
+                // Only a small part of our hashes is sent back
+                assert!(hashes.len() < ours.len() / 4);
+                for missing in &[hash(0), hash(1)] {
+                    assert!(hashes.contains(missing));
+                }
+                // Everything they hold in those buckets can be compared
+                let theirs = OpFingerprint::filter(theirs, 16, &buckets);
+                assert!(theirs.contains(&hash(200)));
+            }
+            r => panic!("expected mismatched buckets but got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn matching_fingerprints_are_consistent() {
+        let ours: OpHashes = (0..50).map(hash).collect();
+        let mut theirs = ours.clone();
+        theirs.reverse();
+        let fingerprint = OpFingerprint::for_hashes(&theirs);
+        assert_eq!(
+            OpConsistency::reconcile(&OpCount::Fingerprint(fingerprint), ours),
+            OpConsistency::Consistent
+        );
+
+        // Nothing on either side is consistent too
+        let fingerprint = OpFingerprint::for_hashes(&[]);
+        assert_eq!(fingerprint.bucket_count(), 1);
+        assert_eq!(
+            OpConsistency::reconcile(&OpCount::Fingerprint(fingerprint), vec![]),
+            OpConsistency::Consistent
+        );
+    }
+}
@@ -8,7 +8,9 @@ use std::ops::RangeBounds;
 #[cfg(test)]
 use std::ops::RangeInclusive;
 
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, From, Into)]
+#[derive(
+    Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash, From, Into,
+)]
 /// Type for representing a location that can wrap around
 /// a u32 dht arc
 pub struct DhtLocation(pub Wrapping<u32>);
@@ -20,7 +22,7 @@ pub struct DhtLocation(pub Wrapping<u32>);
 /// 1 more is added to represent the middle point of an odd length array
 pub const MAX_HALF_LENGTH: u32 = (u32::MAX / 2) + 1 + 1;
 
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
 /// Represents how much of a dht arc is held
 /// center_loc is where the hash is.
 /// The center_loc is the center of the arc
@@ -103,6 +105,132 @@ impl DhtArc {
     }
 }
 
+impl DhtArc {
+    /// The smallest arc that covers every location from start to end
+    /// inclusive, going clockwise from start.
+    /// An arc can only cover an odd number of locations so a range with
+    /// an even number of locations gets one extra location at the end.
+    pub fn from_bounds(start: u32, end: u32) -> Self {
+        let len = (Wrapping(end) - Wrapping(start)).0 as u64 + 1;
+        let half_length = std::cmp::min(len / 2 + 1, MAX_HALF_LENGTH as u64) as u32;
+        let center_loc = Wrapping(start) + Wrapping(half_length - 1);
+        Self::new(center_loc.0, half_length)
+    }
+
+    /// The parts of the dht that both arcs hold.
+    /// Two arcs can overlap in up to two separate places
+    /// when they are large enough to wrap around the dht.
+    pub fn overlap(&self, other: &DhtArc) -> Vec<DhtArc> {
+        let (a_start, a_len) = match self.bounds() {
+            Some(a) => a,
+            None => return Vec::new(),
+        };
+        let (b_start, b_len) = match other.bounds() {
+            Some(b) => b,
+            None => return Vec::new(),
+        };
+        const FULL: i64 = u32::MAX as i64 + 1;
+        if a_len == FULL {
+            return vec![*other];
+        }
+        if b_len == FULL {
+            return vec![*self];
+        }
+        // Measure everything from the start of this arc
+        // so this arc is the range 0..a_len
+        let b_offset = (Wrapping(b_start) - Wrapping(a_start)).0 as i64;
+        let mut overlap = Vec::with_capacity(2);
+        for b_offset in &[b_offset, b_offset - FULL] {
+            let start = std::cmp::max(0, *b_offset);
+            let end = std::cmp::min(a_len, b_offset + b_len);
+            if start < end {
+                overlap.push(DhtArc::from_bounds(
+                    (Wrapping(a_start) + Wrapping(start as u32)).0,
+                    (Wrapping(a_start) + Wrapping((end - 1) as u32)).0,
+                ));
+            }
+        }
+        overlap
+    }
+
+    /// The first location and the number of locations held
+    /// or None if nothing is held
+    fn bounds(&self) -> Option<(u32, i64)> {
+        match self.range() {
+            ArcRange {
+                start: Bound::Included(start),
+                end: Bound::Included(end),
+            } => Some((start, (Wrapping(end) - Wrapping(start)).0 as i64 + 1)),
+            _ => None,
+        }
+    }
+}
+
+/// Grow an arc by at least this much so small arcs can recover.
+const MIN_RESIZE_STEP: u32 = MAX_HALF_LENGTH / 256;
+
+/// How many locations of an arc are checked when estimating redundancy.
+const REDUNDANCY_SAMPLES: u64 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The limits an agent's arc is resized within
+pub struct ArcResizeParams {
+    /// How many peers should hold each location of the arc.
+    /// The arc grows while fewer peers hold it and shrinks
+    /// once more than twice as many do.
+    pub target_redundancy: u32,
+    /// The most ops the agent is willing to hold, if limited.
+    pub storage_limit_ops: Option<u64>,
+}
+
+impl DhtArc {
+    /// Resize this arc given the arcs of the neighbouring peers
+    /// and how many ops are currently held in it.
+    ///
+    /// The arc grows a step while the dht around it is held by fewer
+    /// peers than the target and shrinks a step when it is held by more
+    /// than twice the target. It never grows past the size that would
+    /// hold more ops than the storage limit, assuming ops are spread
+    /// evenly, and always holds at least its center location.
+    pub fn resize(&self, peers: &[DhtArc], ops_held: u64, params: &ArcResizeParams) -> DhtArc {
+        let redundancy = self.redundancy(peers);
+        let half_length = self.half_length as u64;
+        let mut new_half_length = if redundancy < params.target_redundancy {
+            std::cmp::max(half_length * 5 / 4, half_length + MIN_RESIZE_STEP as u64)
+        } else if redundancy > params.target_redundancy.saturating_mul(2) {
+            half_length * 4 / 5
+        } else {
+            half_length
+        };
+        if let Some(limit) = params.storage_limit_ops {
+            if ops_held > 0 {
+                let max_half_length = (limit as u128 * half_length as u128 / ops_held as u128)
+                    .min(MAX_HALF_LENGTH as u128) as u64;
+                new_half_length = std::cmp::min(new_half_length, max_half_length);
+            }
+        }
+        let new_half_length = new_half_length.max(1).min(MAX_HALF_LENGTH as u64) as u32;
+        DhtArc::new(self.center_loc, new_half_length)
+    }
+
+    /// Estimate how many of these peers hold the same part of the dht as
+    /// this arc. This is the fewest peers holding any of a spread of
+    /// locations across the arc.
+    pub fn redundancy(&self, peers: &[DhtArc]) -> u32 {
+        let holding = |loc: u32| peers.iter().filter(|arc| arc.contains(loc)).count() as u32;
+        match self.bounds() {
+            Some((start, len)) => (0..REDUNDANCY_SAMPLES)
+                .map(|i| {
+                    let offset = (len as u64 * (2 * i + 1) / (2 * REDUNDANCY_SAMPLES)) as u32;
+                    holding((Wrapping(start) + Wrapping(offset)).0)
+                })
+                .min()
+                .unwrap_or(0),
+            None => holding(self.center_loc.into()),
+        }
+    }
+}
+
 impl From<u32> for DhtLocation {
     fn from(a: u32) -> Self {
         Self(Wrapping(a))
@@ -315,4 +443,137 @@ mod tests {
         );
         check_bounds_full(0, MAX_HALF_LENGTH, half, half - 1);
     }
+
+    #[test]
+    fn test_arc_from_bounds() {
+        let half = (u32::MAX as f64 / 2.0).round() as u32;
+
+        assert_eq!(DhtArc::from_bounds(5, 5), DhtArc::new(5, 1));
+        assert_eq!(DhtArc::from_bounds(0, 2), DhtArc::new(1, 2));
+        assert_eq!(DhtArc::from_bounds(u32::MAX, 1), DhtArc::new(0, 2));
+        // An even length gets an extra location on the end
+        assert_eq!(DhtArc::from_bounds(0, 1).range().into_inc(), 0..=2);
+        assert_eq!(
+            DhtArc::from_bounds(half, half - 1),
+            DhtArc::new(0, MAX_HALF_LENGTH)
+        );
+        for arc in &[
+            DhtArc::new(0, 2),
+            DhtArc::new(half, 7),
+            DhtArc::new(u32::MAX, 3),
+            DhtArc::new(half, MAX_HALF_LENGTH - 1),
+        ] {
+            let (start, len) = arc.bounds().unwrap();
+            let end = (Wrapping(start) + Wrapping((len - 1) as u32)).0;
+            assert_eq!(DhtArc::from_bounds(start, end), *arc);
+        }
+    }
+
+    #[test]
+    fn test_arc_overlap() {
+        let quarter = (u32::MAX as f64 / 4.0).round() as u32;
+        let half = (u32::MAX as f64 / 2.0).round() as u32;
+        let full = DhtArc::new(0, MAX_HALF_LENGTH);
+
+        // Nothing overlaps an empty arc
+        assert!(DhtArc::new(0, 0).overlap(&full).is_empty());
+        assert!(full.overlap(&DhtArc::new(0, 0)).is_empty());
+
+        // Arcs that don't meet
+        assert!(DhtArc::new(0, 10)
+            .overlap(&DhtArc::new(half, 10))
+            .is_empty());
+
+        // A full arc overlaps with all of the other arc
+        assert_eq!(
+            full.overlap(&DhtArc::new(half, 10)),
+            vec![DhtArc::new(half, 10)]
+        );
+        assert_eq!(
+            DhtArc::new(half, 10).overlap(&full),
+            vec![DhtArc::new(half, 10)]
+        );
+
+        // A small arc inside a larger arc
+        assert_eq!(
+            DhtArc::new(half, quarter).overlap(&DhtArc::new(half + 5, 3)),
+            vec![DhtArc::new(half + 5, 3)]
+        );
+
+        // Arcs overlapping on one side, across zero
+        let overlap = DhtArc::new(u32::MAX - 5, 10).overlap(&DhtArc::new(5, 10));
+        assert_eq!(overlap, vec![DhtArc::from_bounds(u32::MAX - 3, 3)]);
+        assert!(overlap[0].contains(0));
+        assert!(overlap[0].contains(u32::MAX - 3));
+        assert!(!overlap[0].contains(u32::MAX - 4));
+
+        // Large arcs can overlap in two places
+        let overlap = DhtArc::new(0, quarter * 3 / 2).overlap(&DhtArc::new(half, quarter * 3 / 2));
+        assert_eq!(overlap.len(), 2);
+        assert!(overlap.iter().any(|arc| arc.contains(quarter)));
+        assert!(overlap.iter().any(|arc| arc.contains(quarter * 3)));
+        assert!(!overlap.iter().any(|arc| arc.contains(0)));
+        assert!(!overlap.iter().any(|arc| arc.contains(half)));
+    }
+
+    #[test]
+    fn test_arc_redundancy() {
+        let quarter = (u32::MAX as f64 / 4.0).round() as u32;
+        let half = (u32::MAX as f64 / 2.0).round() as u32;
+        let full = DhtArc::new(0, MAX_HALF_LENGTH);
+
+        assert_eq!(DhtArc::new(0, 10).redundancy(&[]), 0);
+        assert_eq!(full.redundancy(&[full, full, full]), 3);
+        // Only part of the arc is covered by the second peer
+        assert_eq!(
+            DhtArc::new(half, quarter).redundancy(&[full, DhtArc::new(half, 10)]),
+            1
+        );
+        // An empty arc counts the peers holding its center
+        assert_eq!(
+            DhtArc::new(half, 0).redundancy(&[full, DhtArc::new(half, 10)]),
+            2
+        );
+    }
+
+    #[test]
+    fn test_arc_resize() {
+        let half = (u32::MAX as f64 / 2.0).round() as u32;
+        let full = DhtArc::new(half, MAX_HALF_LENGTH);
+        let params = ArcResizeParams {
+            target_redundancy: 2,
+            storage_limit_ops: None,
+        };
+
+        // Too few peers so the arc grows
+        let arc = DhtArc::new(half, 1000);
+        let grown = arc.resize(&[full], 0, &params);
+        assert_eq!(grown.center_loc, arc.center_loc);
+        assert!(grown.half_length > arc.half_length);
+
+        // Alone on the network an arc grows until it's full
+        let mut arc = DhtArc::new(half, 1);
+        for _ in 0..100 {
+            arc = arc.resize(&[], 0, &params);
+        }
+        assert_eq!(arc, full);
+
+        // Plenty of peers so the arc shrinks
+        let shrunk = full.resize(&[full; 5], 0, &params);
+        assert!(shrunk.half_length < full.half_length);
+
+        // Within the target so the arc stays the same
+        assert_eq!(full.resize(&[full; 3], 0, &params), full);
+
+        // Holding twice the storage limit halves the arc
+        let params = ArcResizeParams {
+            target_redundancy: 2,
+            storage_limit_ops: Some(100),
+        };
+        let arc = DhtArc::new(half, 1000);
+        assert_eq!(arc.resize(&[], 200, &params), DhtArc::new(half, 500));
+
+        // An arc always holds its center
+        assert_eq!(arc.resize(&[], u64::MAX, &params), DhtArc::new(half, 1));
+    }
 }
@@ -14,6 +14,8 @@ fn init(_: ()) -> ExternResult<InitCallbackResult> {
         // empty access converts to unrestricted
         access: ().into(),
         functions,
+        curry_payloads: CurryPayloads::default(),
+        limits: CapGrantLimits::default(),
     })?;
 
     Ok(InitCallbackResult::Pass)
@@ -29,9 +31,11 @@ fn cap_grant_entry(secret: CapSecret) -> ExternResult<CapGrantEntry> {
     let this_zome = zome_info()?.zome_name;
     functions.insert((this_zome, "needs_cap_claim".into()));
     Ok(CapGrantEntry {
-        tag: "".into(),
+        tag: "needs_cap_claim".into(),
         access: secret.into(),
         functions,
+        curry_payloads: CurryPayloads::default(),
+        limits: CapGrantLimits::default(),
     })
 }
 
@@ -51,6 +55,21 @@ pub fn delete_cap_grant(header_hash: HeaderHash) -> ExternResult<HeaderHash> {
     hdk::prelude::delete_cap_grant(header_hash)
 }
 
+#[hdk_extern]
+fn list_cap_grants(query: CapQuery) -> ExternResult<Vec<CapGrantInfo>> {
+    capability_grants(query)
+}
+
+#[hdk_extern]
+fn list_cap_claims(query: CapQuery) -> ExternResult<Vec<CapClaimInfo>> {
+    capability_claims(query)
+}
+
+#[hdk_extern]
+fn cap_info(_: ()) -> ExternResult<CapabilityInfo> {
+    capability_info()
+}
+
 #[hdk_extern]
 fn get_entry(header_hash: HeaderHash) -> ExternResult<Option<Element>> {
     get(header_hash, GetOptions::latest())
@@ -94,6 +113,8 @@ fn send_assigned_cap_claim(agent: AgentPubKey) -> ExternResult<()> {
         access: (secret, agent.clone()).into(),
         functions,
         tag: tag.clone(),
+        curry_payloads: CurryPayloads::default(),
+        limits: CapGrantLimits::default(),
     })?;
 
     // send the assigned cap token
@@ -11,6 +11,8 @@ fn init(_: ()) -> ExternResult<InitCallbackResult> {
         // empty access converts to unrestricted
         access: ().into(),
         functions,
+        curry_payloads: CurryPayloads::default(),
+        limits: CapGrantLimits::default(),
     })?;
 
     Ok(InitCallbackResult::Pass)
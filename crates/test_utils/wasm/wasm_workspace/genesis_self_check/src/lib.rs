@@ -0,0 +1,11 @@
+use hdk::prelude::*;
+
+#[hdk_extern]
+fn genesis_self_check(data: GenesisSelfCheckData) -> ExternResult<ValidateCallbackResult> {
+    match data.membrane_proof {
+        Some(_) => Ok(ValidateCallbackResult::Valid),
+        None => Ok(ValidateCallbackResult::Invalid(
+            "a membrane proof is required".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,34 @@
+use hdk::prelude::*;
+
+#[hdk_entry(id = "tick")]
+struct Tick(u32);
+
+entry_defs![Tick::entry_def()];
+
+#[hdk_extern]
+fn schedule_ping(_: ()) -> ExternResult<()> {
+    hdk::prelude::schedule("ping", 0_u32, core::time::Duration::from_millis(1))
+}
+
+/// Commits a tick then schedules itself again until there are three ticks.
+#[hdk_extern]
+fn ping(count: u32) -> ExternResult<()> {
+    create_entry(&Tick(count))?;
+    if count < 2 {
+        hdk::prelude::schedule("ping", count + 1, core::time::Duration::from_millis(1))?;
+    }
+    Ok(())
+}
+
+#[hdk_extern]
+fn ticks(_: ()) -> ExternResult<Vec<Element>> {
+    query(
+        QueryFilter::new()
+            .entry_type(EntryType::App(AppEntryType::new(
+                entry_def_index!(Tick)?,
+                zome_info()?.zome_id,
+                EntryVisibility::Public,
+            )))
+            .include_entries(true),
+    )
+}
@@ -4,3 +4,8 @@ use hdk::prelude::*;
 fn sys_time(_: ()) -> ExternResult<core::time::Duration> {
     hdk::prelude::sys_time()
 }
+
+#[hdk_extern]
+fn sleep(duration: core::time::Duration) -> ExternResult<()> {
+    hdk::prelude::sleep(duration)
+}
@@ -9,6 +9,8 @@ fn set_access(_: ()) -> ExternResult<()> {
         // empty access converts to unrestricted
         access: ().into(),
         functions,
+        curry_payloads: CurryPayloads::default(),
+        limits: CapGrantLimits::default(),
     })?;
 
     Ok(())
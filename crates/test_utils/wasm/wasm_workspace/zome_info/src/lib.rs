@@ -4,3 +4,18 @@ use hdk::prelude::*;
 fn zome_info(_: ()) -> ExternResult<ZomeInfo> {
     hdk::prelude::zome_info()
 }
+
+#[hdk_extern]
+fn dna_info(_: ()) -> ExternResult<DnaInfo> {
+    hdk::prelude::dna_info()
+}
+
+#[hdk_extern]
+fn app_info(_: ()) -> ExternResult<AppInfo> {
+    hdk::prelude::app_info()
+}
+
+#[hdk_extern]
+fn call_info(_: ()) -> ExternResult<CallInfo> {
+    hdk::prelude::call_info()
+}